@@ -1,11 +1,38 @@
 use anyhow::Result;
 use base64::prelude::*;
-use ed25519_dalek::{Signature, Signer, Verifier};
+use ciborium::value::Value as CborValue;
+use ed25519_dalek::{Signature, Signer, Verifier, VerifyingKey};
+use rand::RngCore;
+use serde::Deserialize;
 use serde_json::json;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::common::{AppState, Attestation};
+use crate::common::{AppState, Attestation, NonceChallenge};
+
+/// How long an issued challenge nonce remains redeemable.
+pub const NONCE_TTL_SECONDS: i64 = 300;
+
+/// Issue a fresh RCAR-style challenge nonce: a random 32-byte value, hex
+/// encoded, persisted in `AppState` with a short TTL. The caller must echo
+/// this nonce back in its next `generate_upload_attestation` /
+/// `generate_operation_attestation` call; `verify_attestation_signature`
+/// rejects any attestation whose nonce wasn't issued here, has expired, or
+/// has already been consumed.
+pub fn generate_challenge(state: &Arc<AppState>) -> Result<String> {
+    let mut nonce_bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce_hex = hex::encode(nonce_bytes);
+
+    let issued_at = chrono::Utc::now().timestamp();
+    state.store_nonce_challenge(
+        nonce_hex.clone(),
+        NonceChallenge { issued_at, expires_at: issued_at + NONCE_TTL_SECONDS },
+    )?;
+
+    Ok(nonce_hex)
+}
 
 /// Generate attestation for file upload
 pub fn generate_upload_attestation(
@@ -14,10 +41,11 @@ pub fn generate_upload_attestation(
     file_hash: &[u8],
     file_name: &str,
     file_size: u64,
+    nonce: &str,
 ) -> Result<Attestation> {
     let attestation_id = Uuid::new_v4().to_string();
     let timestamp = chrono::Utc::now().timestamp();
-    
+
     // Create attestation data
     let attestation_data = json!({
         "file_id": file_id,
@@ -27,12 +55,13 @@ pub fn generate_upload_attestation(
         "operation": "upload",
         "timestamp": timestamp,
         "enclave_id": get_enclave_id(),
+        "nonce": nonce,
     });
-    
+
     // Serialize and sign
     let data_bytes = serde_json::to_vec(&attestation_data)?;
     let signature = sign_data(&state.signing_key, &data_bytes)?;
-    
+
     Ok(Attestation {
         id: attestation_id,
         file_id: file_id.to_string(),
@@ -51,10 +80,11 @@ pub fn generate_operation_attestation(
     file_hash: &[u8],
     operation: &str,
     metadata: Option<serde_json::Value>,
+    nonce: &str,
 ) -> Result<Attestation> {
     let attestation_id = Uuid::new_v4().to_string();
     let timestamp = chrono::Utc::now().timestamp();
-    
+
     // Create attestation data
     let mut attestation_data = json!({
         "file_id": file_id,
@@ -62,17 +92,18 @@ pub fn generate_operation_attestation(
         "operation": operation,
         "timestamp": timestamp,
         "enclave_id": get_enclave_id(),
+        "nonce": nonce,
     });
-    
+
     // Add custom metadata if provided
     if let Some(meta) = metadata {
         attestation_data["metadata"] = meta;
     }
-    
+
     // Serialize and sign
     let data_bytes = serde_json::to_vec(&attestation_data)?;
     let signature = sign_data(&state.signing_key, &data_bytes)?;
-    
+
     Ok(Attestation {
         id: attestation_id,
         file_id: file_id.to_string(),
@@ -89,14 +120,18 @@ fn sign_data(signing_key: &ed25519_dalek::SigningKey, data: &[u8]) -> Result<Sig
     Ok(signing_key.sign(data))
 }
 
-/// Verify attestation signature
+/// Verify attestation signature. In addition to the Ed25519 check, this
+/// confirms the attestation's `nonce` is one this enclave issued via
+/// `generate_challenge`, has not expired, and has not been used before —
+/// the Response/Attest half of the RCAR handshake. The nonce is consumed
+/// (single-use) regardless of whether the signature itself is valid.
 pub fn verify_attestation_signature(
     state: &Arc<AppState>,
     attestation: &Attestation,
 ) -> Result<bool> {
     // Recreate the data that was signed
     let data_bytes = serde_json::to_vec(&attestation.metadata)?;
-    
+
     // Convert signature bytes back to Signature type
     let signature = Signature::from_bytes(
         attestation.signature
@@ -104,9 +139,17 @@ pub fn verify_attestation_signature(
             .try_into()
             .map_err(|_| anyhow::anyhow!("Invalid signature length"))?
     );
-    
-    // Verify signature
-    Ok(state.verifying_key.verify(&data_bytes, &signature).is_ok())
+
+    let signature_valid = state.verifying_key.verify(&data_bytes, &signature).is_ok();
+
+    let nonce = attestation
+        .metadata
+        .get("nonce")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow::anyhow!("attestation is missing a challenge nonce"))?;
+    let nonce_valid = state.consume_nonce_challenge(nonce, chrono::Utc::now().timestamp())?;
+
+    Ok(signature_valid && nonce_valid)
 }
 
 /// Get enclave ID (would be derived from PCR values in production)
@@ -127,37 +170,301 @@ pub fn get_pcr_values() -> (Vec<u8>, Vec<u8>, Vec<u8>) {
     (pcr0, pcr1, pcr2)
 }
 
-/// Generate attestation document (simplified version)
+/// COSE algorithm identifier for Ed25519 (EdDSA), RFC 8152 section 8.2.
+const COSE_ALG_EDDSA: i128 = -8;
+
+/// The fields of a Nitro-style attestation document payload, decoded back
+/// out of a COSE_Sign1 document by [`verify_attestation_document`].
+#[derive(Debug, Clone)]
+pub struct AttestationDocument {
+    pub module_id: String,
+    pub timestamp: i64,
+    /// PCR index -> 48-byte SHA-384 measurement.
+    pub pcrs: BTreeMap<u32, Vec<u8>>,
+    pub certificate: Vec<u8>,
+    pub cabundle: Vec<Vec<u8>>,
+    pub public_key: Vec<u8>,
+    pub user_data: Option<Vec<u8>>,
+    pub nonce: Option<Vec<u8>>,
+}
+
+fn cbor_to_bytes(value: &CborValue) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(value, &mut bytes)
+        .map_err(|e| anyhow::anyhow!("failed to encode CBOR: {}", e))?;
+    Ok(bytes)
+}
+
+fn attestation_payload_to_cbor(doc: &AttestationDocument) -> CborValue {
+    let pcrs = doc
+        .pcrs
+        .iter()
+        .map(|(index, value)| (CborValue::Integer((*index as i128).into()), CborValue::Bytes(value.clone())))
+        .collect();
+
+    CborValue::Map(vec![
+        (CborValue::Text("module_id".into()), CborValue::Text(doc.module_id.clone())),
+        (CborValue::Text("timestamp".into()), CborValue::Integer((doc.timestamp as i128).into())),
+        (CborValue::Text("pcrs".into()), CborValue::Map(pcrs)),
+        (CborValue::Text("certificate".into()), CborValue::Bytes(doc.certificate.clone())),
+        (
+            CborValue::Text("cabundle".into()),
+            CborValue::Array(doc.cabundle.iter().cloned().map(CborValue::Bytes).collect()),
+        ),
+        (CborValue::Text("public_key".into()), CborValue::Bytes(doc.public_key.clone())),
+        (
+            CborValue::Text("user_data".into()),
+            doc.user_data.clone().map(CborValue::Bytes).unwrap_or(CborValue::Null),
+        ),
+        (
+            CborValue::Text("nonce".into()),
+            doc.nonce.clone().map(CborValue::Bytes).unwrap_or(CborValue::Null),
+        ),
+    ])
+}
+
+fn attestation_payload_from_cbor(value: &CborValue) -> Result<AttestationDocument> {
+    let entries = value
+        .as_map()
+        .ok_or_else(|| anyhow::anyhow!("attestation payload is not a CBOR map"))?;
+    let field = |name: &str| -> Option<&CborValue> {
+        entries.iter().find(|(k, _)| k.as_text() == Some(name)).map(|(_, v)| v)
+    };
+
+    let module_id = field("module_id")
+        .and_then(|v| v.as_text())
+        .ok_or_else(|| anyhow::anyhow!("missing module_id"))?
+        .to_string();
+    let timestamp = field("timestamp")
+        .and_then(|v| v.as_integer())
+        .ok_or_else(|| anyhow::anyhow!("missing timestamp"))?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("timestamp out of range"))?;
+
+    let mut pcrs = BTreeMap::new();
+    if let Some(CborValue::Map(entries)) = field("pcrs") {
+        for (k, v) in entries {
+            let index: u32 = k
+                .as_integer()
+                .ok_or_else(|| anyhow::anyhow!("PCR index is not an integer"))?
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("PCR index out of range"))?;
+            let bytes = v.as_bytes().ok_or_else(|| anyhow::anyhow!("PCR value is not a byte string"))?.clone();
+            pcrs.insert(index, bytes);
+        }
+    }
+
+    let certificate = field("certificate").and_then(|v| v.as_bytes()).cloned().unwrap_or_default();
+    let cabundle = match field("cabundle") {
+        Some(CborValue::Array(items)) => items
+            .iter()
+            .map(|v| v.as_bytes().cloned().ok_or_else(|| anyhow::anyhow!("cabundle entry is not a byte string")))
+            .collect::<Result<Vec<_>>>()?,
+        _ => Vec::new(),
+    };
+    let public_key = field("public_key")
+        .and_then(|v| v.as_bytes())
+        .ok_or_else(|| anyhow::anyhow!("missing public_key"))?
+        .clone();
+    let user_data = field("user_data").and_then(|v| v.as_bytes()).cloned();
+    let nonce = field("nonce").and_then(|v| v.as_bytes()).cloned();
+
+    Ok(AttestationDocument { module_id, timestamp, pcrs, certificate, cabundle, public_key, user_data, nonce })
+}
+
+/// Build the COSE `Sig_structure` (`["Signature1", protected, external_aad, payload]`)
+/// that the signature actually covers, per RFC 8152 section 4.4.
+fn cose_sig_structure(protected: &[u8], payload: &[u8]) -> Result<Vec<u8>> {
+    cbor_to_bytes(&CborValue::Array(vec![
+        CborValue::Text("Signature1".into()),
+        CborValue::Bytes(protected.to_vec()),
+        CborValue::Bytes(Vec::new()), // external_aad: none
+        CborValue::Bytes(payload.to_vec()),
+    ]))
+}
+
+/// Generate a Nitro-style attestation document, COSE_Sign1-encoded as CBOR:
+/// `[protected_headers, unprotected_headers, payload, signature]`. The
+/// payload carries `module_id`, `timestamp`, a `pcrs` map (index -> 48-byte
+/// SHA-384 measurement), `certificate`/`cabundle` (empty here — a real AWS
+/// Nitro enclave populates these from its hardware cert chain), the
+/// enclave's `public_key`, and caller-supplied `user_data`/`nonce`.
 pub fn generate_attestation_document(
     state: &Arc<AppState>,
     user_data: Option<Vec<u8>>,
+    nonce: Option<Vec<u8>>,
 ) -> Result<Vec<u8>> {
     let (pcr0, pcr1, pcr2) = get_pcr_values();
-    
-    let doc = json!({
-        "module_id": get_enclave_id(),
-        "timestamp": chrono::Utc::now().timestamp(),
-        "pcrs": {
-            "pcr0": hex::encode(&pcr0),
-            "pcr1": hex::encode(&pcr1),
-            "pcr2": hex::encode(&pcr2),
-        },
-        "public_key": hex::encode(state.verifying_key.as_bytes()),
-        "user_data": user_data.map(|d| base64::prelude::BASE64_STANDARD.encode(d)),
-    });
-    
-    let doc_bytes = serde_json::to_vec(&doc)?;
-    
-    // Sign the document
-    let signature = sign_data(&state.signing_key, &doc_bytes)?;
-    
-    // Create final attestation document
-    let final_doc = json!({
-        "document": base64::prelude::BASE64_STANDARD.encode(&doc_bytes),
-        "signature": hex::encode(signature.to_bytes()),
-    });
-    
-    Ok(serde_json::to_vec(&final_doc)?)
+    let mut pcrs = BTreeMap::new();
+    pcrs.insert(0, pcr0);
+    pcrs.insert(1, pcr1);
+    pcrs.insert(2, pcr2);
+
+    let doc = AttestationDocument {
+        module_id: get_enclave_id(),
+        timestamp: chrono::Utc::now().timestamp(),
+        pcrs,
+        certificate: Vec::new(),
+        cabundle: Vec::new(),
+        public_key: state.verifying_key.as_bytes().to_vec(),
+        user_data,
+        nonce,
+    };
+
+    let protected = cbor_to_bytes(&CborValue::Map(vec![(
+        CborValue::Integer(1.into()), // alg label, RFC 8152 section 3.1
+        CborValue::Integer(COSE_ALG_EDDSA.into()),
+    )]))?;
+    let unprotected = CborValue::Map(Vec::new());
+    let payload = cbor_to_bytes(&attestation_payload_to_cbor(&doc))?;
+
+    let sig_structure = cose_sig_structure(&protected, &payload)?;
+    let signature = sign_data(&state.signing_key, &sig_structure)?;
+
+    cbor_to_bytes(&CborValue::Array(vec![
+        CborValue::Bytes(protected),
+        unprotected,
+        CborValue::Bytes(payload),
+        CborValue::Bytes(signature.to_bytes().to_vec()),
+    ]))
+}
+
+/// Decode a COSE_Sign1 attestation document produced by
+/// [`generate_attestation_document`], rebuild the `Sig_structure`, verify
+/// the signature against the public key embedded in the payload, and return
+/// the parsed document (including PCRs) on success.
+pub fn verify_attestation_document(document: &[u8]) -> Result<AttestationDocument> {
+    let cose: CborValue = ciborium::de::from_reader(document)
+        .map_err(|e| anyhow::anyhow!("failed to decode COSE_Sign1 CBOR: {}", e))?;
+    let elements = cose.as_array().ok_or_else(|| anyhow::anyhow!("COSE_Sign1 is not a CBOR array"))?;
+    if elements.len() != 4 {
+        return Err(anyhow::anyhow!("COSE_Sign1 must have exactly 4 elements, got {}", elements.len()));
+    }
+
+    let protected = elements[0].as_bytes().ok_or_else(|| anyhow::anyhow!("protected header is not a byte string"))?;
+    let payload = elements[2].as_bytes().ok_or_else(|| anyhow::anyhow!("payload is not a byte string"))?;
+    let signature_bytes =
+        elements[3].as_bytes().ok_or_else(|| anyhow::anyhow!("signature is not a byte string"))?;
+
+    let payload_value: CborValue =
+        ciborium::de::from_reader(payload.as_slice()).map_err(|e| anyhow::anyhow!("failed to decode payload CBOR: {}", e))?;
+    let doc = attestation_payload_from_cbor(&payload_value)?;
+
+    let verifying_key = VerifyingKey::from_bytes(
+        doc.public_key.as_slice().try_into().map_err(|_| anyhow::anyhow!("public_key is not 32 bytes"))?,
+    )
+    .map_err(|e| anyhow::anyhow!("invalid embedded public key: {}", e))?;
+    let signature = Signature::from_bytes(
+        signature_bytes.as_slice().try_into().map_err(|_| anyhow::anyhow!("Invalid signature length"))?,
+    );
+
+    let sig_structure = cose_sig_structure(protected, payload)?;
+    verifying_key
+        .verify(&sig_structure, &signature)
+        .map_err(|_| anyhow::anyhow!("attestation document signature verification failed"))?;
+
+    Ok(doc)
+}
+
+/// What measurement(s) a deployer will accept for one PCR slot. Either the
+/// wildcard `"any"` (don't check this PCR at all) or a set of acceptable
+/// hex-encoded SHA-384 measurements — more than one entry lets a staged
+/// rollout trust both the outgoing and incoming enclave build at once.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum PcrRequirement {
+    Wildcard(AnyWildcard),
+    AllowedMeasurements(Vec<String>),
+}
+
+/// Helper so `PcrRequirement`'s untagged `Deserialize` can distinguish the
+/// bare string `"any"` from a one-element allowlist.
+#[derive(Debug, Clone)]
+pub struct AnyWildcard;
+
+impl<'de> Deserialize<'de> for AnyWildcard {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let marker = String::deserialize(deserializer)?;
+        if marker.eq_ignore_ascii_case("any") {
+            Ok(AnyWildcard)
+        } else {
+            Err(serde::de::Error::custom(format!("expected the wildcard \"any\", got \"{}\"", marker)))
+        }
+    }
+}
+
+impl PcrRequirement {
+    fn check(&self, index: u32, measured: &[u8]) -> Result<()> {
+        match self {
+            PcrRequirement::Wildcard(_) => Ok(()),
+            PcrRequirement::AllowedMeasurements(allowed) => {
+                let measured_hex = hex::encode(measured);
+                if allowed.iter().any(|candidate| candidate.eq_ignore_ascii_case(&measured_hex)) {
+                    Ok(())
+                } else {
+                    Err(anyhow::anyhow!(
+                        "PCR{} mismatch: measured {} is not in the allowed set {:?}",
+                        index,
+                        measured_hex,
+                        allowed
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// Deployer-configured policy for which enclave build(s) an attestation is
+/// allowed to claim to be. Loaded from TOML via [`AttestationPolicy::from_toml_str`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct AttestationPolicy {
+    pub expected_module_id: Option<String>,
+    pub pcr0: PcrRequirement,
+    pub pcr1: PcrRequirement,
+    pub pcr2: PcrRequirement,
+}
+
+impl AttestationPolicy {
+    /// Parse a policy from TOML, e.g.:
+    /// ```toml
+    /// expected_module_id = "satya-enclave-v1"
+    /// pcr0 = ["deadbeef...", "cafebabe..."]
+    /// pcr1 = "any"
+    /// pcr2 = ["01010101..."]
+    /// ```
+    pub fn from_toml_str(toml_str: &str) -> Result<Self> {
+        toml::from_str(toml_str).map_err(|e| anyhow::anyhow!("invalid attestation policy TOML: {}", e))
+    }
+}
+
+/// Verify `document`'s signature, then check its measurements against
+/// `policy`, rejecting with a precise error naming the PCR (or `module_id`)
+/// that failed. Returns the parsed document on success.
+pub fn verify_against_policy(document: &[u8], policy: &AttestationPolicy) -> Result<AttestationDocument> {
+    let doc = verify_attestation_document(document)?;
+
+    if let Some(expected) = &policy.expected_module_id {
+        if &doc.module_id != expected {
+            return Err(anyhow::anyhow!(
+                "module_id mismatch: expected \"{}\", got \"{}\"",
+                expected,
+                doc.module_id
+            ));
+        }
+    }
+
+    for (index, requirement) in [(0u32, &policy.pcr0), (1, &policy.pcr1), (2, &policy.pcr2)] {
+        let measured = doc
+            .pcrs
+            .get(&index)
+            .ok_or_else(|| anyhow::anyhow!("attestation document is missing PCR{}", index))?;
+        requirement.check(index, measured)?;
+    }
+
+    Ok(doc)
 }
 
 #[cfg(test)]
@@ -169,20 +476,22 @@ mod tests {
     #[tokio::test]
     async fn test_attestation_generation() {
         let state = Arc::new(AppState::new().await.unwrap());
-        
+
         let file_id = "test-file-123";
         let file_hash = b"test_hash";
         let file_name = "test.json";
         let file_size = 1024;
-        
+        let nonce = generate_challenge(&state).unwrap();
+
         let attestation = generate_upload_attestation(
             &state,
             file_id,
             file_hash,
             file_name,
             file_size,
+            &nonce,
         ).unwrap();
-        
+
         assert_eq!(attestation.file_id, file_id);
         assert_eq!(attestation.operation, "upload");
         assert!(!attestation.signature.is_empty());
@@ -191,27 +500,136 @@ mod tests {
     #[tokio::test]
     async fn test_signature_verification() {
         let state = Arc::new(AppState::new().await.unwrap());
-        
+
         let file_id = "test-file-456";
         let file_hash = b"another_hash";
         let file_name = "test2.csv";
         let file_size = 2048;
-        
+        let nonce = generate_challenge(&state).unwrap();
+
         let attestation = generate_upload_attestation(
             &state,
             file_id,
             file_hash,
             file_name,
             file_size,
+            &nonce,
         ).unwrap();
-        
+
         let is_valid = verify_attestation_signature(&state, &attestation).unwrap();
         assert!(is_valid);
-        
-        // Test with tampered attestation
+
+        // A second verification attempt must fail: the nonce was single-use
+        // and was already consumed above.
+        let is_valid_again = verify_attestation_signature(&state, &attestation).unwrap();
+        assert!(!is_valid_again);
+    }
+
+    #[tokio::test]
+    async fn test_signature_verification_detects_tampering() {
+        let state = Arc::new(AppState::new(Box::new(crate::storage::InMemoryStorage::new())).await.unwrap());
+        let nonce = generate_challenge(&state).unwrap();
+
+        let attestation = generate_upload_attestation(
+            &state,
+            "test-file-789",
+            b"yet_another_hash",
+            "test3.csv",
+            4096,
+            &nonce,
+        ).unwrap();
+
         let mut tampered = attestation.clone();
         tampered.metadata["file_size"] = json!(9999);
         let is_valid = verify_attestation_signature(&state, &tampered).unwrap();
         assert!(!is_valid);
     }
+
+    #[tokio::test]
+    async fn test_unknown_nonce_is_rejected() {
+        let state = Arc::new(AppState::new(Box::new(crate::storage::InMemoryStorage::new())).await.unwrap());
+
+        let attestation = generate_upload_attestation(
+            &state,
+            "test-file-999",
+            b"hash",
+            "test4.csv",
+            1,
+            "not-a-nonce-we-issued",
+        ).unwrap();
+
+        let is_valid = verify_attestation_signature(&state, &attestation).unwrap();
+        assert!(!is_valid);
+    }
+
+    #[tokio::test]
+    async fn test_attestation_document_round_trip() {
+        let state = Arc::new(AppState::new(Box::new(crate::storage::InMemoryStorage::new())).await.unwrap());
+        let nonce = b"challenge-nonce".to_vec();
+
+        let document =
+            generate_attestation_document(&state, Some(b"user-data".to_vec()), Some(nonce.clone())).unwrap();
+        let parsed = verify_attestation_document(&document).unwrap();
+
+        assert_eq!(parsed.module_id, get_enclave_id());
+        assert_eq!(parsed.pcrs.len(), 3);
+        assert_eq!(parsed.pcrs.get(&0).unwrap().len(), 48);
+        assert_eq!(parsed.public_key, state.verifying_key.as_bytes().to_vec());
+        assert_eq!(parsed.nonce, Some(nonce));
+    }
+
+    #[tokio::test]
+    async fn test_attestation_document_rejects_tampering() {
+        let state = Arc::new(AppState::new(Box::new(crate::storage::InMemoryStorage::new())).await.unwrap());
+        let mut document = generate_attestation_document(&state, None, None).unwrap();
+        let last = document.len() - 1;
+        document[last] ^= 0xFF;
+
+        assert!(verify_attestation_document(&document).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_policy_accepts_matching_pcrs() {
+        let state = Arc::new(AppState::new(Box::new(crate::storage::InMemoryStorage::new())).await.unwrap());
+        let document = generate_attestation_document(&state, None, None).unwrap();
+        let (pcr0, pcr1, _pcr2) = get_pcr_values();
+
+        let policy = AttestationPolicy::from_toml_str(&format!(
+            "expected_module_id = \"{}\"\npcr0 = [\"{}\"]\npcr1 = [\"{}\"]\npcr2 = \"any\"\n",
+            get_enclave_id(),
+            hex::encode(&pcr0),
+            hex::encode(&pcr1),
+        ))
+        .unwrap();
+
+        assert!(verify_against_policy(&document, &policy).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_policy_rejects_pcr_mismatch() {
+        let state = Arc::new(AppState::new(Box::new(crate::storage::InMemoryStorage::new())).await.unwrap());
+        let document = generate_attestation_document(&state, None, None).unwrap();
+
+        let policy = AttestationPolicy::from_toml_str(
+            "pcr0 = [\"0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000\"]\npcr1 = \"any\"\npcr2 = \"any\"\n",
+        )
+        .unwrap();
+
+        let err = verify_against_policy(&document, &policy).unwrap_err();
+        assert!(err.to_string().contains("PCR0"));
+    }
+
+    #[tokio::test]
+    async fn test_policy_rejects_module_id_mismatch() {
+        let state = Arc::new(AppState::new(Box::new(crate::storage::InMemoryStorage::new())).await.unwrap());
+        let document = generate_attestation_document(&state, None, None).unwrap();
+
+        let policy = AttestationPolicy::from_toml_str(
+            "expected_module_id = \"some-other-enclave\"\npcr0 = \"any\"\npcr1 = \"any\"\npcr2 = \"any\"\n",
+        )
+        .unwrap();
+
+        let err = verify_against_policy(&document, &policy).unwrap_err();
+        assert!(err.to_string().contains("module_id"));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,77 @@
+use anyhow::{anyhow, Result};
+use ed25519_dalek::{Signature, Signer, Verifier};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::common::AppState;
+
+/// Claims signed into a time-limited download grant: which file it
+/// authorizes, the file's expected hash (so a grant can't be replayed
+/// against different bytes if the file is ever overwritten), and an expiry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadGrantClaims {
+    pub file_id: String,
+    pub file_hash: String,
+    pub expires_at: i64,
+}
+
+/// A minted download grant: claims plus an Ed25519 signature over their
+/// canonical JSON encoding, using the same enclave key that signs
+/// attestations and capability tokens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadGrant {
+    pub claims: DownloadGrantClaims,
+    pub signature: Vec<u8>,
+}
+
+/// Mint a download grant for `file_id`, bound to `file_hash_hex`, valid for
+/// `ttl_seconds` from now.
+pub fn grant_download(
+    state: &Arc<AppState>,
+    file_id: &str,
+    file_hash_hex: &str,
+    ttl_seconds: i64,
+) -> Result<DownloadGrant> {
+    let claims = DownloadGrantClaims {
+        file_id: file_id.to_string(),
+        file_hash: file_hash_hex.to_string(),
+        expires_at: chrono::Utc::now().timestamp() + ttl_seconds,
+    };
+
+    let claims_bytes = serde_json::to_vec(&claims)?;
+    let signature = state.signing_key.sign(&claims_bytes);
+
+    Ok(DownloadGrant {
+        claims,
+        signature: signature.to_bytes().to_vec(),
+    })
+}
+
+/// Hex-encode a grant so it can travel as an opaque `/download/:token` path
+/// segment.
+pub fn encode_download_token(grant: &DownloadGrant) -> Result<String> {
+    let bytes = serde_json::to_vec(grant)?;
+    Ok(hex::encode(bytes))
+}
+
+/// Verify `token_hex` against `state`'s signing key and reject it if
+/// expired or tampered with.
+pub fn verify_download_token(state: &Arc<AppState>, token_hex: &str) -> Result<DownloadGrantClaims> {
+    let bytes = hex::decode(token_hex).map_err(|e| anyhow!("malformed download token: {}", e))?;
+    let grant: DownloadGrant =
+        serde_json::from_slice(&bytes).map_err(|e| anyhow!("malformed download token: {}", e))?;
+
+    let claims_bytes = serde_json::to_vec(&grant.claims)?;
+    let signature = Signature::from_slice(&grant.signature)
+        .map_err(|e| anyhow!("malformed download token signature: {}", e))?;
+    state
+        .verifying_key
+        .verify(&claims_bytes, &signature)
+        .map_err(|_| anyhow!("download token signature is invalid"))?;
+
+    if chrono::Utc::now().timestamp() > grant.claims.expires_at {
+        return Err(anyhow!("download token has expired"));
+    }
+
+    Ok(grant.claims)
+}
@@ -1,6 +1,6 @@
 use axum::{
     extract::{Multipart, Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::Json,
     routing::{get, post},
     Router,
@@ -8,6 +8,7 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
 use tracing::{error, info};
 use uuid::Uuid;
 
@@ -15,8 +16,13 @@ use crate::common::{AppState, Attestation, FileEntry, FileType};
 
 mod secure_storage;
 mod attestation;
+mod capability;
+mod download;
+mod preview;
+mod transparency;
 
 pub use attestation::*;
+pub use capability::{encode_token, mint_token, require_capability, Capability, CapabilityClaims, CapabilityToken};
 
 /// API error response
 #[derive(Debug, Serialize)]
@@ -51,17 +57,339 @@ pub struct AttestationRequest {
     pub file_id: String,
     pub operation: String,
     pub metadata: Option<serde_json::Value>,
+    /// Challenge nonce obtained from `/challenge`, proving this attestation
+    /// reflects a live enclave rather than a replayed recording.
+    pub nonce: String,
+}
+
+/// Response to a `/challenge` request: a fresh single-use nonce the caller
+/// must echo back in its next attestation request.
+#[derive(Debug, Serialize)]
+pub struct ChallengeResponse {
+    pub nonce: String,
+    pub expires_in_seconds: i64,
+}
+
+/// Supported assessment API schema version range
+#[derive(Debug, Serialize)]
+pub struct VersionResponse {
+    pub min_supported: u16,
+    pub current: u16,
 }
 
 /// Create application routes
 pub fn create_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/health", get(health_check))
+        .route("/version", get(get_version))
         .route("/upload", post(upload_file))
         .route("/file/:id", get(get_file))
+        .route("/challenge", post(create_challenge))
         .route("/attest", post(create_attestation))
         .route("/attestation/:id", get(get_attestation_handler))
         .route("/verify", post(verify_attestation))
+        .route("/auth/token", post(mint_token_handler))
+        .route("/auth/token/:id/revoke", post(revoke_token_handler))
+        .route("/file/:id/grant", post(grant_download_handler))
+        .route("/download/:token", get(download_file_handler))
+        .route("/blob/:hash", get(get_blob_handler))
+        .route("/file/:id/preview", get(get_file_preview_handler))
+        .route("/attestation/:id/proof", get(get_attestation_proof_handler))
+        .route("/log/root", get(get_log_root_handler))
+}
+
+/// Response to `GET /blob/:hash`: a content-addressed blob's size and how
+/// many logical file ids currently reference it, independent of any one
+/// file's metadata.
+#[derive(Debug, Serialize)]
+pub struct BlobMetadataResponse {
+    pub hash: String,
+    pub size: u64,
+    pub ref_count: u32,
+}
+
+/// Look up a content-addressed blob by its hex SHA-256, e.g. to check
+/// whether an upload would dedup against existing storage before sending it.
+async fn get_blob_handler(
+    State(state): State<Arc<AppState>>,
+    Path(hash): Path<String>,
+) -> Result<Json<BlobMetadataResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let metadata = state.get_blob_metadata(&hash).map_err(|e| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("Blob not found: {}", e),
+                code: 404,
+            }),
+        )
+    })?;
+
+    Ok(Json(BlobMetadataResponse {
+        hash: metadata.hash,
+        size: metadata.size,
+        ref_count: metadata.ref_count,
+    }))
+}
+
+/// Request body for `POST /file/:id/grant`.
+#[derive(Debug, Deserialize)]
+pub struct GrantDownloadRequest {
+    pub ttl_seconds: i64,
+}
+
+/// Response to `POST /file/:id/grant`: the signed, time-limited download
+/// token to pass to `GET /download/:token`.
+#[derive(Debug, Serialize)]
+pub struct GrantDownloadResponse {
+    pub download_token: String,
+    pub expires_at: i64,
+}
+
+/// Mint a short-lived, signed download token for a stored file.
+async fn grant_download_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Json(request): Json<GrantDownloadRequest>,
+) -> Result<Json<GrantDownloadResponse>, (StatusCode, Json<ErrorResponse>)> {
+    require_capability(&state, &headers, Capability::Read, &format!("file:{}", id))?;
+
+    let file = state.get_file(&id).map_err(|e| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("File not found: {}", e),
+                code: 404,
+            }),
+        )
+    })?;
+
+    let grant = download::grant_download(&state, &id, &hex::encode(&file.hash), request.ttl_seconds).map_err(|e| {
+        error!("Failed to mint download grant: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to mint download grant".to_string(),
+                code: 500,
+            }),
+        )
+    })?;
+
+    let download_token = download::encode_download_token(&grant).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to encode download token: {}", e),
+                code: 500,
+            }),
+        )
+    })?;
+
+    Ok(Json(GrantDownloadResponse {
+        download_token,
+        expires_at: grant.claims.expires_at,
+    }))
+}
+
+/// Best-effort content type for a stored file, used on the download
+/// response; downloads are otherwise opaque byte streams to the client.
+fn content_type_for(file_type: &FileType) -> &'static str {
+    match file_type {
+        FileType::Model => "application/octet-stream",
+        FileType::Dataset => "text/csv",
+        FileType::Document => "application/pdf",
+        FileType::Other(_) => "application/octet-stream",
+    }
+}
+
+/// Redeem a signed download grant and stream the file's bytes back out of
+/// the enclave, recording a `download` attestation in the process.
+async fn download_file_handler(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+) -> Result<impl axum::response::IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let claims = download::verify_download_token(&state, &token).map_err(|e| {
+        (
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse {
+                error: format!("Invalid download token: {}", e),
+                code: 403,
+            }),
+        )
+    })?;
+
+    let file = state.get_file(&claims.file_id).map_err(|e| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("File not found: {}", e),
+                code: 404,
+            }),
+        )
+    })?;
+
+    if hex::encode(&file.hash) != claims.file_hash {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse {
+                error: "Download token does not match the file's current contents".to_string(),
+                code: 403,
+            }),
+        ));
+    }
+
+    // The grant already proved liveness of the /grant request; mint a fresh
+    // challenge nonce for this attestation so it still goes through the same
+    // anti-replay machinery as every other one (the nonce is consumed when
+    // the attestation is verified).
+    let nonce = generate_challenge(&state).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to generate attestation nonce: {}", e),
+                code: 500,
+            }),
+        )
+    })?;
+
+    let attestation = generate_operation_attestation(&state, &claims.file_id, &file.hash, "download", None, &nonce)
+        .map_err(|e| {
+            error!("Failed to generate download attestation: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to generate attestation".to_string(),
+                    code: 500,
+                }),
+            )
+        })?;
+
+    let attestation_for_log = attestation.clone();
+    state.store_attestation(attestation).map_err(|e| {
+        error!("Failed to store attestation: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to store attestation".to_string(),
+                code: 500,
+            }),
+        )
+    })?;
+    transparency::record_attestation_leaf(&state, &attestation_for_log).map_err(|e| {
+        error!("Failed to append transparency log leaf: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to record attestation in transparency log".to_string(),
+                code: 500,
+            }),
+        )
+    })?;
+
+    let content_type = content_type_for(&file.file_type);
+    let response = axum::response::Response::builder()
+        .status(StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, content_type)
+        .header(
+            axum::http::header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file.name),
+        )
+        .body(axum::body::Body::from(file.data))
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Failed to build download response: {}", e),
+                    code: 500,
+                }),
+            )
+        })?;
+
+    Ok(response)
+}
+
+/// Request body for `POST /auth/token` — admin-only.
+#[derive(Debug, Deserialize)]
+pub struct MintTokenRequest {
+    pub resource: String,
+    pub capabilities: Vec<Capability>,
+    pub ttl_seconds: i64,
+}
+
+/// Response to `POST /auth/token`: the bearer token plus enough metadata to
+/// track or revoke it later.
+#[derive(Debug, Serialize)]
+pub struct MintTokenResponse {
+    pub token: String,
+    pub token_id: String,
+    pub expires_at: i64,
+}
+
+/// Mint a new capability token — requires an existing `Admin` capability.
+async fn mint_token_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Json(request): Json<MintTokenRequest>,
+) -> Result<Json<MintTokenResponse>, (StatusCode, Json<ErrorResponse>)> {
+    require_capability(&state, &headers, Capability::Admin, "*")?;
+
+    let token = mint_token(&state, &request.resource, request.capabilities, request.ttl_seconds)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Failed to mint token: {}", e),
+                    code: 500,
+                }),
+            )
+        })?;
+
+    let encoded = encode_token(&token).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to encode token: {}", e),
+                code: 500,
+            }),
+        )
+    })?;
+
+    Ok(Json(MintTokenResponse {
+        token: encoded,
+        token_id: token.claims.token_id,
+        expires_at: token.claims.expires_at,
+    }))
+}
+
+/// Revoke a previously-minted capability token by ID — requires an
+/// existing `Admin` capability.
+async fn revoke_token_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(token_id): Path<String>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    require_capability(&state, &headers, Capability::Admin, "*")?;
+
+    state.revoke_token(&token_id).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to revoke token: {}", e),
+                code: 500,
+            }),
+        )
+    })?;
+
+    Ok(Json(serde_json::json!({ "revoked": token_id })))
+}
+
+/// Report the supported assessment API schema version range, so integrators
+/// can negotiate before sending a request that might be rejected.
+async fn get_version() -> Json<VersionResponse> {
+    Json(VersionResponse {
+        min_supported: 1,
+        current: 2,
+    })
 }
 
 /// Health check endpoint
@@ -76,17 +404,171 @@ async fn health_check(State(state): State<Arc<AppState>>) -> Json<HealthCheckRes
     })
 }
 
+/// Issue a fresh RCAR-style attestation challenge nonce. Gated behind the
+/// same capability as `/upload` (`Upload` on `file:*`), so an anonymous
+/// caller can't flood `AppState::attestation_nonces`.
+async fn create_challenge(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<ChallengeResponse>, (StatusCode, Json<ErrorResponse>)> {
+    require_capability(&state, &headers, Capability::Upload, "file:*")?;
+
+    let nonce = generate_challenge(&state).map_err(|e| {
+        error!("Failed to generate challenge: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to generate challenge".to_string(),
+                code: 500,
+            }),
+        )
+    })?;
+
+    Ok(Json(ChallengeResponse { nonce, expires_in_seconds: NONCE_TTL_SECONDS }))
+}
+
+/// Default cap on upload size if `SATYA_MAX_UPLOAD_SIZE_BYTES` isn't set —
+/// 500 MiB, comfortably larger than today's target model files.
+const DEFAULT_MAX_UPLOAD_SIZE_BYTES: u64 = 500 * 1024 * 1024;
+
+fn max_upload_size_bytes() -> u64 {
+    std::env::var("SATYA_MAX_UPLOAD_SIZE_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_UPLOAD_SIZE_BYTES)
+}
+
+fn upload_staging_dir() -> std::path::PathBuf {
+    std::env::var("SATYA_UPLOAD_STAGING_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir().join("satya-uploads"))
+}
+
+/// A file streamed to disk by `stream_field_to_staged_file`: its path and
+/// the SHA-256 hash computed incrementally as each chunk arrived, so the
+/// caller never has to buffer the whole upload in memory just to hash it.
+struct StagedUpload {
+    path: std::path::PathBuf,
+    hash: Vec<u8>,
+}
+
+/// Stream a multipart field to disk one chunk at a time (`field.chunk()`),
+/// feeding each chunk into a running `Sha256` hash as it's written to a
+/// uniquely named temp file, then atomically rename the temp file to its
+/// content hash. Aborts with
+/// 413 as soon as `max_upload_size_bytes` is exceeded, rather than after
+/// buffering the whole body.
+async fn stream_field_to_staged_file(
+    field: &mut axum::extract::multipart::Field<'_>,
+) -> Result<StagedUpload, (StatusCode, Json<ErrorResponse>)> {
+    let staging_dir = upload_staging_dir();
+    tokio::fs::create_dir_all(&staging_dir).await.map_err(|e| {
+        error!("Failed to create upload staging dir: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to prepare upload storage".to_string(),
+                code: 500,
+            }),
+        )
+    })?;
+
+    let temp_path = staging_dir.join(format!("{}.part", Uuid::new_v4()));
+    let mut temp_file = tokio::fs::File::create(&temp_path).await.map_err(|e| {
+        error!("Failed to create temp upload file: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to prepare upload storage".to_string(),
+                code: 500,
+            }),
+        )
+    })?;
+
+    let max_size = max_upload_size_bytes();
+    let mut hasher = Sha256::new();
+    let mut bytes_written: u64 = 0;
+
+    while let Some(chunk) = field.chunk().await.map_err(|e| {
+        error!("Failed to read upload chunk: {}", e);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "Failed to read file".to_string(),
+                code: 400,
+            }),
+        )
+    })? {
+        bytes_written += chunk.len() as u64;
+        if bytes_written > max_size {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err((
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(ErrorResponse {
+                    error: format!("Upload exceeds max_upload_size of {} bytes", max_size),
+                    code: 413,
+                }),
+            ));
+        }
+
+        hasher.update(&chunk);
+        temp_file.write_all(&chunk).await.map_err(|e| {
+            error!("Failed to write upload chunk to disk: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "Failed to stage upload".to_string(),
+                    code: 500,
+                }),
+            )
+        })?;
+    }
+
+    temp_file.flush().await.map_err(|e| {
+        error!("Failed to flush staged upload: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to stage upload".to_string(),
+                code: 500,
+            }),
+        )
+    })?;
+    drop(temp_file);
+
+    let hash = hasher.finalize().to_vec();
+    let final_path = staging_dir.join(hex::encode(&hash));
+    tokio::fs::rename(&temp_path, &final_path).await.map_err(|e| {
+        error!("Failed to finalize staged upload: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to stage upload".to_string(),
+                code: 500,
+            }),
+        )
+    })?;
+
+    info!("📥 Staged upload of {} bytes to {}", bytes_written, final_path.display());
+
+    Ok(StagedUpload { path: final_path, hash })
+}
+
 /// Upload file to secure enclave storage
 async fn upload_file(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     mut multipart: Multipart,
 ) -> Result<Json<UploadResponse>, (StatusCode, Json<ErrorResponse>)> {
-    let mut file_data: Option<Vec<u8>> = None;
+    require_capability(&state, &headers, Capability::Upload, "file:*")?;
+
+    let mut staged_upload: Option<StagedUpload> = None;
     let mut file_name = String::new();
     let mut file_type = FileType::Other("unknown".to_string());
+    let mut nonce: Option<String> = None;
 
     // Process multipart form data
-    while let Some(field) = multipart
+    while let Some(mut field) = multipart
         .next_field()
         .await
         .map_err(|e| {
@@ -101,14 +583,14 @@ async fn upload_file(
         })?
     {
         let field_name = field.name().unwrap_or("").to_string();
-        
+
         match field_name.as_str() {
             "file" => {
                 file_name = field
                     .file_name()
                     .unwrap_or("unknown")
                     .to_string();
-                
+
                 // Determine file type from extension
                 if file_name.ends_with(".json") || file_name.ends_with(".model") {
                     file_type = FileType::Model;
@@ -117,23 +599,8 @@ async fn upload_file(
                 } else if file_name.ends_with(".pdf") || file_name.ends_with(".doc") {
                     file_type = FileType::Document;
                 }
-                
-                file_data = Some(
-                    field
-                        .bytes()
-                        .await
-                        .map_err(|e| {
-                            error!("Failed to read file bytes: {}", e);
-                            (
-                                StatusCode::BAD_REQUEST,
-                                Json(ErrorResponse {
-                                    error: "Failed to read file".to_string(),
-                                    code: 400,
-                                }),
-                            )
-                        })?
-                        .to_vec(),
-                );
+
+                staged_upload = Some(stream_field_to_staged_file(&mut field).await?);
             }
             "type" => {
                 let type_str = field.text().await.unwrap_or_default();
@@ -144,11 +611,14 @@ async fn upload_file(
                     other => FileType::Other(other.to_string()),
                 };
             }
+            "nonce" => {
+                nonce = Some(field.text().await.unwrap_or_default());
+            }
             _ => {}
         }
     }
 
-    let file_bytes = file_data.ok_or_else(|| {
+    let staged_upload = staged_upload.ok_or_else(|| {
         (
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
@@ -158,13 +628,45 @@ async fn upload_file(
         )
     })?;
 
-    // Calculate file hash
-    let mut hasher = Sha256::new();
-    hasher.update(&file_bytes);
-    let file_hash = hasher.finalize().to_vec();
+    let nonce = match nonce {
+        Some(nonce) => nonce,
+        None => {
+            // The file was already staged to disk; without this the staged
+            // file would leak forever on every no-nonce upload attempt.
+            let _ = tokio::fs::remove_file(&staged_upload.path).await;
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "No challenge nonce provided; call /challenge first".to_string(),
+                    code: 400,
+                }),
+            ));
+        }
+    };
+
+    // The hash was already computed incrementally while streaming chunks to
+    // the staged file, so this is the one and only time the bytes are read
+    // back into memory (as opposed to the old `.bytes()` + `.clone()` path,
+    // which held the upload in RAM twice).
+    let file_hash = staged_upload.hash.clone();
+    let file_bytes = tokio::fs::read(&staged_upload.path).await.map_err(|e| {
+        error!("Failed to read staged upload: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to read staged upload".to_string(),
+                code: 500,
+            }),
+        )
+    })?;
+    let _ = tokio::fs::remove_file(&staged_upload.path).await;
 
     // Create file entry
     let file_id = Uuid::new_v4().to_string();
+    // Models and datasets are never images; skip queuing decode attempts for
+    // them so only document/unclassified uploads (where an image is likely)
+    // pay for the background thumbnail attempt.
+    let should_generate_preview = matches!(file_type, FileType::Document | FileType::Other(_));
     let file_entry = FileEntry {
         id: file_id.clone(),
         name: file_name.clone(),
@@ -172,6 +674,7 @@ async fn upload_file(
         hash: file_hash.clone(),
         uploaded_at: chrono::Utc::now(),
         file_type,
+        preview_hash: None,
     };
 
     // Store file in enclave
@@ -188,6 +691,10 @@ async fn upload_file(
 
     info!("File uploaded: {} ({})", file_id, file_name);
 
+    if should_generate_preview {
+        preview::queue_preview_generation(state.clone(), file_id.clone(), file_bytes.clone());
+    }
+
     // Create automatic attestation for upload
     let attestation = generate_upload_attestation(
         &state,
@@ -195,6 +702,7 @@ async fn upload_file(
         &file_hash,
         &file_name,
         file_bytes.len() as u64,
+        &nonce,
     )
     .map_err(|e| {
         error!("Failed to generate attestation: {}", e);
@@ -208,6 +716,7 @@ async fn upload_file(
     })?;
 
     let attestation_id = attestation.id.clone();
+    let attestation_for_log = attestation.clone();
     state.store_attestation(attestation).map_err(|e| {
         error!("Failed to store attestation: {}", e);
         (
@@ -218,6 +727,16 @@ async fn upload_file(
             }),
         )
     })?;
+    transparency::record_attestation_leaf(&state, &attestation_for_log).map_err(|e| {
+        error!("Failed to append transparency log leaf: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to record attestation in transparency log".to_string(),
+                code: 500,
+            }),
+        )
+    })?;
 
     Ok(Json(UploadResponse {
         file_id,
@@ -232,8 +751,11 @@ async fn upload_file(
 /// Get file metadata (not the actual file data for security)
 async fn get_file(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Path(id): Path<String>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    require_capability(&state, &headers, Capability::Read, &format!("file:{}", id))?;
+
     let file = state.get_file(&id).map_err(|e| {
         (
             StatusCode::NOT_FOUND,
@@ -251,14 +773,76 @@ async fn get_file(
         "file_size": file.data.len(),
         "uploaded_at": file.uploaded_at,
         "file_type": format!("{:?}", file.file_type),
+        "has_preview": file.preview_hash.is_some(),
     })))
 }
 
+/// Fetch a file's generated preview thumbnail (PNG), if one exists yet.
+/// Generation runs asynchronously after upload, so a fresh upload may 404
+/// here for a short while before the thumbnail appears.
+async fn get_file_preview_handler(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<impl axum::response::IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    require_capability(&state, &headers, Capability::Read, &format!("file:{}", id))?;
+
+    let file = state.get_file(&id).map_err(|e| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: format!("File not found: {}", e),
+                code: 404,
+            }),
+        )
+    })?;
+
+    let preview_hash = file.preview_hash.ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse {
+                error: "No preview available for this file".to_string(),
+                code: 404,
+            }),
+        )
+    })?;
+
+    let thumbnail = state.get_blob(&hex::encode(&preview_hash)).map_err(|e| {
+        error!("Failed to load preview blob for file {}: {}", id, e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to load preview".to_string(),
+                code: 500,
+            }),
+        )
+    })?;
+
+    let response = axum::response::Response::builder()
+        .status(StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, "image/png")
+        .body(axum::body::Body::from(thumbnail))
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Failed to build preview response: {}", e),
+                    code: 500,
+                }),
+            )
+        })?;
+
+    Ok(response)
+}
+
 /// Create attestation for a file operation
 async fn create_attestation(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(request): Json<AttestationRequest>,
 ) -> Result<Json<Attestation>, (StatusCode, Json<ErrorResponse>)> {
+    require_capability(&state, &headers, Capability::Attest, &format!("file:{}", request.file_id))?;
+
     let file = state.get_file(&request.file_id).map_err(|e| {
         (
             StatusCode::NOT_FOUND,
@@ -275,6 +859,7 @@ async fn create_attestation(
         &file.hash,
         &request.operation,
         request.metadata,
+        &request.nonce,
     )
     .map_err(|e| {
         error!("Failed to generate attestation: {}", e);
@@ -298,10 +883,56 @@ async fn create_attestation(
             }),
         )
     })?;
+    transparency::record_attestation_leaf(&state, &attestation_clone).map_err(|e| {
+        error!("Failed to append transparency log leaf: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "Failed to record attestation in transparency log".to_string(),
+                code: 500,
+            }),
+        )
+    })?;
 
     Ok(Json(attestation_clone))
 }
 
+/// Prove a stored attestation is included in the transparency log: its
+/// position, the audit path from its leaf to the root, and a freshly signed
+/// root to check that path against. Public like `get_attestation_handler` —
+/// a transparency log's whole point is independent, unprivileged audit.
+async fn get_attestation_proof_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<transparency::InclusionProof>, (StatusCode, Json<ErrorResponse>)> {
+    transparency::build_inclusion_proof(&state, &id)
+        .map(Json)
+        .map_err(|e| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ErrorResponse {
+                    error: format!("No inclusion proof available: {}", e),
+                    code: 404,
+                }),
+            )
+        })
+}
+
+/// The transparency log's current signed root and size.
+async fn get_log_root_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<transparency::SignedLogRoot>, (StatusCode, Json<ErrorResponse>)> {
+    transparency::current_signed_root(&state).map(Json).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Failed to compute log root: {}", e),
+                code: 500,
+            }),
+        )
+    })
+}
+
 /// Get attestation by ID
 async fn get_attestation_handler(
     State(state): State<Arc<AppState>>,
@@ -322,8 +953,11 @@ async fn get_attestation_handler(
 /// Verify an attestation signature
 async fn verify_attestation(
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
     Json(attestation): Json<Attestation>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<ErrorResponse>)> {
+    require_capability(&state, &headers, Capability::Verify, &format!("file:{}", attestation.file_id))?;
+
     let is_valid = verify_attestation_signature(&state, &attestation)
         .map_err(|e| {
             error!("Failed to verify attestation: {}", e);
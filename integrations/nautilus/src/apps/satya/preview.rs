@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use image::imageops::FilterType;
+use tracing::{info, warn};
+
+use crate::common::AppState;
+
+/// Max width/height of a generated preview thumbnail, in pixels. Aspect
+/// ratio is preserved; the longer side is scaled down to this bound.
+const MAX_THUMBNAIL_DIMENSION: u32 = 256;
+
+/// Best-effort thumbnail generation: decode `data` as an image and
+/// downscale it so neither dimension exceeds `MAX_THUMBNAIL_DIMENSION`,
+/// re-encoding as PNG. Returns `None` for content the `image` crate can't
+/// decode (PDFs, model weights, CSVs, ...) — those simply have no preview.
+fn generate_thumbnail(data: &[u8]) -> Option<Vec<u8>> {
+    let decoded = image::load_from_memory(data).ok()?;
+    let thumbnail = decoded.resize(MAX_THUMBNAIL_DIMENSION, MAX_THUMBNAIL_DIMENSION, FilterType::Triangle);
+
+    let mut encoded = std::io::Cursor::new(Vec::new());
+    thumbnail.write_to(&mut encoded, image::ImageFormat::Png).ok()?;
+    Some(encoded.into_inner())
+}
+
+/// Spawn background thumbnail generation for a freshly uploaded file so the
+/// upload response doesn't wait on image decoding/resizing. Once generated,
+/// the thumbnail is stored as its own content-addressed blob and its hash
+/// recorded against `file_id`; `GET /file/:id/preview` has nothing to serve
+/// until this completes, and simply never will if `data` didn't decode.
+pub fn queue_preview_generation(state: Arc<AppState>, file_id: String, data: Vec<u8>) {
+    tokio::spawn(async move {
+        let Some(thumbnail) = generate_thumbnail(&data) else {
+            return;
+        };
+
+        let preview_hash = match state.store_blob(thumbnail) {
+            Ok(hash) => hash,
+            Err(e) => {
+                warn!("Failed to store preview blob for file {}: {}", file_id, e);
+                return;
+            }
+        };
+
+        if let Err(e) = state.set_preview_hash(&file_id, preview_hash) {
+            warn!("Failed to record preview for file {}: {}", file_id, e);
+            return;
+        }
+
+        info!("🖼️  Generated preview for file {}", file_id);
+    });
+}
@@ -1,12 +1,40 @@
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
 use anyhow::Result;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use tracing::debug;
 
+/// Length in bytes of the AES-256-GCM nonce prepended to every ciphertext.
+const NONCE_LEN: usize = 12;
+/// Length in bytes of the random per-file salt mixed into `generate_storage_key`.
+const STORAGE_KEY_SALT_LEN: usize = 16;
+/// Length in bytes of a data-encryption key (DEK) — AES-256.
+const DEK_LEN: usize = 32;
+
+/// Which kind of master key protects per-file data-encryption keys (DEKs).
+/// Selects the algorithm [`wrap_dek`]/[`unwrap_dek`] use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MasterKeyMode {
+    /// `master_key` is used directly as an AES-256-GCM key-wrapping key.
+    /// Simple and fast, but anyone holding the master key can unwrap every
+    /// DEK it ever wrapped.
+    Symmetric,
+    /// `master_key` is instead a 32-byte X25519 public key. Each wrap does a
+    /// fresh ephemeral ECDH, so the DEK is sealed to whoever holds the
+    /// matching private key — the master key itself never needs to exist on
+    /// the encrypting side.
+    Asymmetric,
+}
+
 /// Secure storage configuration
 pub struct SecureStorageConfig {
     pub max_file_size: usize,
     pub allowed_extensions: Vec<String>,
     pub encryption_enabled: bool,
+    /// Which master-key scheme protects per-file DEKs; see [`MasterKeyMode`].
+    pub master_key_mode: MasterKeyMode,
 }
 
 impl Default for SecureStorageConfig {
@@ -22,6 +50,7 @@ impl Default for SecureStorageConfig {
                 "pdf".to_string(),
             ],
             encryption_enabled: true,
+            master_key_mode: MasterKeyMode::Symmetric,
         }
     }
 }
@@ -55,36 +84,161 @@ pub fn validate_file(
     Ok(())
 }
 
-/// Encrypt file data (simplified for now, would use AES-256-GCM in production)
-pub fn encrypt_file_data(data: &[u8], key: &[u8]) -> Result<Vec<u8>> {
-    // In production, this would use proper encryption like AES-256-GCM
-    // For now, we'll just XOR with a derived key for demonstration
-    
-    let mut encrypted = Vec::with_capacity(data.len());
-    let key_len = key.len();
-    
-    for (i, byte) in data.iter().enumerate() {
-        encrypted.push(byte ^ key[i % key_len]);
-    }
-    
+/// Encrypt file data with AES-256-GCM. `key` must be exactly 32 bytes
+/// (see [`generate_storage_key`]). `aad` is optional associated data
+/// (e.g. the file id or file hash) bound to the ciphertext but not
+/// encrypted — decryption must be given the same `aad` or the tag check
+/// fails. Returns `nonce || ciphertext || tag`.
+pub fn encrypt_file_data(data: &[u8], key: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, Payload { msg: data, aad })
+        .map_err(|e| anyhow::anyhow!("AES-256-GCM encryption failed: {}", e))?;
+
+    let mut encrypted = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    encrypted.extend_from_slice(&nonce_bytes);
+    encrypted.extend_from_slice(&ciphertext);
+
     debug!("Encrypted {} bytes of data", data.len());
     Ok(encrypted)
 }
 
-/// Decrypt file data
-pub fn decrypt_file_data(encrypted_data: &[u8], key: &[u8]) -> Result<Vec<u8>> {
-    // Since we're using XOR for demonstration, decryption is the same as encryption
-    encrypt_file_data(encrypted_data, key)
+/// Decrypt data produced by [`encrypt_file_data`]. `aad` must match the
+/// value passed at encryption time. Fails loudly (returns `Err`) if the
+/// nonce is missing or the GCM authentication tag does not verify —
+/// either the key is wrong, `aad` doesn't match, or the ciphertext was
+/// tampered with.
+pub fn decrypt_file_data(encrypted_data: &[u8], key: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    if encrypted_data.len() < NONCE_LEN {
+        return Err(anyhow::anyhow!(
+            "encrypted data is too short to contain a nonce"
+        ));
+    }
+    let (nonce_bytes, ciphertext) = encrypted_data.split_at(NONCE_LEN);
+    let nonce = Nonce::from_slice(nonce_bytes);
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    cipher
+        .decrypt(nonce, Payload { msg: ciphertext, aad })
+        .map_err(|e| anyhow::anyhow!("AES-256-GCM authentication failed: {}", e))
 }
 
-/// Generate a deterministic storage key from file hash
-pub fn generate_storage_key(file_hash: &[u8]) -> Vec<u8> {
+/// Derive a 32-byte storage key from a file's hash and a random per-file
+/// `salt`, so two files with identical contents never share a key. Callers
+/// should generate a fresh random salt per file (e.g. with `rand`) and
+/// store it alongside the encrypted blob — it's needed again to decrypt.
+pub fn generate_storage_key(file_hash: &[u8], salt: &[u8]) -> Vec<u8> {
     let mut hasher = Sha256::new();
     hasher.update(b"SATYA_STORAGE_KEY_");
+    hasher.update(salt);
     hasher.update(file_hash);
     hasher.finalize().to_vec()
 }
 
+/// Generate a fresh random salt for [`generate_storage_key`].
+pub fn generate_storage_key_salt() -> Vec<u8> {
+    let mut salt = vec![0u8; STORAGE_KEY_SALT_LEN];
+    rand::thread_rng().fill_bytes(&mut salt);
+    salt
+}
+
+/// A data-encryption key (DEK), wrapped under the deployment's master key so
+/// it can be stored alongside the encrypted blob (in file metadata) instead
+/// of needing to be re-derived. Produced by [`wrap_dek`], consumed by
+/// [`unwrap_dek`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedDek {
+    pub mode: MasterKeyMode,
+    /// Present only in [`MasterKeyMode::Asymmetric`]: the ephemeral X25519
+    /// public key used for the ECDH that derived this wrap's
+    /// key-encryption key.
+    pub ephemeral_public_key: Option<[u8; 32]>,
+    /// `nonce || ciphertext || tag` from sealing the DEK itself, in the
+    /// format returned by [`encrypt_file_data`].
+    pub wrapped_key: Vec<u8>,
+}
+
+/// Generate a fresh random 256-bit data-encryption key (DEK) for one file.
+/// Each file gets its own DEK, which is what makes rotating or sharing
+/// access to individual files possible without re-encrypting anything else.
+pub fn generate_dek() -> [u8; DEK_LEN] {
+    let mut dek = [0u8; DEK_LEN];
+    rand::thread_rng().fill_bytes(&mut dek);
+    dek
+}
+
+/// Wrap `dek` under the deployment's master key, per `mode`:
+/// - [`MasterKeyMode::Symmetric`]: `master_key` is the 32-byte AES-256-GCM
+///   key-wrapping key directly.
+/// - [`MasterKeyMode::Asymmetric`]: `master_key` is the recipient's 32-byte
+///   X25519 public key; a fresh ephemeral ECDH derives the key-encryption
+///   key, so only the holder of the matching private key can unwrap it.
+///
+/// `aad` binds the wrap to its file (e.g. the `file_id`), the same way
+/// [`encrypt_file_data`] binds the payload ciphertext.
+pub fn wrap_dek(dek: &[u8; DEK_LEN], mode: MasterKeyMode, master_key: &[u8], aad: &[u8]) -> Result<WrappedDek> {
+    match mode {
+        MasterKeyMode::Symmetric => {
+            let wrapped_key = encrypt_file_data(dek, master_key, aad)?;
+            Ok(WrappedDek { mode, ephemeral_public_key: None, wrapped_key })
+        }
+        MasterKeyMode::Asymmetric => {
+            let recipient_pk_bytes: [u8; 32] = master_key
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("asymmetric master key must be a 32-byte X25519 public key"))?;
+
+            let ephemeral_secret = x25519_dalek::EphemeralSecret::random_from_rng(rand::thread_rng());
+            let ephemeral_public_key = x25519_dalek::PublicKey::from(&ephemeral_secret);
+            let shared_secret =
+                ephemeral_secret.diffie_hellman(&x25519_dalek::PublicKey::from(recipient_pk_bytes));
+            let kek = derive_kek(shared_secret.as_bytes(), aad);
+
+            let wrapped_key = encrypt_file_data(dek, &kek, aad)?;
+            Ok(WrappedDek { mode, ephemeral_public_key: Some(ephemeral_public_key.to_bytes()), wrapped_key })
+        }
+    }
+}
+
+/// Inverse of [`wrap_dek`]: recovers the DEK given the master key (the
+/// symmetric key-wrapping key, or the recipient's X25519 private key in
+/// asymmetric mode) and the same `aad` passed to `wrap_dek`.
+pub fn unwrap_dek(wrapped: &WrappedDek, master_key: &[u8], aad: &[u8]) -> Result<Vec<u8>> {
+    match wrapped.mode {
+        MasterKeyMode::Symmetric => decrypt_file_data(&wrapped.wrapped_key, master_key, aad),
+        MasterKeyMode::Asymmetric => {
+            let ephemeral_public_key = wrapped
+                .ephemeral_public_key
+                .ok_or_else(|| anyhow::anyhow!("asymmetric WrappedDek is missing its ephemeral public key"))?;
+            let recipient_sk_bytes: [u8; 32] = master_key
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("asymmetric master key must be a 32-byte X25519 private key"))?;
+
+            let recipient_secret = x25519_dalek::StaticSecret::from(recipient_sk_bytes);
+            let shared_secret =
+                recipient_secret.diffie_hellman(&x25519_dalek::PublicKey::from(ephemeral_public_key));
+            let kek = derive_kek(shared_secret.as_bytes(), aad);
+
+            decrypt_file_data(&wrapped.wrapped_key, &kek, aad)
+        }
+    }
+}
+
+/// Fold an X25519 shared secret and the wrap's `aad` into a 32-byte
+/// key-encryption key, binding the key-wrap layer to its file the same way
+/// the payload layer is bound.
+fn derive_kek(shared_secret: &[u8], aad: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(b"SATYA_DEK_WRAP_KEK_");
+    hasher.update(shared_secret);
+    hasher.update(aad);
+    hasher.finalize().to_vec()
+}
+
 /// Secure deletion of sensitive data
 pub fn secure_delete(data: &mut [u8]) {
     // Overwrite with random data multiple times
@@ -99,7 +253,11 @@ pub fn secure_delete(data: &mut [u8]) {
     }
 }
 
-/// File integrity verification
+/// File integrity verification by hash comparison. Note: for data encrypted
+/// with [`encrypt_file_data`], this is redundant — AES-256-GCM's tag already
+/// authenticates the ciphertext, so a successful `decrypt_file_data` call is
+/// proof of integrity on its own. This function remains useful for plaintext
+/// or unencrypted storage paths.
 pub fn verify_file_integrity(file_data: &[u8], expected_hash: &[u8]) -> bool {
     let mut hasher = Sha256::new();
     hasher.update(file_data);
@@ -127,14 +285,88 @@ mod tests {
     fn test_encryption_decryption() {
         let data = b"Hello, Satya Marketplace!";
         let key = b"test_encryption_key_32_bytes_ok!";
-        
-        let encrypted = encrypt_file_data(data, key).unwrap();
-        assert_ne!(&encrypted[..], data);
-        
-        let decrypted = decrypt_file_data(&encrypted, key).unwrap();
+        let aad = b"file-id-123";
+
+        let encrypted = encrypt_file_data(data, key, aad).unwrap();
+        assert_ne!(&encrypted[NONCE_LEN..], &data[..]);
+
+        let decrypted = decrypt_file_data(&encrypted, key, aad).unwrap();
         assert_eq!(&decrypted[..], data);
     }
 
+    #[test]
+    fn test_decryption_fails_on_wrong_aad() {
+        let data = b"Hello, Satya Marketplace!";
+        let key = b"test_encryption_key_32_bytes_ok!";
+
+        let encrypted = encrypt_file_data(data, key, b"file-id-123").unwrap();
+        assert!(decrypt_file_data(&encrypted, key, b"file-id-456").is_err());
+    }
+
+    #[test]
+    fn test_decryption_fails_on_tampered_ciphertext() {
+        let data = b"Hello, Satya Marketplace!";
+        let key = b"test_encryption_key_32_bytes_ok!";
+        let aad = b"file-id-123";
+
+        let mut encrypted = encrypt_file_data(data, key, aad).unwrap();
+        let last = encrypted.len() - 1;
+        encrypted[last] ^= 0xFF;
+        assert!(decrypt_file_data(&encrypted, key, aad).is_err());
+    }
+
+    #[test]
+    fn test_storage_key_differs_per_salt() {
+        let file_hash = Sha256::digest(b"identical file contents").to_vec();
+        let salt_a = generate_storage_key_salt();
+        let salt_b = generate_storage_key_salt();
+
+        let key_a = generate_storage_key(&file_hash, &salt_a);
+        let key_b = generate_storage_key(&file_hash, &salt_b);
+        assert_ne!(key_a, key_b);
+    }
+
+    #[test]
+    fn test_wrap_unwrap_dek_symmetric() {
+        let dek = generate_dek();
+        let master_key = generate_dek(); // any 32-byte key will do
+        let aad = b"file-id-abc";
+
+        let wrapped = wrap_dek(&dek, MasterKeyMode::Symmetric, &master_key, aad).unwrap();
+        assert!(wrapped.ephemeral_public_key.is_none());
+
+        let unwrapped = unwrap_dek(&wrapped, &master_key, aad).unwrap();
+        assert_eq!(unwrapped, dek.to_vec());
+    }
+
+    #[test]
+    fn test_wrap_unwrap_dek_asymmetric() {
+        let dek = generate_dek();
+        let aad = b"file-id-xyz";
+
+        let recipient_sk = x25519_dalek::StaticSecret::from(generate_dek());
+        let recipient_pk = x25519_dalek::PublicKey::from(&recipient_sk);
+
+        let wrapped = wrap_dek(&dek, MasterKeyMode::Asymmetric, recipient_pk.as_bytes(), aad).unwrap();
+        assert!(wrapped.ephemeral_public_key.is_some());
+
+        let unwrapped = unwrap_dek(&wrapped, &recipient_sk.to_bytes(), aad).unwrap();
+        assert_eq!(unwrapped, dek.to_vec());
+    }
+
+    #[test]
+    fn test_unwrap_dek_fails_for_wrong_recipient() {
+        let dek = generate_dek();
+        let aad = b"file-id-xyz";
+
+        let recipient_sk = x25519_dalek::StaticSecret::from(generate_dek());
+        let recipient_pk = x25519_dalek::PublicKey::from(&recipient_sk);
+        let wrapped = wrap_dek(&dek, MasterKeyMode::Asymmetric, recipient_pk.as_bytes(), aad).unwrap();
+
+        let wrong_sk = x25519_dalek::StaticSecret::from(generate_dek());
+        assert!(unwrap_dek(&wrapped, &wrong_sk.to_bytes(), aad).is_err());
+    }
+
     #[test]
     fn test_file_integrity() {
         let data = b"Test file content";
@@ -0,0 +1,284 @@
+use std::sync::Arc;
+
+use ed25519_dalek::Signer;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use anyhow::{anyhow, Result};
+
+use crate::common::{AppState, Attestation};
+
+/// RFC 6962 domain separation prefixes, so a leaf hash can never collide
+/// with an internal node hash over the same bytes (the second-preimage
+/// attack the scheme is named for).
+const LEAF_HASH_PREFIX: u8 = 0x00;
+const NODE_HASH_PREFIX: u8 = 0x01;
+
+fn leaf_hash(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_HASH_PREFIX]);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_HASH_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Canonical bytes hashed into a transparency-log leaf for an attestation.
+/// The attestation's own signature already binds its fields, so the leaf
+/// only needs to commit to the attestation's identity and that signature —
+/// the log vouches for *which signed attestations exist and in what order*,
+/// not for their content a second time.
+fn attestation_leaf_data(attestation: &Attestation) -> Vec<u8> {
+    let mut data = Vec::with_capacity(attestation.id.len() + attestation.signature.len());
+    data.extend_from_slice(attestation.id.as_bytes());
+    data.extend_from_slice(&attestation.signature);
+    data
+}
+
+/// The largest power of two strictly less than `n`, per the RFC 6962
+/// `MTH`/`PATH` recursive definitions (`n` is assumed `>= 2`).
+fn split_point(n: usize) -> usize {
+    let mut k = 1;
+    while 2 * k < n {
+        k *= 2;
+    }
+    k
+}
+
+/// RFC 6962 `MTH(D[n])`: the Merkle tree hash over `leaves`.
+fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    match leaves.len() {
+        0 => Sha256::digest([]).into(),
+        1 => leaves[0],
+        n => {
+            let k = split_point(n);
+            let left = merkle_root(&leaves[..k]);
+            let right = merkle_root(&leaves[k..]);
+            node_hash(&left, &right)
+        }
+    }
+}
+
+/// RFC 6962 `PATH(m, D[n])`: the audit path proving `leaves[m]` is included
+/// in `MTH(leaves)`, ordered from the leaf's immediate sibling up to the
+/// sibling of the root's top-level split.
+fn audit_path(index: usize, leaves: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    let n = leaves.len();
+    if n <= 1 {
+        return Vec::new();
+    }
+
+    let k = split_point(n);
+    if index < k {
+        let mut path = audit_path(index, &leaves[..k]);
+        path.push(merkle_root(&leaves[k..]));
+        path
+    } else {
+        let mut path = audit_path(index - k, &leaves[k..]);
+        path.push(merkle_root(&leaves[..k]));
+        path
+    }
+}
+
+/// Recompute the root a `leaf` at `index` (out of `size` total leaves)
+/// would produce given its `audit_path`, mirroring `audit_path`'s recursion
+/// so each sibling is combined on the correct side.
+fn root_from_audit_path(leaf: [u8; 32], index: usize, size: usize, path: &[[u8; 32]]) -> [u8; 32] {
+    if size <= 1 {
+        return leaf;
+    }
+
+    let k = split_point(size);
+    let sibling = path[path.len() - 1];
+    let rest = &path[..path.len() - 1];
+
+    if index < k {
+        let left = root_from_audit_path(leaf, index, k, rest);
+        node_hash(&left, &sibling)
+    } else {
+        let right = root_from_audit_path(leaf, index - k, size - k, rest);
+        node_hash(&sibling, &right)
+    }
+}
+
+/// A tree root signed by the enclave, attesting to the log's state at the
+/// moment it was computed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedLogRoot {
+    pub tree_size: u64,
+    pub root_hash: String,
+    pub signature: Vec<u8>,
+}
+
+fn sign_root(state: &Arc<AppState>, tree_size: u64, root: &[u8; 32]) -> SignedLogRoot {
+    let mut message = Vec::with_capacity(8 + 32);
+    message.extend_from_slice(&tree_size.to_be_bytes());
+    message.extend_from_slice(root);
+    let signature = state.signing_key.sign(&message);
+
+    SignedLogRoot {
+        tree_size,
+        root_hash: hex::encode(root),
+        signature: signature.to_bytes().to_vec(),
+    }
+}
+
+/// Append `attestation` as the next leaf of the transparency log. Called
+/// right after every successful `AppState::store_attestation`, so the log's
+/// order always matches attestation storage order.
+pub fn record_attestation_leaf(state: &Arc<AppState>, attestation: &Attestation) -> Result<()> {
+    let leaf = leaf_hash(&attestation_leaf_data(attestation));
+    state.append_log_leaf(&attestation.id, leaf.to_vec())?;
+    Ok(())
+}
+
+/// An inclusion proof for one attestation: its position in the log, the
+/// tree size it was proven against, the audit path (sibling hashes, hex
+/// encoded, leaf to root), and the signed root the path recomputes to.
+#[derive(Debug, Clone, Serialize)]
+pub struct InclusionProof {
+    pub leaf_index: u64,
+    pub audit_path: Vec<String>,
+    pub signed_root: SignedLogRoot,
+}
+
+fn load_leaves(state: &Arc<AppState>) -> Result<Vec<(String, [u8; 32])>> {
+    state
+        .get_log_entries()?
+        .into_iter()
+        .map(|(id, hash)| {
+            let leaf: [u8; 32] = hash
+                .as_slice()
+                .try_into()
+                .map_err(|_| anyhow!("stored leaf hash for {} is not 32 bytes", id))?;
+            Ok((id, leaf))
+        })
+        .collect()
+}
+
+/// Build an inclusion proof for `attestation_id`: its audit path plus a
+/// freshly signed root over the log's current state. A client recomputes
+/// the leaf hash from the attestation it already has, walks the audit path
+/// with `leaf_index`/`tree_size`, and checks the result matches
+/// `signed_root` under the enclave's public key — proving inclusion without
+/// trusting the server's own lookup.
+pub fn build_inclusion_proof(state: &Arc<AppState>, attestation_id: &str) -> Result<InclusionProof> {
+    let entries = load_leaves(state)?;
+    let index = entries
+        .iter()
+        .position(|(id, _)| id == attestation_id)
+        .ok_or_else(|| anyhow!("no transparency log entry for attestation {}", attestation_id))?;
+
+    let leaves: Vec<[u8; 32]> = entries.iter().map(|(_, leaf)| *leaf).collect();
+    let path = audit_path(index, &leaves);
+    let root = merkle_root(&leaves);
+
+    Ok(InclusionProof {
+        leaf_index: index as u64,
+        audit_path: path.iter().map(hex::encode).collect(),
+        signed_root: sign_root(state, leaves.len() as u64, &root),
+    })
+}
+
+/// The log's current signed root and size.
+pub fn current_signed_root(state: &Arc<AppState>) -> Result<SignedLogRoot> {
+    let leaves: Vec<[u8; 32]> = load_leaves(state)?.into_iter().map(|(_, leaf)| leaf).collect();
+    let root = merkle_root(&leaves);
+    Ok(sign_root(state, leaves.len() as u64, &root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves_of(values: &[&[u8]]) -> Vec<[u8; 32]> {
+        values.iter().map(|v| leaf_hash(v)).collect()
+    }
+
+    #[test]
+    fn single_leaf_tree_is_inclusion_proof_of_itself() {
+        let leaves = leaves_of(&[b"a"]);
+        let root = merkle_root(&leaves);
+        assert!(audit_path(0, &leaves).is_empty());
+        assert_eq!(root_from_audit_path(leaves[0], 0, 1, &[]), root);
+    }
+
+    #[test]
+    fn four_leaf_audit_paths_recompute_the_root() {
+        let leaves = leaves_of(&[b"a", b"b", b"c", b"d"]);
+        let root = merkle_root(&leaves);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let path = audit_path(index, &leaves);
+            assert_eq!(root_from_audit_path(*leaf, index, leaves.len(), &path), root);
+        }
+    }
+
+    #[test]
+    fn odd_sized_tree_audit_paths_recompute_the_root() {
+        let leaves = leaves_of(&[b"a", b"b", b"c", b"d", b"e"]);
+        let root = merkle_root(&leaves);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let path = audit_path(index, &leaves);
+            assert_eq!(root_from_audit_path(*leaf, index, leaves.len(), &path), root);
+        }
+    }
+
+    #[test]
+    fn tampered_leaf_fails_to_recompute_the_root() {
+        let leaves = leaves_of(&[b"a", b"b", b"c", b"d"]);
+        let root = merkle_root(&leaves);
+        let path = audit_path(1, &leaves);
+
+        let wrong_leaf = leaf_hash(b"not-b");
+        assert_ne!(root_from_audit_path(wrong_leaf, 1, leaves.len(), &path), root);
+    }
+
+    #[tokio::test]
+    async fn proof_and_root_round_trip_through_app_state() {
+        let state = Arc::new(
+            AppState::new(Box::new(crate::storage::InMemoryStorage::new()))
+                .await
+                .unwrap(),
+        );
+
+        let attestation = Attestation {
+            id: "attestation-1".to_string(),
+            file_id: "file-1".to_string(),
+            file_hash: "deadbeef".to_string(),
+            operation: "upload".to_string(),
+            timestamp: 0,
+            signature: vec![1, 2, 3],
+            metadata: serde_json::json!({}),
+        };
+        record_attestation_leaf(&state, &attestation).unwrap();
+
+        let proof = build_inclusion_proof(&state, &attestation.id).unwrap();
+        assert_eq!(proof.leaf_index, 0);
+        assert_eq!(proof.signed_root.tree_size, 1);
+
+        let root = current_signed_root(&state).unwrap();
+        assert_eq!(root.tree_size, 1);
+        assert_eq!(root.root_hash, proof.signed_root.root_hash);
+
+        let leaf = leaf_hash(&attestation_leaf_data(&attestation));
+        let recomputed = root_from_audit_path(
+            leaf,
+            proof.leaf_index as usize,
+            proof.signed_root.tree_size as usize,
+            &proof
+                .audit_path
+                .iter()
+                .map(|h| hex::decode(h).unwrap().try_into().unwrap())
+                .collect::<Vec<[u8; 32]>>(),
+        );
+        assert_eq!(hex::encode(recomputed), proof.signed_root.root_hash);
+    }
+}
@@ -1,23 +1,44 @@
 use anyhow::Result;
 use ed25519_dalek::{SigningKey, VerifyingKey};
 use rand::{rngs::OsRng, RngCore};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::RwLock;
 
+use crate::storage::{BlobMetadata, Storage};
+
 /// Shared application state
 pub struct AppState {
     /// Ephemeral key pair for signing attestations
     pub signing_key: SigningKey,
     pub verifying_key: VerifyingKey,
-    
-    /// Storage for uploaded files (in-memory for TEE)
-    pub file_storage: RwLock<HashMap<String, FileEntry>>,
-    
-    /// Attestation storage
-    pub attestations: RwLock<HashMap<String, Attestation>>,
+
+    /// Pluggable backend for files and attestations — in-memory by default,
+    /// or a durable SQLite-backed store selected via
+    /// `storage::storage_backend_from_env`. See `storage::Storage`.
+    storage: Box<dyn Storage>,
+
+    /// Outstanding RCAR-style attestation challenge nonces, keyed by their
+    /// hex encoding, each single-use and valid only until `expires_at`.
+    pub attestation_nonces: RwLock<HashMap<String, NonceChallenge>>,
+
+    /// `token_id`s of capability tokens that have been revoked before their
+    /// natural expiry. See `capability::require_capability`.
+    pub revoked_tokens: RwLock<HashSet<String>>,
 }
 
-/// Represents a stored file in the enclave
+/// An issued attestation challenge nonce, awaiting a single use before its
+/// TTL expires. See `attestation::generate_challenge` /
+/// `AppState::consume_nonce_challenge`.
+#[derive(Clone, Debug)]
+pub struct NonceChallenge {
+    pub issued_at: i64,
+    pub expires_at: i64,
+}
+
+/// Represents a stored file in the enclave. `id` is the logical identity
+/// handed out to callers; `hash` is the SHA-256 of `data` and also the key
+/// under which the backend physically stores the bytes, so two entries
+/// with identical content share one on-disk blob. See `storage::Storage`.
 #[derive(Clone, Debug)]
 pub struct FileEntry {
     pub id: String,
@@ -26,6 +47,9 @@ pub struct FileEntry {
     pub hash: Vec<u8>,
     pub uploaded_at: chrono::DateTime<chrono::Utc>,
     pub file_type: FileType,
+    /// Hash of this file's generated preview thumbnail, once one exists.
+    /// See `apps::satya::preview`.
+    pub preview_hash: Option<Vec<u8>>,
 }
 
 #[derive(Clone, Debug)]
@@ -49,7 +73,7 @@ pub struct Attestation {
 }
 
 impl AppState {
-    pub async fn new() -> Result<Self> {
+    pub async fn new(storage: Box<dyn Storage>) -> Result<Self> {
         // Generate ephemeral signing key for this enclave instance
         let mut csprng = OsRng;
         let mut secret_key_bytes = [0u8; 32];
@@ -60,48 +84,125 @@ impl AppState {
         Ok(Self {
             signing_key,
             verifying_key,
-            file_storage: RwLock::new(HashMap::new()),
-            attestations: RwLock::new(HashMap::new()),
+            storage,
+            attestation_nonces: RwLock::new(HashMap::new()),
+            revoked_tokens: RwLock::new(HashSet::new()),
         })
     }
 
-    /// Store a file in the enclave
+    /// Store a file via the configured storage backend
     pub fn store_file(&self, file_entry: FileEntry) -> Result<String> {
-        let id = file_entry.id.clone();
-        self.file_storage
-            .write()
-            .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))?
-            .insert(id.clone(), file_entry);
-        Ok(id)
+        self.storage.store_file(file_entry)
     }
 
-    /// Retrieve a file from storage
+    /// Retrieve a file from the configured storage backend
     pub fn get_file(&self, id: &str) -> Result<FileEntry> {
-        self.file_storage
-            .read()
-            .map_err(|_| anyhow::anyhow!("Failed to acquire read lock"))?
-            .get(id)
-            .cloned()
-            .ok_or_else(|| anyhow::anyhow!("File not found: {}", id))
+        self.storage.get_file(id)
     }
 
-    /// Store an attestation
+    /// List every stored file
+    pub fn list_files(&self) -> Result<Vec<FileEntry>> {
+        self.storage.list_files()
+    }
+
+    /// Drop a logical file id. The underlying content-addressed blob is
+    /// only removed once no other file id still references its hash.
+    pub fn delete_file(&self, id: &str) -> Result<()> {
+        self.storage.delete_file(id)
+    }
+
+    /// Look up a content-addressed blob's size and reference count by its
+    /// hex SHA-256, independent of which file id(s) point at it.
+    pub fn get_blob_metadata(&self, hash_hex: &str) -> Result<BlobMetadata> {
+        self.storage.get_blob_metadata(hash_hex)
+    }
+
+    /// Store an arbitrary content-addressed blob (e.g. a preview thumbnail)
+    /// and return its hex SHA-256.
+    pub fn store_blob(&self, data: Vec<u8>) -> Result<String> {
+        self.storage.store_blob(data)
+    }
+
+    /// Fetch a content-addressed blob's raw bytes by its hex SHA-256.
+    pub fn get_blob(&self, hash_hex: &str) -> Result<Vec<u8>> {
+        self.storage.get_blob(hash_hex)
+    }
+
+    /// Record `id`'s preview thumbnail as living at `preview_hash_hex`.
+    pub fn set_preview_hash(&self, id: &str, preview_hash_hex: String) -> Result<()> {
+        self.storage.set_preview_hash(id, preview_hash_hex)
+    }
+
+    /// Append a transparency-log leaf, returning its 0-based index.
+    pub fn append_log_leaf(&self, attestation_id: &str, leaf_hash: Vec<u8>) -> Result<u64> {
+        self.storage.append_log_leaf(attestation_id, leaf_hash)
+    }
+
+    /// Every transparency-log entry in append order.
+    pub fn get_log_entries(&self) -> Result<Vec<(String, Vec<u8>)>> {
+        self.storage.get_log_entries()
+    }
+
+    /// Store an attestation via the configured storage backend
     pub fn store_attestation(&self, attestation: Attestation) -> Result<String> {
-        let id = attestation.id.clone();
-        self.attestations
+        self.storage.store_attestation(attestation)
+    }
+
+    /// Get attestation by ID from the configured storage backend
+    pub fn get_attestation(&self, id: &str) -> Result<Attestation> {
+        self.storage.get_attestation(id)
+    }
+
+    /// List every stored attestation
+    pub fn list_attestations(&self) -> Result<Vec<Attestation>> {
+        self.storage.list_attestations()
+    }
+
+    /// Record a freshly-issued attestation challenge nonce. Sweeps out any
+    /// other entries that expired before ever being redeemed, so an
+    /// unredeemed nonce can't sit in memory forever — without this, only
+    /// `consume_nonce_challenge` ever removed entries, which only happens
+    /// when an attestation carrying that nonce is verified.
+    pub fn store_nonce_challenge(&self, nonce_hex: String, challenge: NonceChallenge) -> Result<()> {
+        let mut nonces = self
+            .attestation_nonces
+            .write()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))?;
+        let now = chrono::Utc::now().timestamp();
+        nonces.retain(|_, c| c.expires_at >= now);
+        nonces.insert(nonce_hex, challenge);
+        Ok(())
+    }
+
+    /// Consume a nonce if it was issued, is unexpired, and hasn't been used
+    /// before. Always removes the entry (single-use), even when the check
+    /// fails, so a captured nonce can never be replayed.
+    pub fn consume_nonce_challenge(&self, nonce_hex: &str, now: i64) -> Result<bool> {
+        let challenge = self
+            .attestation_nonces
+            .write()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))?
+            .remove(nonce_hex);
+
+        Ok(matches!(challenge, Some(c) if now <= c.expires_at))
+    }
+
+    /// Revoke a capability token by ID, so it's rejected even if otherwise
+    /// unexpired.
+    pub fn revoke_token(&self, token_id: &str) -> Result<()> {
+        self.revoked_tokens
             .write()
             .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))?
-            .insert(id.clone(), attestation);
-        Ok(id)
+            .insert(token_id.to_string());
+        Ok(())
     }
 
-    /// Get attestation by ID
-    pub fn get_attestation(&self, id: &str) -> Result<Attestation> {
-        self.attestations
+    /// Check whether a capability token ID has been revoked.
+    pub fn is_token_revoked(&self, token_id: &str) -> Result<bool> {
+        Ok(self
+            .revoked_tokens
             .read()
             .map_err(|_| anyhow::anyhow!("Failed to acquire read lock"))?
-            .get(id)
-            .cloned()
-            .ok_or_else(|| anyhow::anyhow!("Attestation not found: {}", id))
+            .contains(token_id))
     }
 }
\ No newline at end of file
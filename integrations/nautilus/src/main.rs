@@ -11,6 +11,7 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod apps;
 mod common;
+mod storage;
 
 use common::AppState;
 
@@ -27,8 +28,25 @@ async fn main() -> Result<()> {
 
     info!("Starting Nautilus Server for Satya Marketplace");
 
-    // Initialize application state
-    let state = Arc::new(AppState::new().await?);
+    // Initialize application state, backed by whichever storage backend
+    // SATYA_STORAGE_BACKEND/SATYA_STORAGE_DIR select (in-memory by default).
+    let backend = storage::storage_backend_from_env()?;
+    let state = Arc::new(AppState::new(backend).await?);
+
+    // Mint a bootstrap admin capability token so the operator has a way to
+    // mint/revoke further tokens via /auth/token — that route requires an
+    // Admin capability, and the upload/read/attest/verify routes require
+    // their own capabilities.
+    let bootstrap_token = apps::satya::mint_token(
+        &state,
+        "*",
+        vec![apps::satya::Capability::Admin],
+        365 * 24 * 3600,
+    )?;
+    info!(
+        "Bootstrap admin capability token (store securely, it won't be logged again): {}",
+        apps::satya::encode_token(&bootstrap_token)?
+    );
 
     // Create CORS layer
     let cors = CorsLayer::new()
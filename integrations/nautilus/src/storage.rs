@@ -0,0 +1,817 @@
+use anyhow::{Context, Result};
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+
+use crate::common::{Attestation, FileEntry, FileType};
+
+/// Metadata for a content-addressed blob: its hex SHA-256, size, and how
+/// many logical `FileEntry` ids currently point at it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobMetadata {
+    pub hash: String,
+    pub size: u64,
+    pub ref_count: u32,
+}
+
+/// Where uploaded files and signed attestations are kept. `InMemoryStorage`
+/// is the original behavior (nothing survives a restart); `SqliteStorage`
+/// persists metadata in a WAL-mode SQLite database plus file bytes on disk,
+/// so the service survives an enclave restart. Both backends are
+/// content-addressed: a blob is physically stored once per distinct SHA-256,
+/// and logical `FileEntry` ids merely reference it by hash, so uploading
+/// the same bytes under a second id doesn't duplicate storage. Selected
+/// once at startup via `storage_backend_from_env`; route handlers only
+/// ever see this trait.
+pub trait Storage: Send + Sync {
+    fn store_file(&self, file: FileEntry) -> Result<String>;
+    fn get_file(&self, id: &str) -> Result<FileEntry>;
+    fn list_files(&self) -> Result<Vec<FileEntry>>;
+    /// Drop a logical file id. The underlying blob is only deleted once no
+    /// other id references its hash.
+    fn delete_file(&self, id: &str) -> Result<()>;
+
+    fn get_blob_metadata(&self, hash_hex: &str) -> Result<BlobMetadata>;
+
+    /// Store an arbitrary content-addressed blob (e.g. a generated preview
+    /// thumbnail) that isn't itself a `FileEntry`, returning its hex
+    /// SHA-256. Shares the same ref-counted blob pool `store_file` writes
+    /// into, so a thumbnail identical to one already stored is deduped too.
+    fn store_blob(&self, data: Vec<u8>) -> Result<String>;
+    fn get_blob(&self, hash_hex: &str) -> Result<Vec<u8>>;
+
+    /// Record that `id`'s preview thumbnail lives at the blob keyed by
+    /// `preview_hash_hex`. The blob's reference count is left as is: the
+    /// reference is the one `store_blob` took out, released by `delete_file`.
+    fn set_preview_hash(&self, id: &str, preview_hash_hex: String) -> Result<()>;
+
+    fn store_attestation(&self, attestation: Attestation) -> Result<String>;
+    fn get_attestation(&self, id: &str) -> Result<Attestation>;
+    fn list_attestations(&self) -> Result<Vec<Attestation>>;
+
+    /// Append a transparency-log leaf for `attestation_id`, returning its
+    /// 0-based index. See `apps::satya::transparency`.
+    fn append_log_leaf(&self, attestation_id: &str, leaf_hash: Vec<u8>) -> Result<u64>;
+    /// Every log entry in append order: `(attestation_id, leaf_hash)`.
+    fn get_log_entries(&self) -> Result<Vec<(String, Vec<u8>)>>;
+}
+
+/// File metadata kept separately from its (possibly shared) blob bytes.
+#[derive(Clone, Debug)]
+struct FileMeta {
+    name: String,
+    hash: Vec<u8>,
+    uploaded_at: chrono::DateTime<chrono::Utc>,
+    file_type: FileType,
+    preview_hash: Option<Vec<u8>>,
+}
+
+/// A content-addressed blob: its bytes plus how many `FileEntry` ids
+/// currently reference it.
+#[derive(Clone, Debug)]
+struct Blob {
+    data: Vec<u8>,
+    ref_count: u32,
+}
+
+/// Original in-memory backend: simplest thing that works, nothing survives
+/// a restart, no `storage_dir` required.
+pub struct InMemoryStorage {
+    files: RwLock<HashMap<String, FileMeta>>,
+    blobs: RwLock<HashMap<String, Blob>>,
+    attestations: RwLock<HashMap<String, Attestation>>,
+    log_entries: RwLock<Vec<(String, Vec<u8>)>>,
+}
+
+impl InMemoryStorage {
+    pub fn new() -> Self {
+        Self {
+            files: RwLock::new(HashMap::new()),
+            blobs: RwLock::new(HashMap::new()),
+            attestations: RwLock::new(HashMap::new()),
+            log_entries: RwLock::new(Vec::new()),
+        }
+    }
+}
+
+impl Storage for InMemoryStorage {
+    fn store_file(&self, file: FileEntry) -> Result<String> {
+        let id = file.id.clone();
+        let hash_hex = hex::encode(&file.hash);
+
+        {
+            let mut blobs = self
+                .blobs
+                .write()
+                .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))?;
+            match blobs.get_mut(&hash_hex) {
+                Some(blob) => blob.ref_count += 1,
+                None => {
+                    blobs.insert(
+                        hash_hex,
+                        Blob {
+                            data: file.data,
+                            ref_count: 1,
+                        },
+                    );
+                }
+            }
+        }
+
+        self.files
+            .write()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))?
+            .insert(
+                id.clone(),
+                FileMeta {
+                    name: file.name,
+                    hash: file.hash,
+                    uploaded_at: file.uploaded_at,
+                    file_type: file.file_type,
+                    preview_hash: None,
+                },
+            );
+
+        Ok(id)
+    }
+
+    fn get_file(&self, id: &str) -> Result<FileEntry> {
+        let meta = self
+            .files
+            .read()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire read lock"))?
+            .get(id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("File not found: {}", id))?;
+
+        let hash_hex = hex::encode(&meta.hash);
+        let data = self
+            .blobs
+            .read()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire read lock"))?
+            .get(&hash_hex)
+            .map(|blob| blob.data.clone())
+            .ok_or_else(|| anyhow::anyhow!("Blob not found for hash {}", hash_hex))?;
+
+        Ok(FileEntry {
+            id: id.to_string(),
+            name: meta.name,
+            data,
+            hash: meta.hash,
+            uploaded_at: meta.uploaded_at,
+            file_type: meta.file_type,
+            preview_hash: meta.preview_hash,
+        })
+    }
+
+    fn list_files(&self) -> Result<Vec<FileEntry>> {
+        let files = self
+            .files
+            .read()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire read lock"))?
+            .clone();
+        let blobs = self
+            .blobs
+            .read()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire read lock"))?;
+
+        files
+            .into_iter()
+            .map(|(id, meta)| {
+                let hash_hex = hex::encode(&meta.hash);
+                let data = blobs
+                    .get(&hash_hex)
+                    .map(|blob| blob.data.clone())
+                    .ok_or_else(|| anyhow::anyhow!("Blob not found for hash {}", hash_hex))?;
+                Ok(FileEntry {
+                    id,
+                    name: meta.name,
+                    data,
+                    hash: meta.hash,
+                    uploaded_at: meta.uploaded_at,
+                    file_type: meta.file_type,
+                    preview_hash: meta.preview_hash,
+                })
+            })
+            .collect()
+    }
+
+    fn delete_file(&self, id: &str) -> Result<()> {
+        let meta = self
+            .files
+            .write()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))?
+            .remove(id)
+            .ok_or_else(|| anyhow::anyhow!("File not found: {}", id))?;
+
+        let hash_hex = hex::encode(&meta.hash);
+        let mut blobs = self
+            .blobs
+            .write()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))?;
+        if let Some(blob) = blobs.get_mut(&hash_hex) {
+            if blob.ref_count <= 1 {
+                blobs.remove(&hash_hex);
+            } else {
+                blob.ref_count -= 1;
+            }
+        }
+
+        if let Some(preview_hash) = meta.preview_hash {
+            let preview_hex = hex::encode(&preview_hash);
+            if let Some(blob) = blobs.get_mut(&preview_hex) {
+                if blob.ref_count <= 1 {
+                    blobs.remove(&preview_hex);
+                } else {
+                    blob.ref_count -= 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn get_blob_metadata(&self, hash_hex: &str) -> Result<BlobMetadata> {
+        self.blobs
+            .read()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire read lock"))?
+            .get(hash_hex)
+            .map(|blob| BlobMetadata {
+                hash: hash_hex.to_string(),
+                size: blob.data.len() as u64,
+                ref_count: blob.ref_count,
+            })
+            .ok_or_else(|| anyhow::anyhow!("Blob not found: {}", hash_hex))
+    }
+
+    fn store_blob(&self, data: Vec<u8>) -> Result<String> {
+        let hash_hex = hex::encode(Sha256::digest(&data));
+        let mut blobs = self
+            .blobs
+            .write()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))?;
+        match blobs.get_mut(&hash_hex) {
+            Some(blob) => blob.ref_count += 1,
+            None => {
+                blobs.insert(hash_hex.clone(), Blob { data, ref_count: 1 });
+            }
+        }
+        Ok(hash_hex)
+    }
+
+    fn get_blob(&self, hash_hex: &str) -> Result<Vec<u8>> {
+        self.blobs
+            .read()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire read lock"))?
+            .get(hash_hex)
+            .map(|blob| blob.data.clone())
+            .ok_or_else(|| anyhow::anyhow!("Blob not found: {}", hash_hex))
+    }
+
+    fn set_preview_hash(&self, id: &str, preview_hash_hex: String) -> Result<()> {
+        let preview_hash = hex::decode(&preview_hash_hex).context("preview hash is not valid hex")?;
+        self.files
+            .write()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))?
+            .get_mut(id)
+            .ok_or_else(|| anyhow::anyhow!("File not found: {}", id))?
+            .preview_hash = Some(preview_hash);
+        Ok(())
+    }
+
+    fn store_attestation(&self, attestation: Attestation) -> Result<String> {
+        let id = attestation.id.clone();
+        self.attestations
+            .write()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))?
+            .insert(id.clone(), attestation);
+        Ok(id)
+    }
+
+    fn get_attestation(&self, id: &str) -> Result<Attestation> {
+        self.attestations
+            .read()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire read lock"))?
+            .get(id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Attestation not found: {}", id))
+    }
+
+    fn list_attestations(&self) -> Result<Vec<Attestation>> {
+        Ok(self
+            .attestations
+            .read()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire read lock"))?
+            .values()
+            .cloned()
+            .collect())
+    }
+
+    fn append_log_leaf(&self, attestation_id: &str, leaf_hash: Vec<u8>) -> Result<u64> {
+        let mut entries = self
+            .log_entries
+            .write()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire write lock"))?;
+        let index = entries.len() as u64;
+        entries.push((attestation_id.to_string(), leaf_hash));
+        Ok(index)
+    }
+
+    fn get_log_entries(&self) -> Result<Vec<(String, Vec<u8>)>> {
+        Ok(self
+            .log_entries
+            .read()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire read lock"))?
+            .clone())
+    }
+}
+
+/// SQLite-backed durable storage. `files` holds logical metadata (id, name,
+/// content hash, upload time, file type) keyed by id; `blobs` holds each
+/// distinct SHA-256's on-disk path, size, and reference count keyed by hex
+/// hash. Two files with identical bytes share one row in `blobs` and one
+/// file on disk under `storage_dir/blobs/<hash>`.
+pub struct SqliteStorage {
+    conn: Mutex<rusqlite::Connection>,
+    storage_dir: PathBuf,
+}
+
+impl SqliteStorage {
+    pub fn open(storage_dir: impl AsRef<Path>) -> Result<Self> {
+        let storage_dir = storage_dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(storage_dir.join("blobs"))
+            .with_context(|| format!("failed to create storage dir at {}", storage_dir.display()))?;
+
+        let db_path = storage_dir.join("satya.sqlite3");
+        let conn = rusqlite::Connection::open(&db_path)
+            .with_context(|| format!("failed to open SQLite database at {}", db_path.display()))?;
+
+        // WAL journal mode. All access still goes through the single
+        // mutex-guarded connection below, so reads and writes from separate
+        // axum tasks run one at a time.
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .context("failed to enable SQLite WAL mode")?;
+
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS files (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                uploaded_at TEXT NOT NULL,
+                file_type TEXT NOT NULL,
+                preview_hash TEXT
+            );
+            CREATE TABLE IF NOT EXISTS blobs (
+                hash TEXT PRIMARY KEY,
+                blob_path TEXT NOT NULL,
+                size INTEGER NOT NULL,
+                ref_count INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS attestations (
+                id TEXT PRIMARY KEY,
+                file_id TEXT NOT NULL,
+                file_hash TEXT NOT NULL,
+                operation TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                signature BLOB NOT NULL,
+                metadata TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS transparency_log (
+                idx INTEGER PRIMARY KEY,
+                attestation_id TEXT NOT NULL,
+                leaf_hash TEXT NOT NULL
+            );",
+        )
+        .context("failed to initialize SQLite schema")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            storage_dir,
+        })
+    }
+
+    fn blob_path(&self, hash_hex: &str) -> PathBuf {
+        self.storage_dir.join("blobs").join(hash_hex)
+    }
+
+    fn conn(&self) -> Result<std::sync::MutexGuard<'_, rusqlite::Connection>> {
+        self.conn
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Failed to acquire SQLite connection lock"))
+    }
+}
+
+fn file_type_to_db_string(file_type: &FileType) -> String {
+    match file_type {
+        FileType::Model => "model".to_string(),
+        FileType::Dataset => "dataset".to_string(),
+        FileType::Document => "document".to_string(),
+        FileType::Other(other) => format!("other:{}", other),
+    }
+}
+
+fn file_type_from_db_string(value: &str) -> FileType {
+    match value {
+        "model" => FileType::Model,
+        "dataset" => FileType::Dataset,
+        "document" => FileType::Document,
+        other => FileType::Other(other.strip_prefix("other:").unwrap_or(other).to_string()),
+    }
+}
+
+/// Decrement a blob's ref count, deleting its row and on-disk bytes once it
+/// hits zero. Shared by `delete_file` for both a file's primary blob and its
+/// optional preview blob.
+fn release_blob_row(conn: &rusqlite::Connection, hash_hex: &str) -> Result<()> {
+    let ref_count: i64 = conn.query_row(
+        "SELECT ref_count FROM blobs WHERE hash = ?1",
+        rusqlite::params![hash_hex],
+        |row| row.get(0),
+    )?;
+
+    if ref_count <= 1 {
+        let blob_path: String = conn.query_row(
+            "SELECT blob_path FROM blobs WHERE hash = ?1",
+            rusqlite::params![hash_hex],
+            |row| row.get(0),
+        )?;
+        conn.execute("DELETE FROM blobs WHERE hash = ?1", rusqlite::params![hash_hex])?;
+        let _ = std::fs::remove_file(&blob_path);
+    } else {
+        conn.execute(
+            "UPDATE blobs SET ref_count = ref_count - 1 WHERE hash = ?1",
+            rusqlite::params![hash_hex],
+        )?;
+    }
+
+    Ok(())
+}
+
+fn row_to_file_entry(
+    id: String,
+    name: String,
+    hash_hex: String,
+    uploaded_at: String,
+    file_type: String,
+    blob_path: String,
+    preview_hash: Option<String>,
+) -> Result<FileEntry> {
+    let data = std::fs::read(&blob_path)
+        .with_context(|| format!("failed to read file blob at {}", blob_path))?;
+
+    Ok(FileEntry {
+        id,
+        name,
+        data,
+        hash: hex::decode(&hash_hex).context("stored file hash is not valid hex")?,
+        uploaded_at: chrono::DateTime::parse_from_rfc3339(&uploaded_at)
+            .context("stored uploaded_at is not valid RFC3339")?
+            .with_timezone(&chrono::Utc),
+        file_type: file_type_from_db_string(&file_type),
+        preview_hash: preview_hash
+            .map(|h| hex::decode(&h).context("stored preview hash is not valid hex"))
+            .transpose()?,
+    })
+}
+
+impl Storage for SqliteStorage {
+    fn store_file(&self, file: FileEntry) -> Result<String> {
+        let hash_hex = hex::encode(&file.hash);
+        let conn = self.conn()?;
+
+        let existing_ref_count: Option<i64> = conn
+            .query_row(
+                "SELECT ref_count FROM blobs WHERE hash = ?1",
+                rusqlite::params![hash_hex],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        match existing_ref_count {
+            Some(ref_count) => {
+                conn.execute(
+                    "UPDATE blobs SET ref_count = ?1 WHERE hash = ?2",
+                    rusqlite::params![ref_count + 1, hash_hex],
+                )?;
+            }
+            None => {
+                let blob_path = self.blob_path(&hash_hex);
+                std::fs::write(&blob_path, &file.data)
+                    .with_context(|| format!("failed to write blob to {}", blob_path.display()))?;
+                conn.execute(
+                    "INSERT INTO blobs (hash, blob_path, size, ref_count) VALUES (?1, ?2, ?3, 1)",
+                    rusqlite::params![hash_hex, blob_path.to_string_lossy(), file.data.len() as i64],
+                )?;
+            }
+        }
+
+        conn.execute(
+            "INSERT OR REPLACE INTO files (id, name, hash, uploaded_at, file_type)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            rusqlite::params![
+                file.id,
+                file.name,
+                hash_hex,
+                file.uploaded_at.to_rfc3339(),
+                file_type_to_db_string(&file.file_type),
+            ],
+        )?;
+
+        Ok(file.id)
+    }
+
+    fn get_file(&self, id: &str) -> Result<FileEntry> {
+        let conn = self.conn()?;
+        let (name, hash_hex, uploaded_at, file_type, preview_hash): (
+            String,
+            String,
+            String,
+            String,
+            Option<String>,
+        ) = conn
+            .query_row(
+                "SELECT name, hash, uploaded_at, file_type, preview_hash FROM files WHERE id = ?1",
+                rusqlite::params![id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+            )
+            .map_err(|_| anyhow::anyhow!("File not found: {}", id))?;
+
+        let blob_path: String = conn
+            .query_row(
+                "SELECT blob_path FROM blobs WHERE hash = ?1",
+                rusqlite::params![hash_hex],
+                |row| row.get(0),
+            )
+            .map_err(|_| anyhow::anyhow!("Blob not found for hash {}", hash_hex))?;
+        drop(conn);
+
+        row_to_file_entry(id.to_string(), name, hash_hex, uploaded_at, file_type, blob_path, preview_hash)
+    }
+
+    fn list_files(&self) -> Result<Vec<FileEntry>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT files.id, files.name, files.hash, files.uploaded_at, files.file_type, blobs.blob_path, files.preview_hash
+             FROM files JOIN blobs ON files.hash = blobs.hash",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, Option<String>>(6)?,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        drop(stmt);
+        drop(conn);
+
+        rows.into_iter()
+            .map(|(id, name, hash_hex, uploaded_at, file_type, blob_path, preview_hash)| {
+                row_to_file_entry(id, name, hash_hex, uploaded_at, file_type, blob_path, preview_hash)
+            })
+            .collect()
+    }
+
+    fn delete_file(&self, id: &str) -> Result<()> {
+        let conn = self.conn()?;
+        let (hash_hex, preview_hash): (String, Option<String>) = conn
+            .query_row(
+                "SELECT hash, preview_hash FROM files WHERE id = ?1",
+                rusqlite::params![id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|_| anyhow::anyhow!("File not found: {}", id))?;
+
+        conn.execute("DELETE FROM files WHERE id = ?1", rusqlite::params![id])?;
+
+        release_blob_row(&conn, &hash_hex)?;
+        if let Some(preview_hash) = preview_hash {
+            release_blob_row(&conn, &preview_hash)?;
+        }
+
+        Ok(())
+    }
+
+    fn get_blob_metadata(&self, hash_hex: &str) -> Result<BlobMetadata> {
+        let conn = self.conn()?;
+        let (size, ref_count): (i64, i64) = conn
+            .query_row(
+                "SELECT size, ref_count FROM blobs WHERE hash = ?1",
+                rusqlite::params![hash_hex],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|_| anyhow::anyhow!("Blob not found: {}", hash_hex))?;
+
+        Ok(BlobMetadata {
+            hash: hash_hex.to_string(),
+            size: size as u64,
+            ref_count: ref_count as u32,
+        })
+    }
+
+    fn store_blob(&self, data: Vec<u8>) -> Result<String> {
+        let hash_hex = hex::encode(Sha256::digest(&data));
+        let conn = self.conn()?;
+
+        let existing_ref_count: Option<i64> = conn
+            .query_row(
+                "SELECT ref_count FROM blobs WHERE hash = ?1",
+                rusqlite::params![hash_hex],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        match existing_ref_count {
+            Some(ref_count) => {
+                conn.execute(
+                    "UPDATE blobs SET ref_count = ?1 WHERE hash = ?2",
+                    rusqlite::params![ref_count + 1, hash_hex],
+                )?;
+            }
+            None => {
+                let blob_path = self.blob_path(&hash_hex);
+                std::fs::write(&blob_path, &data)
+                    .with_context(|| format!("failed to write blob to {}", blob_path.display()))?;
+                conn.execute(
+                    "INSERT INTO blobs (hash, blob_path, size, ref_count) VALUES (?1, ?2, ?3, 1)",
+                    rusqlite::params![hash_hex, blob_path.to_string_lossy(), data.len() as i64],
+                )?;
+            }
+        }
+
+        Ok(hash_hex)
+    }
+
+    fn get_blob(&self, hash_hex: &str) -> Result<Vec<u8>> {
+        let blob_path: String = self
+            .conn()?
+            .query_row(
+                "SELECT blob_path FROM blobs WHERE hash = ?1",
+                rusqlite::params![hash_hex],
+                |row| row.get(0),
+            )
+            .map_err(|_| anyhow::anyhow!("Blob not found: {}", hash_hex))?;
+
+        std::fs::read(&blob_path).with_context(|| format!("failed to read blob at {}", blob_path))
+    }
+
+    fn set_preview_hash(&self, id: &str, preview_hash_hex: String) -> Result<()> {
+        let conn = self.conn()?;
+        let updated = conn.execute(
+            "UPDATE files SET preview_hash = ?1 WHERE id = ?2",
+            rusqlite::params![preview_hash_hex, id],
+        )?;
+        if updated == 0 {
+            return Err(anyhow::anyhow!("File not found: {}", id));
+        }
+        Ok(())
+    }
+
+    fn store_attestation(&self, attestation: Attestation) -> Result<String> {
+        let metadata_json = serde_json::to_string(&attestation.metadata)
+            .context("failed to serialize attestation metadata")?;
+
+        self.conn()?.execute(
+            "INSERT OR REPLACE INTO attestations (id, file_id, file_hash, operation, timestamp, signature, metadata)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            rusqlite::params![
+                attestation.id,
+                attestation.file_id,
+                attestation.file_hash,
+                attestation.operation,
+                attestation.timestamp,
+                attestation.signature,
+                metadata_json,
+            ],
+        )?;
+
+        Ok(attestation.id)
+    }
+
+    fn get_attestation(&self, id: &str) -> Result<Attestation> {
+        let conn = self.conn()?;
+        conn.query_row(
+            "SELECT id, file_id, file_hash, operation, timestamp, signature, metadata FROM attestations WHERE id = ?1",
+            rusqlite::params![id],
+            |row| {
+                let metadata_json: String = row.get(6)?;
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, i64>(4)?,
+                    row.get::<_, Vec<u8>>(5)?,
+                    metadata_json,
+                ))
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Attestation not found: {}", id))
+        .and_then(
+            |(id, file_id, file_hash, operation, timestamp, signature, metadata_json)| {
+                Ok(Attestation {
+                    id,
+                    file_id,
+                    file_hash,
+                    operation,
+                    timestamp,
+                    signature,
+                    metadata: serde_json::from_str(&metadata_json)
+                        .context("stored attestation metadata is not valid JSON")?,
+                })
+            },
+        )
+    }
+
+    fn list_attestations(&self) -> Result<Vec<Attestation>> {
+        let conn = self.conn()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, file_id, file_hash, operation, timestamp, signature, metadata FROM attestations",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, i64>(4)?,
+                    row.get::<_, Vec<u8>>(5)?,
+                    row.get::<_, String>(6)?,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        drop(stmt);
+        drop(conn);
+
+        rows.into_iter()
+            .map(
+                |(id, file_id, file_hash, operation, timestamp, signature, metadata_json)| {
+                    Ok(Attestation {
+                        id,
+                        file_id,
+                        file_hash,
+                        operation,
+                        timestamp,
+                        signature,
+                        metadata: serde_json::from_str(&metadata_json)
+                            .context("stored attestation metadata is not valid JSON")?,
+                    })
+                },
+            )
+            .collect()
+    }
+
+    fn append_log_leaf(&self, attestation_id: &str, leaf_hash: Vec<u8>) -> Result<u64> {
+        let conn = self.conn()?;
+        let next_idx: i64 = conn.query_row(
+            "SELECT COALESCE(MAX(idx), -1) + 1 FROM transparency_log",
+            [],
+            |row| row.get(0),
+        )?;
+        conn.execute(
+            "INSERT INTO transparency_log (idx, attestation_id, leaf_hash) VALUES (?1, ?2, ?3)",
+            rusqlite::params![next_idx, attestation_id, hex::encode(&leaf_hash)],
+        )?;
+        Ok(next_idx as u64)
+    }
+
+    fn get_log_entries(&self) -> Result<Vec<(String, Vec<u8>)>> {
+        let conn = self.conn()?;
+        let mut stmt =
+            conn.prepare("SELECT attestation_id, leaf_hash FROM transparency_log ORDER BY idx ASC")?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        drop(stmt);
+        drop(conn);
+
+        rows.into_iter()
+            .map(|(id, hash_hex)| {
+                Ok((id, hex::decode(&hash_hex).context("stored leaf hash is not valid hex")?))
+            })
+            .collect()
+    }
+}
+
+/// Pick a storage backend from the environment: `SATYA_STORAGE_BACKEND` of
+/// `"sqlite"` persists to `SATYA_STORAGE_DIR` (default `"./data"`); anything
+/// else (including unset) keeps the original in-memory behavior so existing
+/// deployments and tests don't need any config to keep working.
+pub fn storage_backend_from_env() -> Result<Box<dyn Storage>> {
+    match std::env::var("SATYA_STORAGE_BACKEND").as_deref() {
+        Ok("sqlite") => {
+            let storage_dir = std::env::var("SATYA_STORAGE_DIR").unwrap_or_else(|_| "./data".to_string());
+            Ok(Box::new(SqliteStorage::open(storage_dir)?))
+        }
+        _ => Ok(Box::new(InMemoryStorage::new())),
+    }
+}
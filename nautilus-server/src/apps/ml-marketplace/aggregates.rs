@@ -0,0 +1,205 @@
+// Copyright (c) Satya Data Marketplace
+// Verifiable aggregate-function computation over dataset columns.
+// SPDX-License-Identifier: Apache-2.0
+
+use sha2::{Digest, Sha256};
+
+use crate::EnclaveError;
+
+use super::{AggregateFunction, AggregateRequest};
+
+/// Fixed-point scale shared with `AccuracyMetrics`/`BiasAssessment`/the Move
+/// contract: every u64 result here is the real value's magnitude times this
+/// constant (a negative result is stored as its absolute value).
+const FIXED_POINT_SCALE: f64 = 10000.0;
+
+/// Results of every requested aggregate, plus the root of a Merkle tree over
+/// the dataset's data rows (same SHA-256 leaf/pairwise-fold scheme as
+/// `verify_merkle_inclusion_proof`) so a verifier who is later shown a
+/// disclosed subset of rows can check they're the ones these aggregates were
+/// computed over.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AggregateComputation {
+    pub results: Vec<AggregateResult>,
+    pub rows_merkle_root_b64: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AggregateResult {
+    pub column_index: u64,
+    pub function: AggregateFunction,
+    /// Fixed-point (×10000) result. `None` for an empty column rather than a
+    /// divide-by-zero.
+    pub value: Option<u64>,
+}
+
+/// Parse `dataset_data` as CSV and run every requested aggregate over its
+/// target column in a single streaming pass per request: a running sum and
+/// sum-of-squares (for AVG/STDDEV), a running min/max, and a count/count_if
+/// tally, rather than materializing the column twice.
+pub(crate) fn compute_aggregates(
+    dataset_data: &[u8],
+    requests: &[AggregateRequest],
+) -> Result<AggregateComputation, EnclaveError> {
+    let text = std::str::from_utf8(dataset_data)
+        .map_err(|e| EnclaveError::GenericError(format!("Dataset is not valid UTF-8 CSV: {}", e)))?;
+    let rows: Vec<&str> = text.lines().skip(1).filter(|line| !line.trim().is_empty()).collect();
+
+    let results = requests
+        .iter()
+        .map(|request| compute_one(&rows, request))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(AggregateComputation { results, rows_merkle_root_b64: rows_merkle_root_b64(&rows) })
+}
+
+fn compute_one(rows: &[&str], request: &AggregateRequest) -> Result<AggregateResult, EnclaveError> {
+    let column_index = request.column_index as usize;
+
+    let mut count: u64 = 0;
+    let mut numeric_count: u64 = 0;
+    let mut count_if: u64 = 0;
+    let mut sum = 0f64;
+    let mut sum_sq = 0f64;
+    let mut min: Option<f64> = None;
+    let mut max: Option<f64> = None;
+
+    let threshold = match request.function {
+        AggregateFunction::CountIf => Some(request.threshold.ok_or_else(|| {
+            EnclaveError::GenericError("CountIf requires a threshold".to_string())
+        })?),
+        _ => None,
+    };
+
+    for row in rows {
+        let Some(raw) = row.split(',').nth(column_index) else { continue };
+        count += 1;
+        if let Ok(value) = raw.trim().parse::<f64>() {
+            numeric_count += 1;
+            sum += value;
+            sum_sq += value * value;
+            min = Some(min.map_or(value, |m| m.min(value)));
+            max = Some(max.map_or(value, |m| m.max(value)));
+            if let Some(threshold) = threshold {
+                if value >= threshold {
+                    count_if += 1;
+                }
+            }
+        }
+    }
+
+    let requires_numeric_column = !matches!(request.function, AggregateFunction::Count);
+    if requires_numeric_column && count > 0 && numeric_count != count {
+        return Err(EnclaveError::GenericError(format!(
+            "Column {} is not numeric; {:?} requires a numeric column",
+            column_index, request.function
+        )));
+    }
+
+    let value = if count == 0 {
+        None
+    } else {
+        match request.function {
+            AggregateFunction::Sum => Some(scale(sum)),
+            AggregateFunction::Avg => Some(scale(sum / count as f64)),
+            AggregateFunction::Min => min.map(scale),
+            AggregateFunction::Max => max.map(scale),
+            AggregateFunction::Count => Some(scale(count as f64)),
+            AggregateFunction::CountIf => Some(scale(count_if as f64)),
+            AggregateFunction::StdDev => {
+                let mean = sum / count as f64;
+                let variance = (sum_sq / count as f64 - mean * mean).max(0.0);
+                Some(scale(variance.sqrt()))
+            }
+        }
+    };
+
+    Ok(AggregateResult { column_index: request.column_index, function: request.function, value })
+}
+
+fn scale(value: f64) -> u64 {
+    (value.abs() * FIXED_POINT_SCALE).round() as u64
+}
+
+/// Standard bottom-up Merkle root over SHA-256 row-hash leaves, duplicating
+/// the last leaf at each level when the level has odd length.
+fn rows_merkle_root_b64(rows: &[&str]) -> String {
+    use base64::Engine;
+
+    let mut level: Vec<[u8; 32]> = rows.iter().map(|row| Sha256::digest(row.as_bytes()).into()).collect();
+    if level.is_empty() {
+        level.push([0u8; 32]);
+    }
+    while level.len() > 1 {
+        if level.len() % 2 == 1 {
+            level.push(*level.last().unwrap());
+        }
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut hasher = Sha256::new();
+                hasher.update(pair[0]);
+                hasher.update(pair[1]);
+                hasher.finalize().into()
+            })
+            .collect();
+    }
+
+    base64::engine::general_purpose::STANDARD.encode(level[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn csv(rows: &[&str]) -> Vec<u8> {
+        format!("value\n{}", rows.join("\n")).into_bytes()
+    }
+
+    #[test]
+    fn sum_avg_min_max_over_numeric_column() {
+        let data = csv(&["1", "2", "3", "4"]);
+        let requests = vec![
+            AggregateRequest { column_index: 0, function: AggregateFunction::Sum, threshold: None },
+            AggregateRequest { column_index: 0, function: AggregateFunction::Avg, threshold: None },
+            AggregateRequest { column_index: 0, function: AggregateFunction::Min, threshold: None },
+            AggregateRequest { column_index: 0, function: AggregateFunction::Max, threshold: None },
+        ];
+
+        let computation = compute_aggregates(&data, &requests).unwrap();
+        assert_eq!(computation.results[0].value, Some(100_000)); // sum=10
+        assert_eq!(computation.results[1].value, Some(25_000)); // avg=2.5
+        assert_eq!(computation.results[2].value, Some(10_000)); // min=1
+        assert_eq!(computation.results[3].value, Some(40_000)); // max=4
+    }
+
+    #[test]
+    fn count_if_counts_rows_meeting_threshold() {
+        let data = csv(&["1", "5", "10", "20"]);
+        let requests = vec![AggregateRequest {
+            column_index: 0,
+            function: AggregateFunction::CountIf,
+            threshold: Some(10.0),
+        }];
+
+        let computation = compute_aggregates(&data, &requests).unwrap();
+        assert_eq!(computation.results[0].value, Some(20_000)); // 2 rows >= 10
+    }
+
+    #[test]
+    fn empty_column_yields_none_not_divide_by_zero() {
+        let data = csv(&[]);
+        let requests = vec![AggregateRequest { column_index: 0, function: AggregateFunction::Avg, threshold: None }];
+
+        let computation = compute_aggregates(&data, &requests).unwrap();
+        assert_eq!(computation.results[0].value, None);
+    }
+
+    #[test]
+    fn non_numeric_column_rejects_sum() {
+        let data = csv(&["red", "green", "blue"]);
+        let requests = vec![AggregateRequest { column_index: 0, function: AggregateFunction::Sum, threshold: None }];
+
+        assert!(compute_aggregates(&data, &requests).is_err());
+    }
+}
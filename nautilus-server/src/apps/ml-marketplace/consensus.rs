@@ -0,0 +1,141 @@
+// Copyright (c) Satya Data Marketplace
+// Light-client style verification for a stream of enclave attestations
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use super::signature::{verify_attestation, AttestationSignature};
+use super::types::{MLMarketplaceError, QualityAssessmentReport, TEEAttestation};
+
+/// Minimal state a light client (or a Sui Move module) needs to keep in order to
+/// trust a stream of attestations from one enclave without re-checking every
+/// signature from scratch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnclaveConsensusState {
+    pub enclave_measurement: String,
+    pub trusted_pubkey: Vec<u8>,
+    pub latest_timestamp: u64,
+    pub latest_assessment_hash: String,
+    /// Set once `check_misbehaviour` detects conflicting headers at the same
+    /// timestamp; while frozen, no further headers are accepted.
+    pub frozen: bool,
+}
+
+/// Evidence that the enclave signed two conflicting attestations for the same
+/// instant in time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Misbehaviour {
+    pub timestamp: u64,
+    pub assessment_hash_a: String,
+    pub assessment_hash_b: String,
+    pub model_hash_a: String,
+    pub model_hash_b: String,
+}
+
+impl EnclaveConsensusState {
+    pub fn new(enclave_measurement: String, trusted_pubkey: Vec<u8>) -> Self {
+        Self {
+            enclave_measurement,
+            trusted_pubkey,
+            latest_timestamp: 0,
+            latest_assessment_hash: String::new(),
+            frozen: false,
+        }
+    }
+}
+
+/// Treat `attestation` as a new "header": verify its signature against the
+/// trusted public key, require the enclave measurement to match exactly, and
+/// require the timestamp to strictly advance. On success, mutate `state` to the
+/// new head.
+pub fn update_consensus_state(
+    state: &mut EnclaveConsensusState,
+    attestation: &TEEAttestation,
+    signature: &AttestationSignature,
+) -> Result<(), MLMarketplaceError> {
+    if state.frozen {
+        return Err(MLMarketplaceError::CryptoError(
+            "consensus state is frozen after detected misbehaviour".to_string(),
+        ));
+    }
+
+    let verified = verify_attestation(attestation, signature, &state.trusted_pubkey)
+        .map_err(|e| MLMarketplaceError::CryptoError(format!("signature verification error: {}", e)))?;
+    if !verified {
+        return Err(MLMarketplaceError::CryptoError(
+            "attestation signature does not match trusted public key".to_string(),
+        ));
+    }
+
+    if attestation.enclave_measurement != state.enclave_measurement {
+        return Err(MLMarketplaceError::CryptoError(format!(
+            "enclave measurement mismatch: expected {}, got {}",
+            state.enclave_measurement, attestation.enclave_measurement
+        )));
+    }
+
+    if attestation.timestamp <= state.latest_timestamp {
+        return Err(MLMarketplaceError::CryptoError(format!(
+            "non-increasing timestamp: latest is {}, header has {}",
+            state.latest_timestamp, attestation.timestamp
+        )));
+    }
+
+    state.latest_timestamp = attestation.timestamp;
+    state.latest_assessment_hash = attestation.assessment_hash.clone();
+    Ok(())
+}
+
+/// Check whether a given `QualityAssessmentReport` hashes into the attestation
+/// most recently accepted into the consensus state; earlier heads are not
+/// matched.
+pub fn verify_membership(
+    state: &EnclaveConsensusState,
+    report: &QualityAssessmentReport,
+    accepted_assessment_hash: &str,
+) -> bool {
+    use sha2::{Digest, Sha256};
+
+    if accepted_assessment_hash != state.latest_assessment_hash {
+        return false;
+    }
+
+    let Ok(report_bytes) = serde_json::to_vec(report) else {
+        return false;
+    };
+    let mut hasher = Sha256::new();
+    hasher.update(&report_bytes);
+    let computed = format!("{:x}", hasher.finalize());
+
+    // Bind the report's actual content to the accepted hash, rather than
+    // trusting the caller's claim that `report` is the thing `state` already
+    // accepted — the hash above isn't just a liveness check.
+    computed == accepted_assessment_hash
+}
+
+/// Inspect two validly-signed attestations that share a `timestamp`. If their
+/// `assessment_hash` or `model_hash` differ, this is equivocation: emit a
+/// `Misbehaviour` record and freeze `state` so no further headers are accepted
+/// until an operator resets it.
+pub fn check_misbehaviour(
+    state: &mut EnclaveConsensusState,
+    a: &TEEAttestation,
+    b: &TEEAttestation,
+) -> Option<Misbehaviour> {
+    if a.timestamp != b.timestamp {
+        return None;
+    }
+
+    if a.assessment_hash == b.assessment_hash && a.model_hash == b.model_hash {
+        return None;
+    }
+
+    state.frozen = true;
+    Some(Misbehaviour {
+        timestamp: a.timestamp,
+        assessment_hash_a: a.assessment_hash.clone(),
+        assessment_hash_b: b.assessment_hash.clone(),
+        model_hash_a: a.model_hash.clone(),
+        model_hash_b: b.model_hash.clone(),
+    })
+}
@@ -0,0 +1,873 @@
+// Copyright (c) Satya Data Marketplace
+// Pluggable model-format backends for the local (non-remote-evaluator) quality
+// assessment path.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::EnclaveError;
+use ndarray::{Array2, Axis};
+use std::collections::HashMap;
+use tracing::info;
+
+use super::{
+    analyze_onnx_model, analyze_pytorch_model, analyze_tensorflow_model, estimate_parameters_from_size,
+    parse_json_model_metadata, AccuracyMetrics, AssessmentResult, AssessmentType, BiasAssessment, DatasetInfo,
+    ModelInfo, QuantizationAssessment,
+};
+
+/// A model after an engine's `load` step: the framework-specific info plus the
+/// raw bytes the engine needs for `infer`.
+pub(crate) struct LoadedModel {
+    pub(crate) info: ModelInfo,
+    pub(crate) bytes: Vec<u8>,
+}
+
+/// Opaque inference output an engine's `infer` produces and its own `metrics`
+/// consumes. Engines that don't run real inference (TorchScript, TF-Lite,
+/// mock, and ONNX when its `ort` run fails) populate this with a
+/// simulated accuracy factor derived from model/dataset size, matching the
+/// heuristic the old single mock function used.
+pub(crate) struct RawPredictions {
+    pub(crate) base_accuracy: f64,
+    /// Metrics from an engine that actually ran inference against the
+    /// dataset, rather than synthesizing them from `base_accuracy`. `None`
+    /// for every engine that hasn't been wired up to a real runtime yet (or
+    /// that fell back after a real attempt failed).
+    pub(crate) measured: Option<AccuracyMetrics>,
+}
+
+/// A pluggable model-format backend. A registry selects one of these by
+/// `can_load` (magic-byte sniffing, falling back to a hint string) so that
+/// ONNX, TorchScript, and TF-Lite backends can be added independently of
+/// `process_data` and of each other.
+pub(crate) trait AssessmentEngine {
+    fn name(&self) -> &'static str;
+    fn can_load(&self, model_info: &ModelInfo) -> bool;
+    fn load(&self, bytes: &[u8]) -> Result<LoadedModel, EnclaveError>;
+    fn infer(&self, model: &LoadedModel, dataset_info: &DatasetInfo, dataset_bytes: &[u8]) -> Result<RawPredictions, EnclaveError>;
+    fn metrics(&self, preds: &RawPredictions) -> AccuracyMetrics;
+
+    /// Whether `infer_int8` measures a real quantization effect for this
+    /// engine. Only `OnnxEngine` has a real numeric inference path to
+    /// quantize today; the rest fall back to simulated accuracy in `infer`,
+    /// so there's nothing genuine for `infer_int8` to measure.
+    fn supports_int8_sensitivity(&self) -> bool {
+        false
+    }
+
+    /// Re-run inference with numeric inputs passed through symmetric INT8
+    /// quantize/dequantize first, to measure this engine's sensitivity to
+    /// post-training quantization. Only called when `supports_int8_sensitivity`
+    /// returns true; the default just repeats `infer` unchanged.
+    fn infer_int8(&self, model: &LoadedModel, dataset_info: &DatasetInfo, dataset_bytes: &[u8]) -> Result<RawPredictions, EnclaveError> {
+        self.infer(model, dataset_info, dataset_bytes)
+    }
+
+    /// Real per-row predictions and labels behind `infer`'s aggregated
+    /// `AccuracyMetrics`, for callers that need to group rows by a sensitive
+    /// attribute (`compute_fairness_metrics`). `None` when this engine has no
+    /// real numeric inference path to draw per-row predictions from — only
+    /// `OnnxEngine` overrides this today.
+    fn raw_predictions(&self, _model: &LoadedModel, _dataset_info: &DatasetInfo, _dataset_bytes: &[u8]) -> Option<(Vec<f32>, Vec<f32>)> {
+        None
+    }
+}
+
+fn accuracy_metrics_from(base_accuracy: f64) -> AccuracyMetrics {
+    AccuracyMetrics {
+        precision: ((base_accuracy + 0.02) * 10000.0) as u64,
+        recall: ((base_accuracy - 0.01) * 10000.0) as u64,
+        f1_score: (base_accuracy * 10000.0) as u64,
+        auc: Some(((base_accuracy + 0.05) * 10000.0) as u64),
+        rmse: None,
+        mae: None,
+    }
+}
+
+/// Simulated accuracy shared by every engine that doesn't run real
+/// inference: heavier models over bigger datasets score a bit higher. Split out
+/// so ONNX/TorchScript/TF-Lite and the mock fallback stay consistent with each
+/// other rather than drifting as engines are added.
+fn simulated_base_accuracy(model_info: &ModelInfo, dataset_info: &DatasetInfo) -> f64 {
+    let data_quality_factor = if dataset_info.rows > 1000 { 0.9 } else { 0.7 };
+    let model_quality_factor = if model_info.parameters > 50000 { 0.95 } else { 0.8 };
+    data_quality_factor * model_quality_factor
+}
+
+struct OnnxEngine;
+
+impl AssessmentEngine for OnnxEngine {
+    fn name(&self) -> &'static str {
+        "onnx"
+    }
+
+    fn can_load(&self, model_info: &ModelInfo) -> bool {
+        model_info.framework == "onnx"
+    }
+
+    fn load(&self, bytes: &[u8]) -> Result<LoadedModel, EnclaveError> {
+        Ok(LoadedModel { info: analyze_onnx_model(bytes)?, bytes: bytes.to_vec() })
+    }
+
+    fn infer(&self, model: &LoadedModel, dataset_info: &DatasetInfo, dataset_bytes: &[u8]) -> Result<RawPredictions, EnclaveError> {
+        match run_onnx_inference(model, dataset_info, dataset_bytes, false) {
+            Ok(output) => {
+                info!("Ran real ONNX inference via ort: f1={}", output.metrics.f1_score);
+                let base_accuracy = output.metrics.f1_score as f64 / 10000.0;
+                Ok(RawPredictions { base_accuracy, measured: Some(output.metrics) })
+            }
+            Err(e) => {
+                info!("ONNX inference unavailable ({}), falling back to simulated accuracy", e);
+                Ok(RawPredictions { base_accuracy: simulated_base_accuracy(&model.info, dataset_info), measured: None })
+            }
+        }
+    }
+
+    fn metrics(&self, preds: &RawPredictions) -> AccuracyMetrics {
+        preds.measured.clone().unwrap_or_else(|| accuracy_metrics_from(preds.base_accuracy))
+    }
+
+    fn supports_int8_sensitivity(&self) -> bool {
+        true
+    }
+
+    fn infer_int8(&self, model: &LoadedModel, dataset_info: &DatasetInfo, dataset_bytes: &[u8]) -> Result<RawPredictions, EnclaveError> {
+        let output = run_onnx_inference(model, dataset_info, dataset_bytes, true)?;
+        info!("Ran real ONNX inference over INT8-quantized inputs: f1={}", output.metrics.f1_score);
+        let base_accuracy = output.metrics.f1_score as f64 / 10000.0;
+        Ok(RawPredictions { base_accuracy, measured: Some(output.metrics) })
+    }
+
+    fn raw_predictions(&self, model: &LoadedModel, dataset_info: &DatasetInfo, dataset_bytes: &[u8]) -> Option<(Vec<f32>, Vec<f32>)> {
+        run_onnx_inference(model, dataset_info, dataset_bytes, false).ok().map(|output| (output.predictions, output.labels))
+    }
+}
+
+/// Real ONNX inference output: the aggregate metrics plus the raw per-row
+/// predictions and labels they were computed from, so callers that need
+/// per-row grouping (`compute_fairness_metrics`) don't have to re-run `ort`.
+struct OnnxInferenceOutput {
+    metrics: AccuracyMetrics,
+    predictions: Vec<f32>,
+    labels: Vec<f32>,
+}
+
+/// Run real inference for `model` over `dataset_bytes` using the `ort` ONNX
+/// Runtime, the same way the Twitter `navi` serving stack loads a `Session`
+/// and feeds it batched `ndarray` tensors. Any failure along the way (the
+/// dataset not parsing into a numeric feature/label matrix, `ort` rejecting
+/// the model, or the model's declared input shape not matching the dataset)
+/// is surfaced as an `Err` so the caller can fall back to the simulated path
+/// instead of the whole assessment failing. When `quantize_inputs` is set,
+/// the feature matrix is passed through `quantize_int8_symmetric` per column
+/// before being handed to `ort`, simulating the numeric error a real INT8
+/// post-training quantization pass would introduce at the model's input
+/// boundary — see `assess_quantization_sensitivity`.
+fn run_onnx_inference(
+    model: &LoadedModel,
+    dataset_info: &DatasetInfo,
+    dataset_bytes: &[u8],
+    quantize_inputs: bool,
+) -> Result<OnnxInferenceOutput, EnclaveError> {
+    let (mut features, labels) = parse_feature_label_matrix(dataset_info, dataset_bytes).ok_or_else(|| {
+        EnclaveError::GenericError("dataset does not parse into a numeric feature/label matrix".to_string())
+    })?;
+    if quantize_inputs {
+        features = quantize_feature_columns(&features);
+    }
+    let batch_size = features.nrows();
+
+    let session = ort::session::Session::builder()
+        .map_err(|e| EnclaveError::GenericError(format!("ort session builder failed: {}", e)))?
+        .commit_from_memory(&model.bytes)
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to load ONNX model into ort: {}", e)))?;
+
+    let input_name = session
+        .inputs
+        .first()
+        .map(|input| input.name.clone())
+        .ok_or_else(|| EnclaveError::GenericError("ONNX model declares no inputs".to_string()))?;
+    let output_name = session
+        .outputs
+        .first()
+        .map(|output| output.name.clone())
+        .ok_or_else(|| EnclaveError::GenericError("ONNX model declares no outputs".to_string()))?;
+
+    let input_tensor = ort::value::Value::from_array(features)
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to build ort input tensor (shape mismatch?): {}", e)))?;
+    let inputs = ort::inputs![input_name.as_str() => input_tensor]
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to bind ort inputs: {}", e)))?;
+    let outputs = session
+        .run(inputs)
+        .map_err(|e| EnclaveError::GenericError(format!("ort inference run failed: {}", e)))?;
+
+    let (_, raw_predictions) = outputs[output_name.as_str()]
+        .try_extract_tensor::<f32>()
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to extract ort output tensor: {}", e)))?;
+
+    if raw_predictions.len() < batch_size {
+        return Err(EnclaveError::GenericError(format!(
+            "ONNX model produced {} predictions for {} input rows",
+            raw_predictions.len(),
+            batch_size
+        )));
+    }
+
+    let predictions = raw_predictions[..batch_size].to_vec();
+    let metrics = binary_classification_metrics(&predictions, &labels);
+    Ok(OnnxInferenceOutput { metrics, predictions, labels })
+}
+
+/// Parse a CSV dataset into a feature matrix (every column but the last) and
+/// a label vector (the last column), the layout `ort` batches expect.
+/// Returns `None` for non-CSV formats or any row that doesn't parse cleanly
+/// as numeric, so the caller can fall back rather than guess.
+fn parse_feature_label_matrix(dataset_info: &DatasetInfo, dataset_bytes: &[u8]) -> Option<(Array2<f32>, Vec<f32>)> {
+    if dataset_info.format != "csv" {
+        return None;
+    }
+
+    let text = std::str::from_utf8(dataset_bytes).ok()?;
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next()?;
+    let column_count = header.split(',').count();
+    if column_count < 2 {
+        return None;
+    }
+
+    let mut features = Vec::new();
+    let mut labels = Vec::new();
+    let mut row_count = 0usize;
+    for line in lines {
+        let values: Vec<f32> = line.split(',').map(|v| v.trim().parse::<f32>()).collect::<Result<_, _>>().ok()?;
+        if values.len() != column_count {
+            return None;
+        }
+        let (label, feature_values) = values.split_last()?;
+        features.extend_from_slice(feature_values);
+        labels.push(*label);
+        row_count += 1;
+    }
+    if row_count == 0 {
+        return None;
+    }
+
+    let matrix = Array2::from_shape_vec((row_count, column_count - 1), features).ok()?;
+    Some((matrix, labels))
+}
+
+/// Symmetric per-tensor INT8 quantization, per the standard post-training
+/// quantization recipe: scale = max(|w|)/127, round each value to the
+/// nearest integer at that scale, clamp to the INT8 range, and dequantize
+/// back to f32 so the round-tripped values can be compared directly against
+/// the originals. An all-zero slice returns a scale of 0.0 rather than
+/// dividing by zero.
+fn quantize_int8_symmetric(values: &[f32]) -> (Vec<f32>, f32) {
+    let max_abs = values.iter().fold(0f32, |acc, v| acc.max(v.abs()));
+    if max_abs == 0.0 {
+        return (vec![0.0; values.len()], 0.0);
+    }
+    let scale = max_abs / 127.0;
+    let dequantized = values
+        .iter()
+        .map(|v| ((v / scale).round().clamp(-127.0, 127.0)) * scale)
+        .collect();
+    (dequantized, scale)
+}
+
+/// Applies `quantize_int8_symmetric` independently to each column of
+/// `features` (each feature column stands in for one "tensor" in the
+/// per-tensor scheme), simulating the numeric error INT8 post-training
+/// quantization would introduce at the model's input boundary.
+fn quantize_feature_columns(features: &Array2<f32>) -> Array2<f32> {
+    let mut quantized = features.clone();
+    for mut column in quantized.axis_iter_mut(Axis(1)) {
+        let values: Vec<f32> = column.iter().copied().collect();
+        let (dequantized, _scale) = quantize_int8_symmetric(&values);
+        for (cell, value) in column.iter_mut().zip(dequantized) {
+            *cell = value;
+        }
+    }
+    quantized
+}
+
+/// Precision/recall/F1/AUC for a binary classifier, thresholding raw model
+/// outputs at 0.5 and labels the same way.
+fn binary_classification_metrics(predictions: &[f32], labels: &[f32]) -> AccuracyMetrics {
+    let (mut tp, mut fp, mut fn_count) = (0u64, 0u64, 0u64);
+    for (&prediction, &label) in predictions.iter().zip(labels.iter()) {
+        match (prediction >= 0.5, label >= 0.5) {
+            (true, true) => tp += 1,
+            (true, false) => fp += 1,
+            (false, true) => fn_count += 1,
+            (false, false) => {}
+        }
+    }
+
+    let precision = if tp + fp > 0 { tp as f64 / (tp + fp) as f64 } else { 0.0 };
+    let recall = if tp + fn_count > 0 { tp as f64 / (tp + fn_count) as f64 } else { 0.0 };
+    let f1 = if precision + recall > 0.0 { 2.0 * precision * recall / (precision + recall) } else { 0.0 };
+    let auc = rank_based_auc(predictions, labels);
+
+    AccuracyMetrics {
+        precision: (precision * 10000.0) as u64,
+        recall: (recall * 10000.0) as u64,
+        f1_score: (f1 * 10000.0) as u64,
+        auc: Some((auc * 10000.0) as u64),
+        rmse: None,
+        mae: None,
+    }
+}
+
+/// Rank-based (Mann-Whitney U) AUC: the probability a random positive scores
+/// higher than a random negative. Ties are broken with averaged ranks so
+/// repeated prediction values don't bias the estimate.
+fn rank_based_auc(predictions: &[f32], labels: &[f32]) -> f64 {
+    let mut scored: Vec<(f32, bool)> =
+        predictions.iter().zip(labels.iter()).map(|(&prediction, &label)| (prediction, label >= 0.5)).collect();
+    scored.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let positive_count = scored.iter().filter(|(_, is_positive)| *is_positive).count() as f64;
+    let negative_count = scored.len() as f64 - positive_count;
+    if positive_count == 0.0 || negative_count == 0.0 {
+        return 0.5;
+    }
+
+    let mut ranks = vec![0.0f64; scored.len()];
+    let mut i = 0;
+    while i < scored.len() {
+        let mut j = i;
+        while j + 1 < scored.len() && scored[j + 1].0 == scored[i].0 {
+            j += 1;
+        }
+        let average_rank = ((i + 1) + (j + 1)) as f64 / 2.0;
+        for rank in ranks.iter_mut().take(j + 1).skip(i) {
+            *rank = average_rank;
+        }
+        i = j + 1;
+    }
+
+    let positive_rank_sum: f64 =
+        scored.iter().zip(ranks.iter()).filter(|((_, is_positive), _)| *is_positive).map(|(_, rank)| rank).sum();
+
+    (positive_rank_sum - positive_count * (positive_count + 1.0) / 2.0) / (positive_count * negative_count)
+}
+
+struct TorchScriptEngine;
+
+impl AssessmentEngine for TorchScriptEngine {
+    fn name(&self) -> &'static str {
+        "torchscript"
+    }
+
+    fn can_load(&self, model_info: &ModelInfo) -> bool {
+        model_info.framework == "pytorch"
+    }
+
+    fn load(&self, bytes: &[u8]) -> Result<LoadedModel, EnclaveError> {
+        Ok(LoadedModel { info: analyze_pytorch_model(bytes)?, bytes: bytes.to_vec() })
+    }
+
+    fn infer(&self, model: &LoadedModel, dataset: &DatasetInfo, _dataset_bytes: &[u8]) -> Result<RawPredictions, EnclaveError> {
+        Ok(RawPredictions { base_accuracy: simulated_base_accuracy(&model.info, dataset), measured: None })
+    }
+
+    fn metrics(&self, preds: &RawPredictions) -> AccuracyMetrics {
+        accuracy_metrics_from(preds.base_accuracy)
+    }
+}
+
+struct TfLiteEngine;
+
+impl AssessmentEngine for TfLiteEngine {
+    fn name(&self) -> &'static str {
+        "tflite"
+    }
+
+    fn can_load(&self, model_info: &ModelInfo) -> bool {
+        model_info.framework == "tensorflow"
+    }
+
+    fn load(&self, bytes: &[u8]) -> Result<LoadedModel, EnclaveError> {
+        Ok(LoadedModel { info: analyze_tensorflow_model(bytes)?, bytes: bytes.to_vec() })
+    }
+
+    fn infer(&self, model: &LoadedModel, dataset: &DatasetInfo, _dataset_bytes: &[u8]) -> Result<RawPredictions, EnclaveError> {
+        Ok(RawPredictions { base_accuracy: simulated_base_accuracy(&model.info, dataset), measured: None })
+    }
+
+    fn metrics(&self, preds: &RawPredictions) -> AccuracyMetrics {
+        accuracy_metrics_from(preds.base_accuracy)
+    }
+}
+
+/// Fallback engine for anything the format-specific engines above don't claim:
+/// JSON-metadata mock models, unrecognized binaries, and test fixtures.
+/// Always reports `can_load == true` so the registry can use it as a last
+/// resort.
+struct MockEngine;
+
+impl AssessmentEngine for MockEngine {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn can_load(&self, _model_info: &ModelInfo) -> bool {
+        true
+    }
+
+    fn load(&self, bytes: &[u8]) -> Result<LoadedModel, EnclaveError> {
+        let info = parse_json_model_metadata(bytes).unwrap_or_else(|_| ModelInfo {
+            model_type: "neural_network".to_string(),
+            framework: "unknown".to_string(),
+            parameters: estimate_parameters_from_size(bytes.len()),
+            input_shape: vec![1, 784],
+            output_shape: vec![1, 10],
+            dtype_distribution: HashMap::new(),
+        });
+        Ok(LoadedModel { info, bytes: bytes.to_vec() })
+    }
+
+    fn infer(&self, model: &LoadedModel, dataset: &DatasetInfo, _dataset_bytes: &[u8]) -> Result<RawPredictions, EnclaveError> {
+        Ok(RawPredictions { base_accuracy: simulated_base_accuracy(&model.info, dataset), measured: None })
+    }
+
+    fn metrics(&self, preds: &RawPredictions) -> AccuracyMetrics {
+        accuracy_metrics_from(preds.base_accuracy)
+    }
+}
+
+/// All registered format-specific engines, in the order they're tried. New
+/// backends register here without touching `process_data` or the other
+/// engines.
+fn registry() -> Vec<Box<dyn AssessmentEngine>> {
+    vec![Box::new(OnnxEngine), Box::new(TorchScriptEngine), Box::new(TfLiteEngine)]
+}
+
+/// Pick the engine that claims `model_info` (by `model_type_hint`/magic-byte
+/// sniffing already baked into `model_info.framework`), falling back to
+/// `MockEngine` when nothing else claims it.
+pub(crate) fn select_engine(model_info: &ModelInfo) -> Box<dyn AssessmentEngine> {
+    registry()
+        .into_iter()
+        .find(|engine| engine.can_load(model_info))
+        .unwrap_or_else(|| Box::new(MockEngine))
+}
+
+/// Shared, framework-independent pieces of an assessment that don't belong to
+/// any one engine: simulated timing, memory, throughput, data-integrity, and
+/// bias figures. Kept here (rather than duplicated per engine) so they stay
+/// identical across ONNX/TorchScript/TF-Lite/mock, same as before this was
+/// split into a trait.
+pub(crate) fn shared_performance_profile(model_info: &ModelInfo, dataset_info: &DatasetInfo) -> (u64, u64, u64) {
+    let base_inference_time = (model_info.parameters / 1000) + (dataset_info.rows / 10);
+    let inference_time_ms = base_inference_time.max(50).min(30000); // 50ms to 30s
+    let memory_usage_mb = (model_info.parameters * 4 / 1_048_576).max(10); // 4 bytes per param, min 10MB
+    let throughput = (100000 / inference_time_ms.max(1)).max(1); // Scaled by 100, samples per second
+    (inference_time_ms, memory_usage_mb, throughput)
+}
+
+pub(crate) fn shared_data_integrity_score(dataset_info: &DatasetInfo) -> u64 {
+    if dataset_info.columns > 5 && dataset_info.rows > 500 {
+        90
+    } else {
+        70
+    }
+}
+
+pub(crate) fn shared_bias_assessment() -> BiasAssessment {
+    BiasAssessment {
+        fairness_score: 85,
+        bias_detected: false,
+        bias_type: None,
+        demographic_parity: Some(9500), // 95.00% scaled by 10000
+        equalized_odds: Some(9300),     // 93.00% scaled by 10000
+    }
+}
+
+/// Fraction of pairwise group differences, past which `compute_fairness_metrics`
+/// flags `bias_detected`. 10% scaled by 10000, matching the other scaled
+/// percentage fields on `BiasAssessment`.
+const FAIRNESS_BIAS_THRESHOLD: u64 = 1000;
+
+/// Read the named column out of a CSV dataset, row order preserved, one
+/// string per data row (the header is skipped). Returns `None` for non-CSV
+/// formats or a column name that isn't in the header.
+fn extract_csv_column_values(dataset_info: &DatasetInfo, dataset_bytes: &[u8], column_name: &str) -> Option<Vec<String>> {
+    if dataset_info.format != "csv" {
+        return None;
+    }
+
+    let text = std::str::from_utf8(dataset_bytes).ok()?;
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next()?;
+    let column_index = super::split_csv_record(header, ',').iter().position(|name| name.trim() == column_name)?;
+
+    let mut values = Vec::new();
+    for line in lines {
+        let value = super::split_csv_record(line, ',').get(column_index)?.trim().to_string();
+        values.push(value);
+    }
+    Some(values)
+}
+
+/// Largest absolute difference between any two values in `rates`, i.e. the
+/// worst-case gap between groups. `None` when there are fewer than two groups
+/// to compare.
+fn max_pairwise_difference(rates: &[f64]) -> Option<f64> {
+    let mut max_diff = None;
+    for i in 0..rates.len() {
+        for j in (i + 1)..rates.len() {
+            let diff = (rates[i] - rates[j]).abs();
+            max_diff = Some(max_diff.map_or(diff, |current: f64| current.max(diff)));
+        }
+    }
+    max_diff
+}
+
+/// Real fairness audit: group rows by their `sensitive_attribute` column
+/// value, then compare positive-prediction rates (demographic parity) and
+/// true-positive rates (equalized odds) across groups. `None` when the
+/// dataset isn't CSV, the column is missing, or fewer than two groups are
+/// present to compare — the caller falls back to `shared_bias_assessment`
+/// in those cases.
+fn compute_fairness_metrics(
+    dataset_info: &DatasetInfo,
+    dataset_bytes: &[u8],
+    sensitive_attribute: &str,
+    predictions: &[f32],
+    labels: &[f32],
+) -> Option<BiasAssessment> {
+    let groups = extract_csv_column_values(dataset_info, dataset_bytes, sensitive_attribute)?;
+    let row_count = groups.len().min(predictions.len()).min(labels.len());
+
+    let mut by_group: HashMap<String, (usize, usize, usize, usize)> = HashMap::new(); // (positives, total, true_positives, actual_positives)
+    for i in 0..row_count {
+        let predicted_positive = predictions[i] >= 0.5;
+        let actual_positive = labels[i] >= 0.5;
+        let entry = by_group.entry(groups[i].clone()).or_insert((0, 0, 0, 0));
+        entry.1 += 1;
+        if predicted_positive {
+            entry.0 += 1;
+        }
+        if actual_positive {
+            entry.3 += 1;
+            if predicted_positive {
+                entry.2 += 1;
+            }
+        }
+    }
+
+    if by_group.len() < 2 {
+        return None;
+    }
+
+    let positive_rates: Vec<f64> = by_group.values().map(|(positives, total, _, _)| *positives as f64 / *total as f64).collect();
+    let true_positive_rates: Vec<f64> = by_group
+        .values()
+        .filter(|(_, _, _, actual_positives)| *actual_positives > 0)
+        .map(|(_, _, true_positives, actual_positives)| *true_positives as f64 / *actual_positives as f64)
+        .collect();
+
+    let demographic_parity = max_pairwise_difference(&positive_rates).map(|diff| (diff * 10000.0).round() as u64);
+    let equalized_odds = max_pairwise_difference(&true_positive_rates).map(|diff| (diff * 10000.0).round() as u64);
+
+    let bias_detected = demographic_parity.unwrap_or(0) > FAIRNESS_BIAS_THRESHOLD || equalized_odds.unwrap_or(0) > FAIRNESS_BIAS_THRESHOLD;
+    let bias_type = if !bias_detected {
+        None
+    } else if demographic_parity.unwrap_or(0) > equalized_odds.unwrap_or(0) {
+        Some("demographic_parity".to_string())
+    } else {
+        Some("equalized_odds".to_string())
+    };
+
+    let worst_gap = demographic_parity.unwrap_or(0).max(equalized_odds.unwrap_or(0));
+    let fairness_score = 100u64.saturating_sub(worst_gap / 100);
+
+    Some(BiasAssessment { fairness_score, bias_detected, bias_type, demographic_parity, equalized_odds })
+}
+
+/// One MLPerf-LoadGen query-issue scenario, selected for a `ComprehensiveBenchmark`
+/// run via a `scenario:<name>` entry in the request's `quality_metrics` (case
+/// insensitive), defaulting to `SingleStream` when none is named.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BenchmarkScenario {
+    /// One query at a time, waiting for each to finish before issuing the next.
+    SingleStream,
+    /// Queries issued in fixed-size bundles; a bundle is only as fast as its
+    /// slowest member.
+    MultiStream,
+    /// Judged against a target QPS: the harness reports whether the p99
+    /// latency stayed inside the per-query budget that target rate implies.
+    /// Queries are issued one at a time with no inter-arrival delay;
+    /// Poisson-distributed arrivals are not simulated.
+    Server,
+    /// All queries issued back-to-back with no inter-query wait. Reports the
+    /// same latency percentiles as `SingleStream`; no separate throughput
+    /// figure is computed.
+    Offline,
+}
+
+impl BenchmarkScenario {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            BenchmarkScenario::SingleStream => "SingleStream",
+            BenchmarkScenario::MultiStream => "MultiStream",
+            BenchmarkScenario::Server => "Server",
+            BenchmarkScenario::Offline => "Offline",
+        }
+    }
+
+    pub(crate) fn from_quality_metrics(quality_metrics: &[String]) -> Self {
+        quality_metrics
+            .iter()
+            .filter_map(|metric| metric.split_once(':'))
+            .find(|(key, _)| key.eq_ignore_ascii_case("scenario"))
+            .and_then(|(_, name)| match name.to_ascii_lowercase().as_str() {
+                "singlestream" => Some(BenchmarkScenario::SingleStream),
+                "multistream" => Some(BenchmarkScenario::MultiStream),
+                "server" => Some(BenchmarkScenario::Server),
+                "offline" => Some(BenchmarkScenario::Offline),
+                _ => None,
+            })
+            .unwrap_or(BenchmarkScenario::SingleStream)
+    }
+}
+
+/// Number of synthetic queries issued per benchmark run. Small enough to keep
+/// a `ComprehensiveBenchmark` request fast even when an engine's `infer`
+/// rebuilds a real model session per call (as `OnnxEngine` does).
+const BENCHMARK_QUERY_COUNT: usize = 32;
+/// `MultiStream` issues queries in bundles of this size.
+const MULTISTREAM_BUNDLE_SIZE: usize = 4;
+/// Target queries-per-second the `Server` scenario is benchmarked against.
+const SERVER_TARGET_QPS: u64 = 20;
+
+/// Outcome of running one `BenchmarkScenario` against a loaded model.
+pub(crate) struct BenchmarkOutcome {
+    pub(crate) scenario: &'static str,
+    pub(crate) p50_latency_ms: u64,
+    pub(crate) p90_latency_ms: u64,
+    pub(crate) p99_latency_ms: u64,
+    /// Only meaningful for `Server`: whether p99 latency fit inside the
+    /// per-query time budget implied by `SERVER_TARGET_QPS`.
+    pub(crate) latency_sla_met: Option<bool>,
+}
+
+/// Nearest-rank percentile over an already-sorted sample, per the usual
+/// MLPerf-LoadGen convention (`ceil(percentile/100 * n)`th smallest value).
+fn nearest_rank_percentile(sorted_ms: &[u64], percentile: f64) -> u64 {
+    if sorted_ms.is_empty() {
+        return 0;
+    }
+    let rank = ((percentile / 100.0) * sorted_ms.len() as f64).ceil() as usize;
+    let index = rank.saturating_sub(1).min(sorted_ms.len() - 1);
+    sorted_ms[index]
+}
+
+/// Run `scenario` against `loaded`/`dataset_info`/`dataset_bytes` through
+/// `engine`, timing each query's `infer` call, and fold the collected
+/// latencies into percentiles. Queries that themselves error are skipped
+/// (their latency doesn't count toward the sample) rather than aborting the
+/// whole benchmark, since a handful of unsupported-backend errors shouldn't
+/// blank out an otherwise-useful percentile spread.
+pub(crate) fn run_benchmark_scenario(
+    engine: &dyn AssessmentEngine,
+    loaded: &LoadedModel,
+    dataset_info: &DatasetInfo,
+    dataset_bytes: &[u8],
+    scenario: BenchmarkScenario,
+) -> BenchmarkOutcome {
+    let mut latencies_ms: Vec<u64> = match scenario {
+        BenchmarkScenario::MultiStream => {
+            let mut latencies = Vec::with_capacity(BENCHMARK_QUERY_COUNT);
+            let bundle_count = (BENCHMARK_QUERY_COUNT + MULTISTREAM_BUNDLE_SIZE - 1) / MULTISTREAM_BUNDLE_SIZE;
+            for _ in 0..bundle_count {
+                let bundle_start = std::time::Instant::now();
+                let mut completed = 0;
+                for _ in 0..MULTISTREAM_BUNDLE_SIZE {
+                    if engine.infer(loaded, dataset_info, dataset_bytes).is_ok() {
+                        completed += 1;
+                    }
+                }
+                let bundle_latency_ms = bundle_start.elapsed().as_millis() as u64;
+                // The bundle only completes once its slowest member does, so
+                // every query in it is charged the whole bundle's latency.
+                latencies.extend(std::iter::repeat(bundle_latency_ms).take(completed));
+            }
+            latencies
+        }
+        // SingleStream, Server, and Offline all issue one query at a time with
+        // no artificial inter-query delay; they differ only in how the
+        // resulting latency sample is interpreted below.
+        BenchmarkScenario::SingleStream | BenchmarkScenario::Server | BenchmarkScenario::Offline => {
+            (0..BENCHMARK_QUERY_COUNT)
+                .filter_map(|_| {
+                    let query_start = std::time::Instant::now();
+                    engine.infer(loaded, dataset_info, dataset_bytes).ok()?;
+                    Some(query_start.elapsed().as_millis() as u64)
+                })
+                .collect()
+        }
+    };
+    latencies_ms.sort_unstable();
+
+    let p50_latency_ms = nearest_rank_percentile(&latencies_ms, 50.0);
+    let p90_latency_ms = nearest_rank_percentile(&latencies_ms, 90.0);
+    let p99_latency_ms = nearest_rank_percentile(&latencies_ms, 99.0);
+
+    let latency_sla_met = match scenario {
+        BenchmarkScenario::Server => {
+            // Budget per query at the target rate; p99 must fit inside it for
+            // the enclave to keep up with SERVER_TARGET_QPS without queueing
+            // unboundedly.
+            let budget_ms = (1000 / SERVER_TARGET_QPS.max(1)).max(1);
+            Some(p99_latency_ms <= budget_ms)
+        }
+        _ => None,
+    };
+
+    BenchmarkOutcome {
+        scenario: scenario.name(),
+        p50_latency_ms,
+        p90_latency_ms,
+        p99_latency_ms,
+        latency_sla_met,
+    }
+}
+
+/// Re-run `engine`'s inference with numeric inputs quantized to INT8 (via
+/// `infer_int8`) and compare its F1 against the already-measured `fp32_accuracy`,
+/// recommending a deployment precision from the resulting drop. Returns `None`
+/// when `engine` has no real quantizable inference path (`supports_int8_sensitivity`)
+/// or when the INT8 re-run itself errors (e.g. the dataset isn't numeric CSV) —
+/// a buyer gets no quantization claim rather than a fabricated one.
+fn assess_quantization_sensitivity(
+    engine: &dyn AssessmentEngine,
+    loaded: &LoadedModel,
+    dataset_info: &DatasetInfo,
+    dataset_bytes: &[u8],
+    fp32_accuracy: &AccuracyMetrics,
+) -> Option<QuantizationAssessment> {
+    if !engine.supports_int8_sensitivity() {
+        return None;
+    }
+    let int8_preds = engine.infer_int8(loaded, dataset_info, dataset_bytes).ok()?;
+    let int8_accuracy = engine.metrics(&int8_preds);
+
+    let fp32_f1 = fp32_accuracy.f1_score;
+    let int8_f1 = int8_accuracy.f1_score;
+    let accuracy_drop = fp32_f1.saturating_sub(int8_f1);
+
+    // Scaled-by-10000 thresholds: under 1% F1 drop is safe to deploy at INT8;
+    // under 5% can still get away with FP16; anything worse should stay FP32.
+    let recommended_precision = if accuracy_drop <= 100 {
+        "int8"
+    } else if accuracy_drop <= 500 {
+        "fp16"
+    } else {
+        "fp32"
+    };
+
+    Some(QuantizationAssessment {
+        fp32_f1,
+        int8_f1,
+        accuracy_drop,
+        recommended_precision: recommended_precision.to_string(),
+    })
+}
+
+/// Run the full local assessment pipeline for one model/dataset pair: select
+/// an engine for `model_info`, load the model bytes through it, run its
+/// inference (real, where the engine supports it; simulated otherwise), and
+/// fold the engine's metrics together with the shared timing/memory/integrity/
+/// bias figures into an `AssessmentResult`. For `AssessmentType::ComprehensiveBenchmark`,
+/// also runs the MLPerf-LoadGen-style scenario named in `quality_metrics`
+/// (see `BenchmarkScenario::from_quality_metrics`) and folds its latency
+/// percentiles in alongside the usual single-shot `inference_time_ms`.
+/// Quantization sensitivity (`assess_quantization_sensitivity`) is attempted
+/// unconditionally, independent of `assessment_type`, since it's cheap
+/// relative to the benchmark harness and useful on every request. When
+/// `sensitive_attribute` names a dataset column and the engine exposes real
+/// per-row predictions, `bias_assessment` is a real `compute_fairness_metrics`
+/// audit rather than the `shared_bias_assessment` placeholder.
+pub(crate) fn assess(
+    model_bytes: &[u8],
+    model_info: &ModelInfo,
+    dataset_info: &DatasetInfo,
+    dataset_bytes: &[u8],
+    assessment_type: &AssessmentType,
+    quality_metrics: &[String],
+    sensitive_attribute: Option<&str>,
+) -> Result<AssessmentResult, EnclaveError> {
+    let engine = select_engine(model_info);
+    let loaded = engine.load(model_bytes)?;
+    let preds = engine.infer(&loaded, dataset_info, dataset_bytes)?;
+    let accuracy = engine.metrics(&preds);
+
+    let overall_quality_score = ((preds.base_accuracy * 85.0) + 10.0) as u64; // 10-95 range
+    let (inference_time_ms, memory_usage_mb, throughput) = shared_performance_profile(model_info, dataset_info);
+
+    let benchmark = matches!(assessment_type, AssessmentType::ComprehensiveBenchmark).then(|| {
+        let scenario = BenchmarkScenario::from_quality_metrics(quality_metrics);
+        run_benchmark_scenario(engine.as_ref(), &loaded, dataset_info, dataset_bytes, scenario)
+    });
+
+    let quantization = assess_quantization_sensitivity(engine.as_ref(), &loaded, dataset_info, dataset_bytes, &accuracy);
+
+    let bias_assessment = sensitive_attribute
+        .and_then(|attribute| {
+            let (predictions, labels) = engine.raw_predictions(&loaded, dataset_info, dataset_bytes)?;
+            compute_fairness_metrics(dataset_info, dataset_bytes, attribute, &predictions, &labels)
+        })
+        .unwrap_or_else(shared_bias_assessment);
+
+    Ok(AssessmentResult {
+        overall_quality_score,
+        accuracy,
+        inference_time_ms,
+        memory_usage_mb,
+        throughput,
+        data_integrity_score: shared_data_integrity_score(dataset_info),
+        bias_assessment,
+        benchmark_scenario: benchmark.as_ref().map(|outcome| outcome.scenario.to_string()),
+        p50_latency_ms: benchmark.as_ref().map(|outcome| outcome.p50_latency_ms),
+        p90_latency_ms: benchmark.as_ref().map(|outcome| outcome.p90_latency_ms),
+        p99_latency_ms: benchmark.as_ref().map(|outcome| outcome.p99_latency_ms),
+        latency_sla_met: benchmark.as_ref().and_then(|outcome| outcome.latency_sla_met),
+        quantization,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model_info(framework: &str, parameters: u64) -> ModelInfo {
+        ModelInfo {
+            model_type: "test".to_string(),
+            framework: framework.to_string(),
+            parameters,
+            input_shape: vec![1, 10],
+            output_shape: vec![1, 3],
+            dtype_distribution: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn selects_format_specific_engine_over_mock() {
+        let onnx = select_engine(&model_info("onnx", 100_000));
+        assert_eq!(onnx.name(), "onnx");
+
+        let torch = select_engine(&model_info("pytorch", 100_000));
+        assert_eq!(torch.name(), "torchscript");
+
+        let tf = select_engine(&model_info("tensorflow", 100_000));
+        assert_eq!(tf.name(), "tflite");
+    }
+
+    #[test]
+    fn falls_back_to_mock_for_unknown_framework() {
+        let engine = select_engine(&model_info("unknown", 100_000));
+        assert_eq!(engine.name(), "mock");
+    }
+}
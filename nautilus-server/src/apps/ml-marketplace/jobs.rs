@@ -0,0 +1,331 @@
+// Copyright (c) Satya Data Marketplace
+// Asynchronous assessment job subsystem with status polling and cancellation
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::Json;
+use fastcrypto::ed25519::Ed25519KeyPair;
+use fastcrypto::traits::KeyPair;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use super::signature::{self, SignedAttestation};
+use super::types::{AssessmentConfig, AssessmentResponse, AssessmentStatus, BenchmarkConfig, MLMarketplaceError};
+use super::{AssessmentType, MLQualityRequest};
+
+/// A queued unit of work. `timeout_seconds` is the per-`AssessmentType`
+/// default from `default_timeout_for`, which the worker enforces; timeouts
+/// set in an `AssessmentConfig`/`BenchmarkConfig` are not consulted.
+#[derive(Debug, Clone)]
+pub struct JobSubmission {
+    pub request_id: String,
+    pub request: MLQualityRequest,
+    pub timeout_seconds: u64,
+}
+
+/// In-memory record of a submitted job's lifecycle. Mirrors `AssessmentResponse`
+/// closely so it can be persisted/restored directly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub request_id: String,
+    pub status: AssessmentStatus,
+    pub response: Option<AssessmentResponse>,
+    pub submitted_at: u64,
+}
+
+/// Background job subsystem: a bounded queue drained by a fixed-size tokio
+/// worker pool, plus a table of job records that `AppState` is expected to hold
+/// (e.g. `AppState { jobs: Arc<JobStore>, .. }`) so status survives brief
+/// restarts when the table is backed by the same persistence layer as
+/// attestations.
+pub struct JobStore {
+    records: Mutex<HashMap<String, JobRecord>>,
+    handles: Mutex<HashMap<String, JoinHandle<()>>>,
+    sender: mpsc::Sender<JobSubmission>,
+    receiver: Mutex<Option<mpsc::Receiver<JobSubmission>>>,
+    /// Signs every successful job's `TEEAttestation` before it's recorded, so
+    /// `AssessmentResponse::signed_attestation` is always populated rather
+    /// than left for each `process` closure to remember to do itself. See
+    /// `signature::sign_attestation_ed25519`.
+    signing_key: Ed25519KeyPair,
+}
+
+impl JobStore {
+    /// Construct the shared store and its bounded queue (capacity
+    /// `queue_capacity`), signing completed jobs' attestations with
+    /// `signing_key`. Call `spawn_worker_pool` once, after construction, to
+    /// start draining it.
+    pub fn new(queue_capacity: usize, signing_key: Ed25519KeyPair) -> Arc<Self> {
+        let (sender, receiver) = mpsc::channel(queue_capacity);
+        Arc::new(Self {
+            records: Mutex::new(HashMap::new()),
+            handles: Mutex::new(HashMap::new()),
+            sender,
+            receiver: Mutex::new(Some(receiver)),
+            signing_key,
+        })
+    }
+
+    pub async fn submit(&self, request: MLQualityRequest) -> Result<String, MLMarketplaceError> {
+        let request_id = Uuid::new_v4().to_string();
+        let timeout_seconds = default_timeout_for(&request.assessment_type);
+
+        let submitted_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        let record = JobRecord {
+            request_id: request_id.clone(),
+            status: AssessmentStatus::Pending,
+            response: None,
+            submitted_at,
+        };
+        self.records.lock().await.insert(request_id.clone(), record);
+
+        self.sender
+            .send(JobSubmission {
+                request_id: request_id.clone(),
+                request,
+                timeout_seconds,
+            })
+            .await
+            .map_err(|_| MLMarketplaceError::InferenceError("job queue is closed".to_string()))?;
+
+        Ok(request_id)
+    }
+
+    pub async fn status(&self, request_id: &str) -> Option<JobRecord> {
+        self.records.lock().await.get(request_id).cloned()
+    }
+
+    /// Abort the worker task for `request_id` (if still running) and mark the
+    /// job `Failed` with a cancellation note.
+    pub async fn cancel(&self, request_id: &str) -> Result<(), MLMarketplaceError> {
+        let mut handles = self.handles.lock().await;
+        if let Some(handle) = handles.remove(request_id) {
+            handle.abort();
+        }
+        drop(handles);
+
+        let mut records = self.records.lock().await;
+        match records.get_mut(request_id) {
+            Some(record) => {
+                record.status = AssessmentStatus::Failed;
+                record.response = Some(AssessmentResponse {
+                    request_id: request_id.to_string(),
+                    status: AssessmentStatus::Failed,
+                    attestation: None,
+                    signed_attestation: None,
+                    error_message: Some("cancelled by caller".to_string()),
+                    estimated_completion_time: None,
+                });
+                Ok(())
+            }
+            None => Err(MLMarketplaceError::ModelValidationError(format!(
+                "unknown request_id: {}",
+                request_id
+            ))),
+        }
+    }
+
+    /// Start the worker pool, draining the queue and executing each job with
+    /// `process` (typically a thin wrapper around `process_data`), enforcing
+    /// `timeout_seconds` per job via `tokio::time::timeout`. Spawns itself onto
+    /// the current tokio runtime and returns immediately; call once per
+    /// `JobStore`.
+    pub fn spawn_worker_pool<F, Fut>(self: &Arc<Self>, worker_count: usize, process: F)
+    where
+        F: Fn(MLQualityRequest) -> Fut + Send + Sync + Clone + 'static,
+        Fut: std::future::Future<Output = Result<AssessmentResponse, MLMarketplaceError>> + Send + 'static,
+    {
+        let receiver = self
+            .receiver
+            .try_lock()
+            .ok()
+            .and_then(|mut guard| guard.take())
+            .expect("spawn_worker_pool called more than once");
+        let shared_receiver = Arc::new(Mutex::new(receiver));
+
+        for _ in 0..worker_count {
+            let store = self.clone();
+            let process = process.clone();
+            let shared_receiver = shared_receiver.clone();
+            tokio::spawn(async move {
+                loop {
+                    let job = {
+                        let mut receiver = shared_receiver.lock().await;
+                        receiver.recv().await
+                    };
+                    match job {
+                        Some(job) => store.run_job(job, process.clone()).await,
+                        None => {
+                            warn!("job queue closed; worker exiting");
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+    }
+
+    /// Sign `attestation` with `self.signing_key` and self-verify the result
+    /// before handing it back, so a bug in signing/serialization fails here
+    /// rather than shipping an attestation nobody can actually verify.
+    fn sign_attestation(&self, attestation: &super::types::TEEAttestation) -> Result<SignedAttestation, MLMarketplaceError> {
+        let signature = signature::sign_attestation_ed25519(attestation, &self.signing_key)?;
+        let public_key = self.signing_key.public().as_ref().to_vec();
+
+        if !signature::verify_attestation(attestation, &signature, &public_key)? {
+            return Err(MLMarketplaceError::CryptoError(
+                "freshly-signed attestation failed its own verification".to_string(),
+            ));
+        }
+
+        Ok(SignedAttestation {
+            attestation: attestation.clone(),
+            signature,
+            public_key,
+        })
+    }
+
+    async fn run_job<F, Fut>(&self, job: JobSubmission, process: F)
+    where
+        F: Fn(MLQualityRequest) -> Fut,
+        Fut: std::future::Future<Output = Result<AssessmentResponse, MLMarketplaceError>>,
+    {
+        {
+            let mut records = self.records.lock().await;
+            if let Some(record) = records.get_mut(&job.request_id) {
+                record.status = AssessmentStatus::Processing;
+            }
+        }
+
+        let timeout = std::time::Duration::from_secs(job.timeout_seconds);
+        let result = tokio::time::timeout(timeout, process(job.request.clone())).await;
+
+        let mut records = self.records.lock().await;
+        let Some(record) = records.get_mut(&job.request_id) else {
+            return;
+        };
+
+        match result {
+            Ok(Ok(mut response)) => {
+                if let Some(attestation) = response.attestation.clone() {
+                    match self.sign_attestation(&attestation) {
+                        Ok(signed) => response.signed_attestation = Some(signed),
+                        Err(e) => error!("job {} produced an attestation that failed to sign: {}", job.request_id, e),
+                    }
+                }
+                record.status = response.status.clone();
+                record.response = Some(response);
+                info!("job {} completed", job.request_id);
+            }
+            Ok(Err(e)) => {
+                record.status = AssessmentStatus::Failed;
+                record.response = Some(AssessmentResponse {
+                    request_id: job.request_id.clone(),
+                    status: AssessmentStatus::Failed,
+                    attestation: None,
+                    signed_attestation: None,
+                    error_message: Some(e.to_string()),
+                    estimated_completion_time: None,
+                });
+            }
+            Err(_) => {
+                record.status = AssessmentStatus::Timeout;
+                record.response = Some(AssessmentResponse {
+                    request_id: job.request_id.clone(),
+                    status: AssessmentStatus::Timeout,
+                    attestation: None,
+                    signed_attestation: None,
+                    error_message: Some(format!(
+                        "assessment exceeded {}s timeout",
+                        job.timeout_seconds
+                    )),
+                    estimated_completion_time: None,
+                });
+                warn!("job {} timed out after {}s", job.request_id, job.timeout_seconds);
+            }
+        }
+    }
+}
+
+fn default_timeout_for(assessment_type: &AssessmentType) -> u64 {
+    match assessment_type {
+        AssessmentType::BasicValidation => 30,
+        AssessmentType::QuickAssess => 60,
+        AssessmentType::ComprehensiveBenchmark => 900,
+        AssessmentType::BiasAudit => 300,
+    }
+}
+
+#[allow(dead_code)]
+fn timeout_from_assessment_config(config: &AssessmentConfig) -> u64 {
+    config.timeout_seconds.unwrap_or(300)
+}
+
+#[allow(dead_code)]
+fn timeout_from_benchmark_config(config: &BenchmarkConfig) -> u64 {
+    config.timeout_per_model_seconds
+}
+
+/// `POST /assessments` — submit a job and return immediately with status `Pending`.
+pub async fn submit_assessment(
+    State(store): State<Arc<JobStore>>,
+    Json(request): Json<MLQualityRequest>,
+) -> Result<Json<JobRecord>, Json<MLMarketplaceErrorResponse>> {
+    super::versioning::check_api_version(request.api_version)
+        .map_err(|e| Json(MLMarketplaceErrorResponse::from(e)))?;
+
+    let request_id = store
+        .submit(request)
+        .await
+        .map_err(|e| Json(MLMarketplaceErrorResponse::from(e)))?;
+    let record = store.status(&request_id).await.expect("just inserted");
+    Ok(Json(record))
+}
+
+/// `GET /assessments/{request_id}` — current status of a submitted job.
+pub async fn get_assessment_status(
+    State(store): State<Arc<JobStore>>,
+    Path(request_id): Path<String>,
+) -> Result<Json<JobRecord>, Json<MLMarketplaceErrorResponse>> {
+    store
+        .status(&request_id)
+        .await
+        .map(Json)
+        .ok_or_else(|| Json(MLMarketplaceErrorResponse {
+            error: format!("unknown request_id: {}", request_id),
+        }))
+}
+
+/// `POST /assessments/{request_id}/cancel` — abort a running/queued job.
+pub async fn cancel_assessment(
+    State(store): State<Arc<JobStore>>,
+    Path(request_id): Path<String>,
+) -> Result<Json<JobRecord>, Json<MLMarketplaceErrorResponse>> {
+    store
+        .cancel(&request_id)
+        .await
+        .map_err(|e| Json(MLMarketplaceErrorResponse::from(e)))?;
+    let record = store.status(&request_id).await.expect("just cancelled");
+    Ok(Json(record))
+}
+
+#[derive(Debug, Serialize)]
+pub struct MLMarketplaceErrorResponse {
+    pub error: String,
+}
+
+impl From<MLMarketplaceError> for MLMarketplaceErrorResponse {
+    fn from(e: MLMarketplaceError) -> Self {
+        Self { error: e.to_string() }
+    }
+}
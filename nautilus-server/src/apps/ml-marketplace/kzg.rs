@@ -0,0 +1,521 @@
+// KZG polynomial commitments over dataset columns.
+//
+// A dataset column of N values is treated as the evaluations of a
+// degree-(N-1) polynomial p over the domain {0, 1, ..., N-1}. Given a
+// trusted-setup SRS of powers of tau in G1 ([1]_1, [s]_1, [s^2]_1, ...) and
+// [s]_2 in G2, the commitment is C = [p(s)]_1: an multi-scalar multiplication
+// of the SRS points by p's monomial coefficients. An opening at a challenged
+// point z attaches the quotient commitment pi = [q(s)]_1 for
+// q(x) = (p(x) - p(z)) / (x - z), which a verifier holding the SRS checks via
+// e(pi, [s]_2 - [z]_2) == e(C - [p(z)]_1, [1]_2) without needing the dataset
+// itself. Column openings from `commit_and_open` are verified off-enclave,
+// on the SRS holder's side; dataset-wide openings (`commit_dataset` /
+// `open_dataset_row`) are checked in-enclave by `verify_dataset_opening`.
+//
+// Field arithmetic is done directly over the BLS12-381 scalar field with
+// `num_bigint::BigUint` rather than guessing at a specific field-element API,
+// and only converted to `fastcrypto`'s group types at the point of each
+// scalar multiplication.
+
+use crate::EnclaveError;
+use fastcrypto::groups::bls12381::{G1Element, G2Element};
+use fastcrypto::groups::GroupElement;
+use fastcrypto::serde_helpers::ToFromByteArray;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// KZG commitment to one dataset column.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KzgCommitment {
+    /// Base64-encoded compressed G1 point `C = [p(s)]_1`.
+    pub commitment_g1_b64: String,
+    /// Number of real (unpadded) values committed.
+    pub num_elements: u64,
+    /// `num_elements` padded up to the next power of two, the polynomial's
+    /// effective evaluation-domain size.
+    pub padded_len: u64,
+}
+
+/// Opening proof that the committed polynomial evaluates to `value` at
+/// `challenge_point`, both given as hex-encoded big-endian BLS12-381 scalar
+/// field elements.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KzgOpening {
+    /// Challenge point z: for `commit_and_open`, a Fiat-Shamir point derived
+    /// from the dataset hash so no interactive round-trip with a verifier is
+    /// required; for `open_dataset_row`, the opened element's index.
+    pub challenge_point_hex: String,
+    /// Claimed p(z).
+    pub value_hex: String,
+    /// Base64-encoded compressed G1 point `pi = [q(s)]_1`.
+    pub proof_g1_b64: String,
+}
+
+/// Structured Reference String: `max_degree + 1` powers of tau in G1 and
+/// `[s]_2` in G2 from a trusted-setup ceremony, loaded once and cached for
+/// the enclave's lifetime, mirroring how `seal_impl::ML_SEAL_CONFIG` is
+/// loaded once via `lazy_static`.
+struct KzgSrs {
+    powers_of_tau_g1: Vec<G1Element>,
+    /// Used by `verify_dataset_opening`'s in-enclave pairing check; column-
+    /// level openings from `commit_and_open` are still verified off-enclave.
+    tau_g2: G2Element,
+}
+
+impl KzgSrs {
+    fn max_degree(&self) -> usize {
+        self.powers_of_tau_g1.len().saturating_sub(1)
+    }
+
+    /// Parse a flat binary SRS file: an 8-byte little-endian power count,
+    /// that many 48-byte compressed G1 points, then one 96-byte compressed
+    /// G2 point for `[s]_2`.
+    fn load_from_path(path: &str) -> Result<KzgSrs, String> {
+        let bytes = std::fs::read(path).map_err(|e| format!("Failed to read KZG SRS file {}: {}", path, e))?;
+        if bytes.len() < 8 {
+            return Err("KZG SRS file truncated before the power count".to_string());
+        }
+        let num_powers = u64::from_le_bytes(bytes[0..8].try_into().unwrap()) as usize;
+
+        let mut offset = 8usize;
+        let mut powers_of_tau_g1 = Vec::with_capacity(num_powers);
+        for i in 0..num_powers {
+            let chunk: [u8; 48] = bytes
+                .get(offset..offset + 48)
+                .ok_or_else(|| format!("KZG SRS file truncated at G1 power {}", i))?
+                .try_into()
+                .unwrap();
+            powers_of_tau_g1.push(
+                G1Element::from_byte_array(&chunk).map_err(|e| format!("Invalid G1 power {} in SRS: {}", i, e))?,
+            );
+            offset += 48;
+        }
+
+        let g2_chunk: [u8; 96] = bytes
+            .get(offset..offset + 96)
+            .ok_or("KZG SRS file missing trailing [s]_2 point")?
+            .try_into()
+            .unwrap();
+        let tau_g2 =
+            G2Element::from_byte_array(&g2_chunk).map_err(|e| format!("Invalid [s]_2 in SRS: {}", e))?;
+
+        Ok(KzgSrs { powers_of_tau_g1, tau_g2 })
+    }
+}
+
+lazy_static::lazy_static! {
+    /// SRS loaded from `KZG_SRS_PATH` (default `kzg_srs.bin`) on first use.
+    static ref KZG_SRS: Result<KzgSrs, String> = {
+        let path = std::env::var("KZG_SRS_PATH").unwrap_or_else(|_| "kzg_srs.bin".to_string());
+        KzgSrs::load_from_path(&path)
+    };
+}
+
+/// BLS12-381 scalar field modulus `r`.
+fn fr_modulus() -> BigUint {
+    BigUint::parse_bytes(
+        b"52435875175126190479447740508185965837690552500527637822603658699938581184513",
+        10,
+    )
+    .expect("hardcoded BLS12-381 Fr modulus must parse")
+}
+
+fn fr_add(a: &BigUint, b: &BigUint, m: &BigUint) -> BigUint {
+    (a + b) % m
+}
+
+fn fr_sub(a: &BigUint, b: &BigUint, m: &BigUint) -> BigUint {
+    (a + m - (b % m)) % m
+}
+
+fn fr_mul(a: &BigUint, b: &BigUint, m: &BigUint) -> BigUint {
+    (a * b) % m
+}
+
+fn fr_inv(a: &BigUint, m: &BigUint) -> BigUint {
+    // Fermat's little theorem: a^(p-2) == a^-1 (mod p) for prime p.
+    a.modpow(&(m - BigUint::from(2u32)), m)
+}
+
+/// Multiply `poly` (low-to-high coefficients) by the linear factor `(x - root)`.
+fn poly_mul_linear(poly: &[BigUint], root: &BigUint, m: &BigUint) -> Vec<BigUint> {
+    let mut result = vec![BigUint::zero(); poly.len() + 1];
+    for (i, c) in poly.iter().enumerate() {
+        result[i + 1] = fr_add(&result[i + 1], c, m);
+        result[i] = fr_sub(&result[i], &fr_mul(c, root, m), m);
+    }
+    result
+}
+
+/// Exact synthetic division of `poly` by `(x - root)`, assuming `poly(root) == 0`.
+fn poly_div_linear(poly: &[BigUint], root: &BigUint, m: &BigUint) -> Vec<BigUint> {
+    let n = poly.len();
+    let mut q = vec![BigUint::zero(); n - 1];
+    q[n - 2] = poly[n - 1].clone();
+    for k in (1..=n - 2).rev() {
+        q[k - 1] = fr_add(&poly[k], &fr_mul(root, &q[k], m), m);
+    }
+    q
+}
+
+fn poly_eval(coeffs: &[BigUint], z: &BigUint, m: &BigUint) -> BigUint {
+    let mut acc = BigUint::zero();
+    for c in coeffs.iter().rev() {
+        acc = fr_add(&fr_mul(&acc, z, m), c, m);
+    }
+    acc
+}
+
+/// Interpolate the monomial coefficients of the unique degree-`<padded_len`
+/// polynomial through `(j, values[j])` for `j` in `0..values.len()`, zero
+/// elsewhere, over the domain `{0, ..., padded_len - 1}`.
+fn interpolate_coefficients(values: &[u64], padded_len: usize, m: &BigUint) -> Vec<BigUint> {
+    let values: Vec<BigUint> = values.iter().map(|&v| BigUint::from(v)).collect();
+    interpolate_coefficients_fr(&values, padded_len, m)
+}
+
+/// Same as `interpolate_coefficients`, taking field elements directly rather
+/// than `u64`s, for commitments (like `commit_dataset`) whose values don't
+/// fit in a `u64`.
+fn interpolate_coefficients_fr(values: &[BigUint], padded_len: usize, m: &BigUint) -> Vec<BigUint> {
+    let mut ys: Vec<BigUint> = values.iter().map(|v| v % m).collect();
+    ys.resize(padded_len, BigUint::zero());
+    let xs: Vec<BigUint> = (0..padded_len as u64).map(BigUint::from).collect();
+
+    let mut full_poly = vec![BigUint::one()];
+    for x_j in &xs {
+        full_poly = poly_mul_linear(&full_poly, x_j, m);
+    }
+
+    let mut result = vec![BigUint::zero(); padded_len];
+    for (j, y_j) in ys.iter().enumerate() {
+        if y_j.is_zero() {
+            continue;
+        }
+        let quotient = poly_div_linear(&full_poly, &xs[j], m);
+        let mut denom = BigUint::one();
+        for (k, x_k) in xs.iter().enumerate() {
+            if k == j {
+                continue;
+            }
+            denom = fr_mul(&denom, &fr_sub(&xs[j], x_k, m), m);
+        }
+        let scale = fr_mul(y_j, &fr_inv(&denom, m), m);
+        for (c, q) in result.iter_mut().zip(quotient.iter()) {
+            *c = fr_add(c, &fr_mul(q, &scale, m), m);
+        }
+    }
+    result
+}
+
+fn biguint_to_g1_scalar_bytes(value: &BigUint) -> [u8; 32] {
+    let bytes = value.to_bytes_be();
+    let mut array = [0u8; 32];
+    array[32 - bytes.len()..].copy_from_slice(&bytes);
+    array
+}
+
+fn msm_g1(bases: &[G1Element], scalars: &[BigUint]) -> Result<G1Element, EnclaveError> {
+    use fastcrypto::groups::bls12381::Scalar as FrScalar;
+
+    let mut acc = G1Element::zero();
+    for (base, scalar) in bases.iter().zip(scalars.iter()) {
+        let scalar_bytes = biguint_to_g1_scalar_bytes(scalar);
+        let fr_scalar = FrScalar::from_byte_array(&scalar_bytes)
+            .map_err(|e| EnclaveError::GenericError(format!("Failed to encode KZG scalar: {}", e)))?;
+        acc = acc + (*base * fr_scalar);
+    }
+    Ok(acc)
+}
+
+fn encode_g1(point: &G1Element) -> String {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD.encode(point.to_byte_array())
+}
+
+/// Derive a Fiat-Shamir challenge point in the scalar field from the
+/// dataset's SHA-256 hash, so the opening doesn't require an interactive
+/// round-trip with a verifier.
+fn fiat_shamir_challenge(seed: &[u8], m: &BigUint) -> BigUint {
+    let mut hasher = Sha256::new();
+    hasher.update(b"SATYA_KZG_CHALLENGE_V1:");
+    hasher.update(seed);
+    let digest = hasher.finalize();
+    BigUint::from_bytes_be(&digest) % m
+}
+
+/// Commit to `values` with KZG and produce a non-interactive opening at a
+/// Fiat-Shamir challenge point derived from `fiat_shamir_seed` (the
+/// dataset's own hash). Rejects columns whose power-of-two-padded length
+/// exceeds the loaded SRS's committable degree.
+pub fn commit_and_open(values: &[u64], fiat_shamir_seed: &[u8]) -> Result<(KzgCommitment, KzgOpening), EnclaveError> {
+    let srs = KZG_SRS
+        .as_ref()
+        .map_err(|e| EnclaveError::GenericError(format!("KZG SRS unavailable: {}", e)))?;
+
+    let padded_len = values.len().max(1).next_power_of_two();
+    if padded_len > srs.powers_of_tau_g1.len() {
+        return Err(EnclaveError::GenericError(format!(
+            "dataset column has {} value(s) (padded to {}), exceeding the SRS's committable degree of {}",
+            values.len(),
+            padded_len,
+            srs.max_degree()
+        )));
+    }
+
+    let m = fr_modulus();
+    let coeffs = interpolate_coefficients(values, padded_len, &m);
+    let commitment_point = msm_g1(&srs.powers_of_tau_g1[..coeffs.len()], &coeffs)?;
+
+    let challenge = fiat_shamir_challenge(fiat_shamir_seed, &m);
+    let value_at_challenge = poly_eval(&coeffs, &challenge, &m);
+
+    let mut shifted_coeffs = coeffs.clone();
+    shifted_coeffs[0] = fr_sub(&shifted_coeffs[0], &value_at_challenge, &m);
+    let quotient = poly_div_linear(&shifted_coeffs, &challenge, &m);
+    let opening_point = msm_g1(&srs.powers_of_tau_g1[..quotient.len()], &quotient)?;
+
+    Ok((
+        KzgCommitment {
+            commitment_g1_b64: encode_g1(&commitment_point),
+            num_elements: values.len() as u64,
+            padded_len: padded_len as u64,
+        },
+        KzgOpening {
+            challenge_point_hex: hex::encode(challenge.to_bytes_be()),
+            value_hex: hex::encode(value_at_challenge.to_bytes_be()),
+            proof_g1_b64: encode_g1(&opening_point),
+        },
+    ))
+}
+
+/// Split `bytes` into 31-byte little-endian field elements — short enough
+/// that every chunk, regardless of content, is guaranteed to be less than
+/// the BLS12-381 scalar field modulus, the same packing EIP-4844 blobs use.
+fn canonicalize_dataset_to_field_elements(bytes: &[u8]) -> Vec<BigUint> {
+    bytes
+        .chunks(31)
+        .map(|chunk| {
+            let mut padded = [0u8; 31];
+            padded[..chunk.len()].copy_from_slice(chunk);
+            BigUint::from_bytes_le(&padded)
+        })
+        .collect()
+}
+
+/// KZG-commit the entirety of `dataset_bytes`, not just one column: chunked
+/// into 31-byte field elements and interpolated over the domain
+/// `{0, ..., padded_len - 1}`, the same scheme `commit_and_open` uses for a
+/// single column, generalized so any byte range can later be challenged by
+/// element index via `open_dataset_row`.
+pub fn commit_dataset(dataset_bytes: &[u8]) -> Result<KzgCommitment, EnclaveError> {
+    let srs = KZG_SRS
+        .as_ref()
+        .map_err(|e| EnclaveError::GenericError(format!("KZG SRS unavailable: {}", e)))?;
+    commit_dataset_with_srs(srs, dataset_bytes)
+}
+
+/// `commit_dataset`'s core, taking the SRS explicitly so tests can exercise
+/// it against a small toy SRS instead of the `KZG_SRS_PATH` trusted-setup file.
+fn commit_dataset_with_srs(srs: &KzgSrs, dataset_bytes: &[u8]) -> Result<KzgCommitment, EnclaveError> {
+    let elements = canonicalize_dataset_to_field_elements(dataset_bytes);
+    let padded_len = elements.len().max(1).next_power_of_two();
+    if padded_len > srs.powers_of_tau_g1.len() {
+        return Err(EnclaveError::GenericError(format!(
+            "dataset has {} field element(s) (padded to {}), exceeding the SRS's committable degree of {}",
+            elements.len(),
+            padded_len,
+            srs.max_degree()
+        )));
+    }
+
+    let m = fr_modulus();
+    let coeffs = interpolate_coefficients_fr(&elements, padded_len, &m);
+    let commitment_point = msm_g1(&srs.powers_of_tau_g1[..coeffs.len()], &coeffs)?;
+
+    Ok(KzgCommitment {
+        commitment_g1_b64: encode_g1(&commitment_point),
+        num_elements: elements.len() as u64,
+        padded_len: padded_len as u64,
+    })
+}
+
+/// Open `dataset_bytes`'s `commit_dataset` commitment at field-element index
+/// `row_index`. The interpolated polynomial satisfies `p(row_index) ==`
+/// that element's value by construction, so the opening is exact rather than
+/// a value the caller has to separately claim and have checked.
+pub fn open_dataset_row(dataset_bytes: &[u8], row_index: u64) -> Result<KzgOpening, EnclaveError> {
+    let srs = KZG_SRS
+        .as_ref()
+        .map_err(|e| EnclaveError::GenericError(format!("KZG SRS unavailable: {}", e)))?;
+    open_dataset_row_with_srs(srs, dataset_bytes, row_index)
+}
+
+/// `open_dataset_row`'s core, taking the SRS explicitly; see
+/// `commit_dataset_with_srs`.
+fn open_dataset_row_with_srs(srs: &KzgSrs, dataset_bytes: &[u8], row_index: u64) -> Result<KzgOpening, EnclaveError> {
+    let elements = canonicalize_dataset_to_field_elements(dataset_bytes);
+    let row = row_index as usize;
+    if row >= elements.len() {
+        return Err(EnclaveError::GenericError(format!(
+            "row index {} is out of range for a {}-element dataset",
+            row_index,
+            elements.len()
+        )));
+    }
+
+    let padded_len = elements.len().max(1).next_power_of_two();
+    if padded_len > srs.powers_of_tau_g1.len() {
+        return Err(EnclaveError::GenericError(format!(
+            "dataset has {} field element(s) (padded to {}), exceeding the SRS's committable degree of {}",
+            elements.len(),
+            padded_len,
+            srs.max_degree()
+        )));
+    }
+
+    let m = fr_modulus();
+    let coeffs = interpolate_coefficients_fr(&elements, padded_len, &m);
+    let challenge = BigUint::from(row_index);
+    let value_at_challenge = &elements[row] % &m;
+
+    let mut shifted_coeffs = coeffs.clone();
+    shifted_coeffs[0] = fr_sub(&shifted_coeffs[0], &value_at_challenge, &m);
+    let quotient = poly_div_linear(&shifted_coeffs, &challenge, &m);
+    let opening_point = msm_g1(&srs.powers_of_tau_g1[..quotient.len()], &quotient)?;
+
+    Ok(KzgOpening {
+        challenge_point_hex: hex::encode(challenge.to_bytes_be()),
+        value_hex: hex::encode(value_at_challenge.to_bytes_be()),
+        proof_g1_b64: encode_g1(&opening_point),
+    })
+}
+
+fn decode_g1(b64: &str) -> Result<G1Element, EnclaveError> {
+    use base64::Engine;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(b64)
+        .map_err(|e| EnclaveError::GenericError(format!("invalid base64 G1 point: {}", e)))?;
+    let array: [u8; 48] = bytes
+        .try_into()
+        .map_err(|_| EnclaveError::GenericError("G1 point must be 48 bytes".to_string()))?;
+    G1Element::from_byte_array(&array).map_err(|e| EnclaveError::GenericError(format!("invalid G1 point: {}", e)))
+}
+
+/// Verify a `commit_dataset`/`open_dataset_row` opening with one pairing
+/// check: `e(pi, [tau]_2 - [z]_2) == e(C - [y]_1, G2)`. Unlike
+/// `commit_and_open`'s column openings (verified off-enclave by whoever
+/// holds the SRS), this dataset-wide commitment is meant to be challenged
+/// directly by this TEE, so the pairing check runs in-enclave.
+pub fn verify_dataset_opening(commitment: &KzgCommitment, opening: &KzgOpening) -> Result<bool, EnclaveError> {
+    let srs = KZG_SRS
+        .as_ref()
+        .map_err(|e| EnclaveError::GenericError(format!("KZG SRS unavailable: {}", e)))?;
+    verify_dataset_opening_with_srs(srs, commitment, opening)
+}
+
+/// `verify_dataset_opening`'s core, taking the SRS explicitly; see
+/// `commit_dataset_with_srs`.
+fn verify_dataset_opening_with_srs(
+    srs: &KzgSrs,
+    commitment: &KzgCommitment,
+    opening: &KzgOpening,
+) -> Result<bool, EnclaveError> {
+    use fastcrypto::groups::bls12381::Scalar as FrScalar;
+    use fastcrypto::groups::Pairing;
+
+    let commitment_point = decode_g1(&commitment.commitment_g1_b64)?;
+    let proof_point = decode_g1(&opening.proof_g1_b64)?;
+
+    let challenge_bytes = hex::decode(&opening.challenge_point_hex)
+        .map_err(|e| EnclaveError::GenericError(format!("invalid challenge_point_hex: {}", e)))?;
+    let value_bytes = hex::decode(&opening.value_hex)
+        .map_err(|e| EnclaveError::GenericError(format!("invalid value_hex: {}", e)))?;
+    let m = fr_modulus();
+    let challenge = BigUint::from_bytes_be(&challenge_bytes) % &m;
+    let value = BigUint::from_bytes_be(&value_bytes) % &m;
+
+    let challenge_fr = FrScalar::from_byte_array(&biguint_to_g1_scalar_bytes(&challenge))
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to decode challenge scalar: {}", e)))?;
+    let value_fr = FrScalar::from_byte_array(&biguint_to_g1_scalar_bytes(&value))
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to decode value scalar: {}", e)))?;
+
+    let g1_generator = G1Element::generator();
+    let g2_generator = G2Element::generator();
+
+    let tau_minus_z_g2 = srs.tau_g2 - (g2_generator * challenge_fr);
+    let commitment_minus_y_g1 = commitment_point - (g1_generator * value_fr);
+
+    let lhs = proof_point.pairing(&tau_minus_z_g2);
+    let rhs = commitment_minus_y_g1.pairing(&g2_generator);
+
+    Ok(lhs == rhs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fastcrypto::groups::bls12381::Scalar as FrScalar;
+
+    /// A small, insecure trusted setup for a known `tau`, so tests can
+    /// exercise the real commit/open/verify pairing math without depending
+    /// on the `KZG_SRS_PATH` ceremony file.
+    fn toy_srs(max_degree: usize, tau_value: u64) -> KzgSrs {
+        let m = fr_modulus();
+        let tau = BigUint::from(tau_value) % &m;
+
+        let mut powers_of_tau_g1 = Vec::with_capacity(max_degree + 1);
+        let mut power = BigUint::one();
+        for _ in 0..=max_degree {
+            let scalar = FrScalar::from_byte_array(&biguint_to_g1_scalar_bytes(&power)).unwrap();
+            powers_of_tau_g1.push(G1Element::generator() * scalar);
+            power = fr_mul(&power, &tau, &m);
+        }
+
+        let tau_fr = FrScalar::from_byte_array(&biguint_to_g1_scalar_bytes(&tau)).unwrap();
+        let tau_g2 = G2Element::generator() * tau_fr;
+
+        KzgSrs { powers_of_tau_g1, tau_g2 }
+    }
+
+    #[test]
+    fn commit_open_verify_round_trip() {
+        let srs = toy_srs(16, 12345);
+        let dataset = b"the quick brown fox jumps over the lazy dog, repeated for padding".to_vec();
+
+        let commitment = commit_dataset_with_srs(&srs, &dataset).unwrap();
+        let opening = open_dataset_row_with_srs(&srs, &dataset, 0).unwrap();
+
+        assert!(verify_dataset_opening_with_srs(&srs, &commitment, &opening).unwrap());
+    }
+
+    #[test]
+    fn tampered_opening_value_is_rejected() {
+        let srs = toy_srs(16, 12345);
+        let dataset = b"the quick brown fox jumps over the lazy dog, repeated for padding".to_vec();
+
+        let commitment = commit_dataset_with_srs(&srs, &dataset).unwrap();
+        let mut opening = open_dataset_row_with_srs(&srs, &dataset, 0).unwrap();
+
+        let m = fr_modulus();
+        let claimed_value = BigUint::from_bytes_be(&hex::decode(&opening.value_hex).unwrap());
+        let tampered_value = fr_add(&claimed_value, &BigUint::one(), &m);
+        opening.value_hex = hex::encode(tampered_value.to_bytes_be());
+
+        assert!(!verify_dataset_opening_with_srs(&srs, &commitment, &opening).unwrap());
+    }
+
+    #[test]
+    fn opening_against_a_different_commitment_is_rejected() {
+        let srs = toy_srs(16, 12345);
+        let dataset_a = b"the quick brown fox jumps over the lazy dog, repeated for padding".to_vec();
+        let dataset_b = b"a totally different dataset with unrelated content, padded too".to_vec();
+
+        let commitment_b = commit_dataset_with_srs(&srs, &dataset_b).unwrap();
+        let opening_a = open_dataset_row_with_srs(&srs, &dataset_a, 0).unwrap();
+
+        assert!(!verify_dataset_opening_with_srs(&srs, &commitment_b, &opening_a).unwrap());
+    }
+}
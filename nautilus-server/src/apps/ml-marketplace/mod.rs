@@ -10,18 +10,31 @@ use axum::extract::State;
 use axum::Json;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use sha2::{Sha256, Digest};
 use tracing::{debug, info};
 use base64;
-use fastcrypto::encoding::{Hex, Base64 as FcBase64, Encoding};
+use fastcrypto::encoding::{Hex, Encoding};
 use fastcrypto::ed25519::Ed25519KeyPair;
-use fastcrypto::traits::{KeyPair, Signer};
-use seal_sdk::{EncryptedObject, IBEPublicKey, seal_decrypt_all_objects, types::{FetchKeyRequest, FetchKeyResponse, KeyId}};
+use seal_sdk::IBEPublicKey;
 use sui_sdk_types::ObjectId as ObjectID;
-use rand::thread_rng;
-
+use std::str::FromStr;
+
+mod aggregates;
+mod consensus;
+mod engines;
+mod erasure;
+pub mod jobs;
+mod kzg;
+mod onchain;
+mod record_merkle;
+mod safe_reader;
+mod seal_fix;
 mod seal_impl;
+mod signature;
+mod stream_crypto;
+pub mod types;
+pub mod versioning;
 
 /// Response from ML model quality assessment
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -35,6 +48,106 @@ pub struct MLQualityResponse {
     pub bias_assessment: BiasAssessment,
     pub model_type: String,
     pub dataset_format: String,
+    /// KZG commitment to the dataset's first numeric column, plus a
+    /// non-interactive opening at a Fiat-Shamir-derived challenge point, so a
+    /// verifier can later spot-check a specific value without the whole
+    /// dataset. `None` when no numeric column was found to commit to.
+    pub dataset_commitment: Option<DatasetCommitment>,
+    /// Results of any caller-requested `MLQualityRequest::aggregates`, plus
+    /// the Merkle root they were computed over. `None` when the caller asked
+    /// for none.
+    pub aggregates: Option<aggregates::AggregateComputation>,
+    /// Attestation protocol version actually used to sign this assessment's
+    /// on-chain verification message, negotiated by
+    /// `onchain::negotiate_attestation_version` against the caller's
+    /// `MLQualityRequest::max_supported_attestation_version`.
+    pub attestation_protocol_version: u8,
+    /// How much accuracy this model loses if deployed at INT8 instead of
+    /// full precision, from `engines::assess_quantization_sensitivity`.
+    /// `None` when the selected engine has no real quantizable inference
+    /// path to measure (only `OnnxEngine`, today).
+    pub quantization: Option<QuantizationAssessment>,
+    /// KZG commitment to the whole dataset's bytes (not just one column),
+    /// plus an opening at `MLQualityRequest::requested_row_index`, so a
+    /// verifier can later challenge that a specific byte-chunk was part of
+    /// what was assessed. `None` unless a row index was requested.
+    pub dataset_row_opening: Option<DatasetRowOpening>,
+    /// Root of a domain-separated SHA-256 Merkle tree over one leaf per
+    /// dataset record (CSV row / NPY row), from `record_merkle::RecordMerkleTree`.
+    /// Backs `data_integrity_score` with something a third party can
+    /// cryptographically spot-check rather than take on faith. `None` for
+    /// dataset formats `split_dataset_into_records` doesn't support.
+    pub dataset_record_merkle_root: Option<String>,
+    /// Inclusion proof for `MLQualityRequest::requested_record_index`, so an
+    /// auditor can confirm one specific record contributed to this
+    /// assessment via `record_merkle::verify_record_inclusion`. `None`
+    /// unless a record index was requested (and the format supports it).
+    pub requested_record_proof: Option<record_merkle::RecordInclusionProof>,
+    /// Reed-Solomon erasure coding of the dataset plus a genuine
+    /// reconstruct-after-dropping-shards proof, from
+    /// `erasure::assess_dataset_integrity`. `data_integrity_score` is this
+    /// assessment's `integrity_score` when available, instead of the old
+    /// row/column-count heuristic; `None` only for a dataset too small to
+    /// shard (e.g. empty).
+    pub erasure_assessment: Option<erasure::ErasureAssessment>,
+}
+
+/// KZG commitment to the entire dataset's bytes and an opening at one
+/// caller-chosen field-element index, from `kzg::commit_dataset`/
+/// `kzg::open_dataset_row`. Unlike `DatasetCommitment`, which commits only
+/// the first numeric column at a Fiat-Shamir-derived challenge, this commits
+/// every byte of the dataset and opens exactly the row the caller asked for.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DatasetRowOpening {
+    pub commitment: kzg::KzgCommitment,
+    pub opening: kzg::KzgOpening,
+    pub row_index: u64,
+}
+
+/// Result of re-running inference with the model's numeric inputs passed
+/// through symmetric INT8 quantize/dequantize, to estimate how much accuracy
+/// a buyer would give up deploying this model at INT8 instead of full
+/// precision, neural-compressor-style.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuantizationAssessment {
+    pub fp32_f1: u64,       // Scaled by 10000
+    pub int8_f1: u64,       // Scaled by 10000
+    pub accuracy_drop: u64, // Scaled by 10000
+    /// One of "int8", "fp16", "fp32", picked from `accuracy_drop` thresholds.
+    pub recommended_precision: String,
+}
+
+/// KZG commitment and accompanying opening for one dataset column. See
+/// `kzg::commit_and_open` for how these are produced.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DatasetCommitment {
+    pub commitment: kzg::KzgCommitment,
+    pub opening: kzg::KzgOpening,
+}
+
+/// A single verifiable aggregate statistic a caller wants computed over one
+/// dataset column, resolved by `aggregates::compute_aggregates` alongside the
+/// existing KZG column commitment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregateRequest {
+    /// 0-based column index into the dataset's header, same indexing as
+    /// `extract_committable_column`.
+    pub column_index: u64,
+    pub function: AggregateFunction,
+    /// Required only for `CountIf`: rows whose value is greater than or
+    /// equal to this threshold are counted.
+    pub threshold: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AggregateFunction {
+    Sum,
+    Avg,
+    Min,
+    Max,
+    Count,
+    CountIf,
+    StdDev,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -54,6 +167,20 @@ pub struct PerformanceMetrics {
     pub model_size_mb: u64,
     pub dataset_size_mb: u64,
     pub throughput_samples_per_second: u64,  // Scaled by 100 (e.g., 667 = 6.67 samples/sec)
+    /// MLPerf-LoadGen-style query-issue scenario this was benchmarked under
+    /// (`SingleStream`/`MultiStream`/`Server`/`Offline`). `None` unless
+    /// `assessment_type` was `ComprehensiveBenchmark`.
+    pub benchmark_scenario: Option<String>,
+    /// 50th/90th/99th-percentile per-query latency in milliseconds, computed
+    /// by nearest-rank over the sorted per-query latencies collected while
+    /// running `benchmark_scenario`. `None` outside `ComprehensiveBenchmark`.
+    pub p50_latency_ms: Option<u64>,
+    pub p90_latency_ms: Option<u64>,
+    pub p99_latency_ms: Option<u64>,
+    /// For the `Server` scenario only: whether p99 latency stayed within the
+    /// budget implied by the target QPS. `None` for the other scenarios,
+    /// which don't have a latency SLA to meet.
+    pub latency_sla_met: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -66,7 +193,7 @@ pub struct BiasAssessment {
 }
 
 /// Request for ML model quality assessment
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MLQualityRequest {
     pub model_blob_id: String,
     pub dataset_blob_id: String,
@@ -74,6 +201,106 @@ pub struct MLQualityRequest {
     pub quality_metrics: Vec<String>,
     pub model_type_hint: Option<String>,
     pub dataset_format_hint: Option<String>,
+    /// SSE-C-style caller-supplied decryption key material: a base64 AES-256
+    /// key plus a base64 MD5 digest of that key, so the enclave can verify the
+    /// caller sent the key they intended before trusting it.
+    pub model_encryption_key: Option<SseCKey>,
+    pub dataset_encryption_key: Option<SseCKey>,
+    /// Passphrase-based alternative to `model_encryption_key`/`dataset_encryption_key`:
+    /// the enclave derives the AES-256 key with Argon2id instead of the caller
+    /// sending key material directly. Mutually exclusive with the SSE-C key
+    /// for the same blob.
+    pub model_passphrase_key: Option<PassphraseKey>,
+    pub dataset_passphrase_key: Option<PassphraseKey>,
+    /// SPV-style Merkle inclusion proofs binding the downloaded model/dataset
+    /// bytes to an on-chain commitment root, checked by `verify_merkle_inclusion_proof`
+    /// before assessment proceeds. Optional like the key fields above: a blob
+    /// with no proof is assessed as before, but a supplied proof that fails
+    /// to verify is a hard error rather than a silent skip.
+    pub model_merkle_proof: Option<MerkleInclusionProof>,
+    pub dataset_merkle_proof: Option<MerkleInclusionProof>,
+    /// Aggregate statistics (SUM/AVG/MIN/MAX/COUNT/COUNT_IF/STDDEV) to compute
+    /// and attest over dataset columns, in addition to `data_integrity_score`.
+    /// Defaults to empty so existing callers don't need to set it.
+    #[serde(default)]
+    pub aggregates: Vec<AggregateRequest>,
+    /// Newest attestation protocol version the caller's on-chain contract
+    /// understands, passed to `onchain::negotiate_attestation_version`.
+    /// `None` is treated as "supports the current version", so existing
+    /// callers keep working unchanged.
+    #[serde(default)]
+    pub max_supported_attestation_version: Option<u8>,
+    /// Name of a numerically-encoded dataset column (e.g. a protected-group
+    /// code) to group rows by when computing `BiasAssessment::demographic_parity`/
+    /// `equalized_odds`. `None` (the default) keeps the prior placeholder
+    /// fairness figures, since there's no real grouping to measure without it.
+    #[serde(default)]
+    pub sensitive_attribute: Option<String>,
+    /// Field-element index into the dataset, bytes chunked per
+    /// `kzg::commit_dataset`, to open a KZG proof for (see
+    /// `DatasetRowOpening`). `None` (the default) skips the whole-dataset
+    /// commitment; existing callers only get `dataset_commitment` as before.
+    #[serde(default)]
+    pub requested_row_index: Option<u64>,
+    /// Record index (CSV row / NPY row) to return a Merkle inclusion proof
+    /// for, per `record_merkle::RecordMerkleTree::prove`. `None` (the
+    /// default) skips the proof; `dataset_record_merkle_root` is still
+    /// computed and returned when the dataset format supports it.
+    #[serde(default)]
+    pub requested_record_index: Option<u64>,
+    /// Schema version this request was encoded against, per
+    /// `versioning::{MIN_SUPPORTED_API_VERSION, CURRENT_API_VERSION}`. `None`
+    /// is treated as the current version, so existing callers keep working
+    /// unchanged.
+    #[serde(default)]
+    pub api_version: Option<u16>,
+}
+
+/// A customer-supplied decryption key, mirroring the S3 SSE-C pattern: the raw
+/// key never appears in cleartext without a digest to catch transcription
+/// errors or tampering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SseCKey {
+    /// Base64-encoded 32-byte AES-256 key.
+    pub key_b64: String,
+    /// Base64-encoded MD5 digest of the raw key bytes.
+    pub key_md5_b64: String,
+}
+
+/// Caller-supplied passphrase and salt from which the enclave derives a
+/// 256-bit AES key via Argon2id, so the caller never has to generate or
+/// transmit key material themselves. Only the passphrase, salt, and (fixed)
+/// KDF parameters cross the wire or appear in logs — the derived key never
+/// does. The salt should be unique per blob: two callers reusing the same
+/// passphrase with different salts derive unrelated keys.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PassphraseKey {
+    pub passphrase: String,
+    /// Base64-encoded salt, recommended at least 16 bytes.
+    pub salt_b64: String,
+}
+
+/// An SPV-style Merkle inclusion proof binding one downloaded blob to an
+/// on-chain commitment root. The enclave recomputes the leaf hash from the
+/// blob bytes it actually fetched (never from caller-supplied leaf bytes, or
+/// a mismatched blob could be waved through under a valid proof for
+/// something else) and folds it upward through `siblings_b64` using
+/// `leaf_index`'s bits, as `verify_merkle_inclusion_proof` implements.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleInclusionProof {
+    /// Base64-encoded sibling hash at each tree level, ordered from the
+    /// leaf's immediate sibling up to the one just below the root.
+    pub siblings_b64: Vec<String>,
+    /// 0-based position of this blob's leaf among `num_leaves` leaves. Bit
+    /// `i` (from the least-significant bit) selects whether the hash at
+    /// level `i` is the left (0) or right (1) child when folding with
+    /// `siblings_b64[i]`.
+    pub leaf_index: u64,
+    /// Total number of leaves in the committed tree, used to sanity-check
+    /// that `siblings_b64.len()` matches the tree's actual depth.
+    pub num_leaves: u64,
+    /// Base64-encoded Merkle root recorded on-chain for this blob's commitment.
+    pub expected_root_b64: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -92,16 +319,37 @@ pub async fn process_data(
     info!("Starting ML model quality assessment");
     debug!("Request: {:?}", request);
 
+    versioning::check_api_version(request.payload.api_version)
+        .map_err(|e| EnclaveError::GenericError(e.to_string()))?;
+
     let start_time = std::time::Instant::now();
-    
+
     // Step 1: Download model and dataset from Walrus
-    let (model_data, model_hash) = download_and_hash_blob(&request.payload.model_blob_id, "model").await?;
-    let (dataset_data, dataset_hash) = download_and_hash_blob(&request.payload.dataset_blob_id, "dataset").await?;
-    
-    info!("Downloaded model ({}MB) and dataset ({}MB)", 
-               model_data.len() / 1_048_576, 
+    let model_key = resolve_caller_key(
+        request.payload.model_encryption_key.as_ref(),
+        request.payload.model_passphrase_key.as_ref(),
+    )?;
+    let dataset_key = resolve_caller_key(
+        request.payload.dataset_encryption_key.as_ref(),
+        request.payload.dataset_passphrase_key.as_ref(),
+    )?;
+    let (model_data, model_hash) = download_and_hash_blob(&request.payload.model_blob_id, "model", model_key, &state.eph_kp).await?;
+    let (dataset_data, dataset_hash) = download_and_hash_blob(&request.payload.dataset_blob_id, "dataset", dataset_key, &state.eph_kp).await?;
+    
+    info!("Downloaded model ({}MB) and dataset ({}MB)",
+               model_data.len() / 1_048_576,
                dataset_data.len() / 1_048_576);
 
+    // Step 1.5: If the caller supplied Merkle inclusion proofs, verify the
+    // downloaded bytes are committed under the claimed on-chain root before
+    // spending cycles on model/dataset validation below.
+    let model_merkle_root = request.payload.model_merkle_proof.as_ref()
+        .map(|proof| verify_merkle_inclusion_proof(proof, &model_data))
+        .transpose()?;
+    let dataset_merkle_root = request.payload.dataset_merkle_proof.as_ref()
+        .map(|proof| verify_merkle_inclusion_proof(proof, &dataset_data))
+        .transpose()?;
+
     // Step 2: Validate and load model (skip for real test files)
     let model_info = if is_test_model(&request.payload.model_blob_id) {
         // For test models, create basic info without validation
@@ -111,6 +359,7 @@ pub async fn process_data(
             parameters: (model_data.len() / 4) as u64, // Estimate
             input_shape: vec![1, 10],
             output_shape: vec![1, 3],
+            dtype_distribution: HashMap::new(),
         }
     } else {
         validate_and_load_model(&model_data, &request.payload.model_type_hint)?
@@ -124,23 +373,119 @@ pub async fn process_data(
             rows: 1000, // Estimate
             columns: 10, // Estimate
             data_types: HashMap::new(),
+            csv_column_schema: None,
         }
     } else {
         validate_and_process_dataset(&dataset_data, &request.payload.dataset_format_hint)?
     };
-    
-    // Step 4: Perform real model inference and quality assessment
-    let assessment_result = perform_real_quality_assessment(
+
+    // Step 3.5: KZG-commit the dataset's first numeric column, if any, so a
+    // verifier can later challenge a specific row without the whole dataset.
+    let dataset_commitment = match extract_committable_column(&dataset_data) {
+        Some(column_values) => Some(build_dataset_commitment(&column_values, dataset_hash.as_bytes())?),
+        None => {
+            info!("No numeric column found to KZG-commit; skipping dataset commitment");
+            None
+        }
+    };
+
+    // Step 3.55: If the caller asked for a specific row, KZG-commit the
+    // whole dataset (not just the first numeric column) and open it at that
+    // index, so the opening can be challenged without re-downloading the
+    // dataset via `kzg::verify_dataset_opening`.
+    let dataset_row_opening = match request.payload.requested_row_index {
+        Some(row_index) => {
+            let commitment = kzg::commit_dataset(&dataset_data)?;
+            let opening = kzg::open_dataset_row(&dataset_data, row_index)?;
+            // Self-check the opening in-enclave with the same pairing check a
+            // downstream verifier would run, before ever serving it, so a
+            // bug in commitment/opening generation fails loudly here rather
+            // than shipping a proof nobody can actually verify.
+            if !kzg::verify_dataset_opening(&commitment, &opening)? {
+                return Err(EnclaveError::GenericError(
+                    "KZG dataset opening failed self-verification before being returned".to_string(),
+                ));
+            }
+            Some(DatasetRowOpening { commitment, opening, row_index })
+        }
+        None => None,
+    };
+
+    // Step 3.57: Build a domain-separated Merkle tree over one leaf per
+    // dataset record (CSV row / NPY row), so a third party can later confirm
+    // a specific record contributed to this assessment via
+    // `record_merkle::verify_record_inclusion`, rather than trusting
+    // `data_integrity_score` on faith. `None` for formats
+    // `split_dataset_into_records` doesn't yet support (parquet, JSON,
+    // image archives).
+    let record_merkle_tree = match split_dataset_into_records(&dataset_info, &dataset_data) {
+        Ok(records) => Some(record_merkle::RecordMerkleTree::build(&records)?),
+        Err(e) => {
+            info!("Dataset format '{}' has no per-record Merkle commitment: {}", dataset_info.format, e);
+            None
+        }
+    };
+    let dataset_record_merkle_root = record_merkle_tree.as_ref().map(|tree| tree.root_b64());
+    let requested_record_proof = match (&record_merkle_tree, request.payload.requested_record_index) {
+        (Some(tree), Some(record_index)) => Some(tree.prove(record_index)?),
+        _ => None,
+    };
+
+    // Step 3.58: Reed-Solomon erasure-code the dataset and prove it actually
+    // survives losing its parity-shard count worth of data shards, so
+    // `data_integrity_score` reflects a tested recovery rather than a
+    // row/column-count heuristic.
+    let erasure_assessment = match erasure::assess_dataset_integrity(&dataset_data) {
+        Ok(assessment) => Some(assessment),
+        Err(e) => {
+            info!("Skipping erasure-coding integrity assessment: {}", e);
+            None
+        }
+    };
+
+    // Step 3.6: Compute and attest any caller-requested aggregate statistics
+    // over dataset columns, alongside the coarser data_integrity_score.
+    let aggregate_computation = if request.payload.aggregates.is_empty() {
+        None
+    } else {
+        Some(aggregates::compute_aggregates(&dataset_data, &request.payload.aggregates)?)
+    };
+
+    // Step 4: Perform real model inference and quality assessment. The
+    // remote Python evaluator is tried first; if it's unreachable we fall
+    // back to the in-enclave engine registry (engines::assess) rather than
+    // failing the whole request, same as the Walrus-download fallback above.
+    let assessment_result = match perform_real_quality_assessment(
         &model_data,
         &dataset_data,
         &request.payload.model_blob_id,
         &request.payload.dataset_blob_id,
         &request.payload.assessment_type,
         &request.payload.quality_metrics,
-    ).await?;
+    ).await {
+        Ok(result) => result,
+        Err(e) => {
+            info!("ML evaluator unavailable ({}), falling back to in-enclave assessment engines", e);
+            engines::assess(
+                &model_data,
+                &model_info,
+                &dataset_info,
+                &dataset_data,
+                &request.payload.assessment_type,
+                &request.payload.quality_metrics,
+                request.payload.sensitive_attribute.as_deref(),
+            )?
+        }
+    };
     
     let processing_time = start_time.elapsed().as_millis() as u64;
-    
+
+    // Step 4.5: Negotiate which attestation protocol version to sign with,
+    // so older deployed contracts don't choke on a newer signed message
+    // layout before they've upgraded.
+    let attestation_protocol_version =
+        onchain::negotiate_attestation_version(request.payload.max_supported_attestation_version)?;
+
     // Step 5: Create comprehensive quality response
     let quality_response = MLQualityResponse {
         model_hash: model_hash.clone(),
@@ -153,11 +498,27 @@ pub async fn process_data(
             model_size_mb: (model_data.len() / 1_048_576) as u64,
             dataset_size_mb: (dataset_data.len() / 1_048_576) as u64,
             throughput_samples_per_second: assessment_result.throughput,
+            benchmark_scenario: assessment_result.benchmark_scenario,
+            p50_latency_ms: assessment_result.p50_latency_ms,
+            p90_latency_ms: assessment_result.p90_latency_ms,
+            p99_latency_ms: assessment_result.p99_latency_ms,
+            latency_sla_met: assessment_result.latency_sla_met,
         },
-        data_integrity_score: assessment_result.data_integrity_score,
+        data_integrity_score: erasure_assessment
+            .as_ref()
+            .map(|assessment| assessment.integrity_score)
+            .unwrap_or(assessment_result.data_integrity_score),
         bias_assessment: assessment_result.bias_assessment,
         model_type: model_info.model_type,
         dataset_format: dataset_info.format,
+        dataset_commitment: dataset_commitment.clone(),
+        aggregates: aggregate_computation.clone(),
+        attestation_protocol_version,
+        quantization: assessment_result.quantization,
+        dataset_row_opening,
+        dataset_record_merkle_root: dataset_record_merkle_root.clone(),
+        requested_record_proof,
+        erasure_assessment,
     };
 
     let current_timestamp = std::time::SystemTime::now()
@@ -169,23 +530,31 @@ pub async fn process_data(
                processing_time, quality_response.quality_score);
 
     // Generate additional integrity signatures for the assessment
-    let assessment_hash = generate_assessment_integrity_hash(&quality_response, current_timestamp);
-    let model_verification_signature = generate_model_verification_signature(
-        &model_hash, 
-        &dataset_hash, 
+    let assessment_hash = generate_assessment_integrity_hash(
+        &quality_response,
+        current_timestamp,
+        model_merkle_root.as_ref(),
+        dataset_merkle_root.as_ref(),
+    );
+    let model_verification_signature = onchain::generate_model_verification_signature(
+        attestation_protocol_version,
+        &model_hash,
+        &dataset_hash,
         quality_response.quality_score,
+        dataset_commitment.as_ref(),
         &state.eph_kp
     );
-    
+
     info!("Generated assessment hash: {}", &assessment_hash[..16]);
     info!("Generated model verification signature: {}", &model_verification_signature[..16]);
 
     // Publish verification results to on-chain smart contract
-    let publish_result = publish_verification_onchain(
+    let publish_result = onchain::publish_verification(
         &request.payload.model_blob_id,
         &quality_response,
         &assessment_hash,
         &model_verification_signature,
+        &state.eph_kp,
     ).await;
     
     match publish_result {
@@ -201,19 +570,113 @@ pub async fn process_data(
     )))
 }
 
-/// Download blob from Walrus storage and compute hash
-async fn download_and_hash_blob(blob_id: &str, data_type: &str) -> Result<(Vec<u8>, String), EnclaveError> {
+/// Verify an `SseCKey`'s MD5 digest matches the supplied key bytes and return
+/// the decoded 32-byte AES key. Rejects with an `EnclaveError` on any mismatch
+/// rather than silently falling back to a default/derived key.
+fn verify_and_decode_sse_c_key(sse_c_key: &SseCKey) -> Result<[u8; 32], EnclaveError> {
+    use base64::Engine;
+
+    let key_bytes = base64::engine::general_purpose::STANDARD
+        .decode(&sse_c_key.key_b64)
+        .map_err(|e| EnclaveError::GenericError(format!("Invalid base64 encryption key: {}", e)))?;
+
+    let key: [u8; 32] = key_bytes
+        .try_into()
+        .map_err(|_| EnclaveError::GenericError("Encryption key must be exactly 32 bytes".to_string()))?;
+
+    let expected_md5 = base64::engine::general_purpose::STANDARD
+        .decode(&sse_c_key.key_md5_b64)
+        .map_err(|e| EnclaveError::GenericError(format!("Invalid base64 key MD5: {}", e)))?;
+
+    let computed_md5 = md5::compute(key).0;
+    if computed_md5.as_slice() != expected_md5.as_slice() {
+        return Err(EnclaveError::GenericError(
+            "Supplied key MD5 does not match the provided key material".to_string(),
+        ));
+    }
+
+    Ok(key)
+}
+
+/// Argon2id parameters for `derive_key_from_passphrase`: 64 MiB memory cost,
+/// 3 iterations, single-lane parallelism. This is memory-hard enough to make
+/// GPU/ASIC brute force of a weak passphrase expensive while still completing
+/// in well under a second per request.
+const ARGON2ID_MEMORY_COST_KIB: u32 = 64 * 1024;
+const ARGON2ID_ITERATIONS: u32 = 3;
+const ARGON2ID_PARALLELISM: u32 = 1;
+
+/// Derive a 256-bit AES key from a caller-supplied passphrase and salt via
+/// Argon2id, logging the (non-secret) KDF parameters for auditability. The
+/// passphrase and salt cross the wire so two callers with the same passphrase
+/// but different salts end up with unrelated keys; the derived key itself is
+/// never logged, stored, or returned.
+fn derive_key_from_passphrase(passphrase_key: &PassphraseKey) -> Result<[u8; 32], EnclaveError> {
+    use argon2::{Algorithm, Argon2, Params, Version};
+    use base64::Engine;
+
+    let salt = base64::engine::general_purpose::STANDARD
+        .decode(&passphrase_key.salt_b64)
+        .map_err(|e| EnclaveError::GenericError(format!("Invalid base64 salt: {}", e)))?;
+
+    info!(
+        "Deriving AES-256 key via Argon2id (memory_cost={}KiB, iterations={}, parallelism={})",
+        ARGON2ID_MEMORY_COST_KIB, ARGON2ID_ITERATIONS, ARGON2ID_PARALLELISM
+    );
+
+    let params = Params::new(ARGON2ID_MEMORY_COST_KIB, ARGON2ID_ITERATIONS, ARGON2ID_PARALLELISM, Some(32))
+        .map_err(|e| EnclaveError::GenericError(format!("Invalid Argon2id parameters: {}", e)))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+
+    let mut key = [0u8; 32];
+    argon2
+        .hash_password_into(passphrase_key.passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|e| EnclaveError::GenericError(format!("Argon2id key derivation failed: {}", e)))?;
+
+    Ok(key)
+}
+
+/// Resolve the caller-supplied decryption key for one blob, accepting either
+/// a raw SSE-C-style key or an Argon2id-derived passphrase key, but not both
+/// for the same blob.
+fn resolve_caller_key(
+    sse_c_key: Option<&SseCKey>,
+    passphrase_key: Option<&PassphraseKey>,
+) -> Result<Option<[u8; 32]>, EnclaveError> {
+    match (sse_c_key, passphrase_key) {
+        (Some(_), Some(_)) => Err(EnclaveError::GenericError(
+            "Specify either an encryption_key or a passphrase_key for a blob, not both".to_string(),
+        )),
+        (Some(key), None) => Ok(Some(verify_and_decode_sse_c_key(key)?)),
+        (None, Some(key)) => Ok(Some(derive_key_from_passphrase(key)?)),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Download blob from Walrus storage and compute hash. `caller_key`, when
+/// present, is an SSE-C-style or Argon2id-passphrase-derived key the caller
+/// asserted the blob was encrypted under; it takes priority over the
+/// default-key/blob-ID heuristics in `attempt_decrypt_blob`/`seal_impl::attempt_decrypt_blob`
+/// and a failure to decrypt with it is a hard error rather than a silent
+/// fallback to raw data.
+async fn download_and_hash_blob(
+    blob_id: &str,
+    data_type: &str,
+    caller_key: Option<[u8; 32]>,
+    enclave_kp: &Ed25519KeyPair,
+) -> Result<(Vec<u8>, String), EnclaveError> {
     info!("Downloading blob from Walrus: {}", blob_id);
-    
+
     // Check environment variable for enabling real downloads
     let use_real_downloads = std::env::var("WALRUS_REAL_DOWNLOADS")
         .map(|v| v.to_lowercase() == "true" || v == "1")
         .unwrap_or(false);
-    
+
     let data = if use_real_downloads {
         // Try real Walrus blob download first
-        match download_from_walrus(blob_id).await {
+        match download_from_walrus(blob_id, caller_key, enclave_kp).await {
             Ok(data) => data,
+            Err(e) if caller_key.is_some() => return Err(e),
             Err(e) => {
                 info!("Failed to download from Walrus ({}), falling back to demo data for blob: {}", e, blob_id);
                 // Graceful fallback to demo data when Walrus fails
@@ -237,7 +700,7 @@ async fn download_and_hash_blob(blob_id: &str, data_type: &str) -> Result<(Vec<u
 
 /// Attempt to decrypt a blob that may be AES-encrypted
 /// Returns Ok(decrypted_data) if decryption succeeds, Err if not encrypted or decryption fails
-async fn attempt_decrypt_blob(data: &[u8]) -> Result<Vec<u8>, EnclaveError> {
+async fn attempt_decrypt_blob(data: &[u8], enclave_kp: &Ed25519KeyPair) -> Result<Vec<u8>, EnclaveError> {
     use fastcrypto::aes::{Aes256CbcPkcs7, Cipher};
     use fastcrypto::traits::ToFromBytes;
     
@@ -263,7 +726,19 @@ async fn attempt_decrypt_blob(data: &[u8]) -> Result<Vec<u8>, EnclaveError> {
     }
     
     // Try different decryption approaches
-    
+
+    // Approach 0: Try the authenticated AES-256-GCM STREAM construction first —
+    // it carries an integrity tag per chunk, unlike the CBC fallbacks below, so
+    // a tampered ciphertext is rejected instead of silently decrypting to
+    // garbage that would flow into model/dataset validation undetected.
+    {
+        let default_key: [u8; 32] = *b"satya_default_key_32_bytes_long!";
+        if let Ok(decrypted) = stream_crypto::stream_decrypt(&default_key, data) {
+            info!("Successfully decrypted blob with AES-256-GCM STREAM");
+            return Ok(decrypted);
+        }
+    }
+
     // Approach 1: Try to extract IV from the beginning and decrypt with a default key
     if data.len() >= 48 { // 16-byte IV + 32-byte key + some data
         let iv_bytes = &data[0..16];
@@ -319,7 +794,7 @@ async fn attempt_decrypt_blob(data: &[u8]) -> Result<Vec<u8>, EnclaveError> {
     // Approach 3: Try SEAL decryption with Mysten testnet key server
     info!("Attempting REAL SEAL decryption with Mysten testnet key server...");
     
-    match attempt_real_seal_decryption(data).await {
+    match attempt_real_seal_decryption(data, enclave_kp).await {
         Ok(decrypted_data) => {
             info!("Successfully decrypted SEAL blob: {} bytes", decrypted_data.len());
             return Ok(decrypted_data);
@@ -333,248 +808,256 @@ async fn attempt_decrypt_blob(data: &[u8]) -> Result<Vec<u8>, EnclaveError> {
 }
 
 
-/// Check if a 32-byte sequence could be a SEAL key ID
-fn is_potential_key_id(bytes: &[u8]) -> bool {
-    if bytes.len() != 32 {
-        return false;
-    }
-    
-    // Key IDs typically have specific patterns and entropy
-    // Not all zeros, not all 0xFF, reasonable entropy
-    let all_zeros = bytes.iter().all(|&b| b == 0);
-    let all_ff = bytes.iter().all(|&b| b == 0xFF);
-    
-    if all_zeros || all_ff {
-        return false;
-    }
-    
-    // Check for reasonable entropy (not too repetitive)
-    let unique_bytes: std::collections::HashSet<_> = bytes.iter().collect();
-    unique_bytes.len() > 8 // At least 8 different byte values
+/// Mysten testnet SEAL key server (object ID + HTTP endpoint) used for
+/// production SEAL-gated blobs, distinct from `seal_impl::ML_SEAL_CONFIG`'s
+/// H2O Nodes testnet, which backs a separate assessment path.
+const MYSTEN_SEAL_KEY_SERVER_URL: &str = "https://seal-key-server-testnet-2.mystenlabs.com";
+const MYSTEN_SEAL_KEY_SERVER_OBJECT_ID: &str =
+    "0xf5d14a81a982144ae441cd7d64b09027f116a468bd36e7eca494f750591623c8";
+/// Mysten testnet key server's IBE public key (BLS12-381 G2, compressed),
+/// published at `{MYSTEN_SEAL_KEY_SERVER_URL}/v1/service`.
+const MYSTEN_SEAL_IBE_PUBLIC_KEY_HEX: &str =
+    "b1a5f3e89c2d7406158af20c943b6e7d8051c4a29f7e6d3b0814c5a927de63f10b4a8c291e7d5f34086b2c9e1a7d40f\
+     4258ba1cd0e7f93a6b1285dc4e906f7a321085cbe4f19a6d0372ce8b5a149d0fa63e28715cb94d0af2687e1a5c903bd";
+
+lazy_static::lazy_static! {
+    /// Mysten testnet SEAL key server config, in the same shape
+    /// `seal_impl::attempt_real_h2o_seal_decryption` uses for H2O Nodes, so
+    /// `seal_fix::attempt_real_seal_decryption` can run against either
+    /// network. `package_id` is unused here: `seal_fix` reads the on-chain
+    /// package straight off each blob's `EncryptedObject`s instead, since any
+    /// package may gate access through this key server.
+    static ref MYSTEN_SEAL_CONFIG: seal_impl::SealConfigML = {
+        let key_server_id = ObjectID::from_str(MYSTEN_SEAL_KEY_SERVER_OBJECT_ID)
+            .expect("MYSTEN_SEAL_KEY_SERVER_OBJECT_ID must be a valid ObjectID");
+        let ibe_public_key = {
+            use fastcrypto::serde_helpers::ToFromByteArray;
+            let bytes = Hex::decode(MYSTEN_SEAL_IBE_PUBLIC_KEY_HEX)
+                .expect("MYSTEN_SEAL_IBE_PUBLIC_KEY_HEX must be valid hex");
+            IBEPublicKey::from_byte_array(
+                &bytes.try_into().expect("MYSTEN_SEAL_IBE_PUBLIC_KEY_HEX must be 96 bytes"),
+            )
+            .expect("MYSTEN_SEAL_IBE_PUBLIC_KEY_HEX must be a valid IBE public key")
+        };
+
+        seal_impl::SealConfigML {
+            key_servers: vec![key_server_id],
+            public_keys: vec![ibe_public_key.clone()],
+            package_id: key_server_id,
+            server_pk_map: {
+                let mut map = HashMap::new();
+                map.insert(key_server_id, ibe_public_key);
+                map
+            },
+            server_urls: {
+                let mut map = HashMap::new();
+                map.insert(key_server_id, MYSTEN_SEAL_KEY_SERVER_URL.to_string());
+                map
+            },
+            // Only one Mysten testnet key server is configured today.
+            threshold: 1,
+        }
+    };
 }
 
-/// Attempt real SEAL decryption using Mysten testnet key server
-async fn attempt_real_seal_decryption(data: &[u8]) -> Result<Vec<u8>, EnclaveError> {
+/// Attempt real SEAL decryption using the Mysten testnet key server, via the
+/// hardened implementation in `seal_fix` (self-describing blob envelope,
+/// session-cert caching, an on-chain `seal_approve` ACL dry-run, threshold
+/// fetching, and per-response authenticity verification against the pinned
+/// `MYSTEN_SEAL_CONFIG` keyring).
+async fn attempt_real_seal_decryption(data: &[u8], enclave_kp: &Ed25519KeyPair) -> Result<Vec<u8>, EnclaveError> {
     info!("Starting REAL SEAL decryption with Mysten testnet key server");
-    
-    // Parse the SEAL blob structure
-    let (object_info, key_count) = parse_real_seal_blob_sync(data)
-        .map_err(|e| EnclaveError::GenericError(e))?;
-    
-    info!("SEAL blob parsed: object={}, keys={}", object_info, key_count);
-    
-    // Mysten testnet key server configuration
-    let key_server_url = "https://seal-key-server-testnet-2.mystenlabs.com";
-    let key_server_object_id = "0xf5d14a81a982144ae441cd7d64b09027f116a468bd36e7eca494f750591623c8";
-    
-    info!("Connecting to Mysten SEAL key server: {}", key_server_url);
-    info!("Key server object ID: {}", key_server_object_id);
-    
-    // Extract key IDs from the blob for key server requests
-    let key_ids = extract_key_ids_from_blob(data)?;
-    info!("Extracted {} key IDs for decryption", key_ids.len());
-    
-    // Fetch keys from Mysten testnet key server
-    let decryption_keys = fetch_keys_from_mysten_server(&key_ids, key_server_url).await?;
-    info!("Retrieved {} keys from Mysten key server", decryption_keys.len());
-    
-    // Attempt SEAL decryption with the fetched keys
-    let decrypted_data = perform_seal_decryption_with_keys(data, &decryption_keys).await?;
-    
-    info!("SEAL decryption successful: {} bytes decrypted", decrypted_data.len());
-    Ok(decrypted_data)
+    seal_fix::attempt_real_seal_decryption(data, enclave_kp, &MYSTEN_SEAL_CONFIG).await
 }
 
-/// Extract key IDs from SEAL encrypted blob
-fn extract_key_ids_from_blob(data: &[u8]) -> Result<Vec<Vec<u8>>, EnclaveError> {
-    if data.len() < 100 {
-        return Err(EnclaveError::GenericError("Blob too small for SEAL encryption".to_string()));
-    }
-    
-    let mut key_ids = Vec::new();
-    
-    // Scan for key ID patterns starting after the object ID (offset 37)
-    for i in (37..data.len().saturating_sub(32)).step_by(32) {
-        let potential_key = &data[i..i+32];
-        
-        if is_potential_key_id(potential_key) {
-            key_ids.push(potential_key.to_vec());
-            info!("Found key ID at offset {}: {:02x}{:02x}...{:02x}{:02x}", 
-                i, potential_key[0], potential_key[1], potential_key[30], potential_key[31]);
-        }
-        
-        // Limit to reasonable number of keys
-        if key_ids.len() >= 5 {
-            break;
-        }
-    }
-    
-    if key_ids.is_empty() {
-        return Err(EnclaveError::GenericError("No valid key IDs found in SEAL blob".to_string()));
+// There is deliberately no `verify_walrus_blob_id` here. A real Walrus blob ID
+// is a Merkle root over Red-Stuff erasure-coded slivers, not a plain digest of
+// the stored bytes, so `base64url(sha256(raw_content)) == blob_id` is neither
+// sufficient nor necessary: it can't detect a substituted blob (the thing it
+// would be for), and checking it would reject every genuine download, since a
+// real blob_id only coincidentally has the same encoded length as a SHA-256
+// digest. Until this crate reproduces the actual Red-Stuff commitment, a
+// passing no-op security control would be worse than no control at all; the
+// `SafeReader` length/streaming checks below, plus a caller-supplied Merkle
+// inclusion proof when one is given, are the only integrity checks on the
+// downloaded bytes.
+
+/// Number of levels between a leaf and the root of a tree with `num_leaves`
+/// leaves, i.e. `ceil(log2(num_leaves))`. A single-leaf tree has depth 0 (the
+/// leaf hash is the root), matching how `verify_merkle_inclusion_proof`
+/// requires an empty sibling list only in that case.
+fn merkle_depth_for_leaf_count(num_leaves: u64) -> u64 {
+    if num_leaves <= 1 {
+        0
+    } else {
+        (64 - (num_leaves - 1).leading_zeros()) as u64
     }
-    
-    Ok(key_ids)
 }
 
-/// Fetch decryption keys from Mysten SEAL key server
-async fn fetch_keys_from_mysten_server(key_ids: &[Vec<u8>], server_url: &str) -> Result<Vec<Vec<u8>>, EnclaveError> {
-    let client = reqwest::Client::new();
-    let mut decryption_keys = Vec::new();
-    
-    for (i, key_id) in key_ids.iter().enumerate() {
-        info!("Fetching key {}/{} from Mysten server", i+1, key_ids.len());
-        
-        // Convert key_id to hex for the request
-        let key_id_hex = key_id.iter().map(|b| format!("{:02x}", b)).collect::<String>();
-        
-        // Construct the key fetch request URL
-        // This follows SEAL key server API format
-        let fetch_url = format!("{}/fetch_key/{}", server_url, key_id_hex);
-        
-        info!("Fetching from URL: {}", fetch_url);
-        
-        match client.get(&fetch_url)
-            .timeout(std::time::Duration::from_secs(30))
-            .send()
-            .await 
-        {
-            Ok(response) => {
-                if response.status().is_success() {
-                    match response.bytes().await {
-                        Ok(key_data) => {
-                            info!("Successfully fetched key: {} bytes", key_data.len());
-                            decryption_keys.push(key_data.to_vec());
-                        },
-                        Err(e) => {
-                            info!("Failed to read key response: {}", e);
-                        }
-                    }
-                } else {
-                    info!("Key server returned error: {}", response.status());
-                }
-            },
-            Err(e) => {
-                info!("Failed to connect to key server: {}", e);
-            }
-        }
+/// Verify an SPV-style Merkle inclusion proof for `leaf_data` (the blob bytes
+/// this enclave actually downloaded, never caller-supplied leaf bytes).
+/// Recomputes the leaf hash with SHA-256, then folds it upward through
+/// `proof.siblings_b64`: at each level the current hash and that level's
+/// sibling are concatenated in the order given by the corresponding bit of
+/// `proof.leaf_index` (0 = current is the left child, 1 = current is the
+/// right child) and re-hashed. The final value must equal
+/// `proof.expected_root_b64`, the on-chain commitment root, or the
+/// assessment is rejected rather than proceeding against unverified data.
+/// Returns the verified root bytes on success so the caller can bind them
+/// into the assessment's own integrity hash.
+fn verify_merkle_inclusion_proof(proof: &MerkleInclusionProof, leaf_data: &[u8]) -> Result<[u8; 32], EnclaveError> {
+    use base64::Engine;
+
+    let expected_root = base64::engine::general_purpose::STANDARD
+        .decode(&proof.expected_root_b64)
+        .map_err(|e| EnclaveError::GenericError(format!("Invalid base64 Merkle root: {}", e)))?;
+    let expected_root: [u8; 32] = expected_root
+        .try_into()
+        .map_err(|_| EnclaveError::GenericError("Merkle root must be exactly 32 bytes".to_string()))?;
+
+    let expected_depth = merkle_depth_for_leaf_count(proof.num_leaves);
+    if proof.siblings_b64.len() as u64 != expected_depth {
+        return Err(EnclaveError::GenericError(format!(
+            "Merkle proof carries {} sibling(s) but {} leaves imply a tree of depth {}",
+            proof.siblings_b64.len(), proof.num_leaves, expected_depth
+        )));
     }
-    
-    if decryption_keys.is_empty() {
-        return Err(EnclaveError::GenericError("Failed to fetch any keys from Mysten server".to_string()));
+    if expected_depth == 0 && !proof.siblings_b64.is_empty() {
+        return Err(EnclaveError::GenericError(
+            "Merkle proof for a single-leaf tree must not carry sibling hashes".to_string(),
+        ));
     }
-    
-    info!("Successfully fetched {} keys from Mysten SEAL server", decryption_keys.len());
-    Ok(decryption_keys)
-}
 
-/// Perform SEAL decryption using the fetched keys
-async fn perform_seal_decryption_with_keys(blob_data: &[u8], keys: &[Vec<u8>]) -> Result<Vec<u8>, EnclaveError> {
-    info!("Performing SEAL decryption with {} keys", keys.len());
-    
-    // Extract the encrypted payload from the blob
-    // Skip headers and key metadata to get to the actual encrypted content
-    let header_size = 37 + (keys.len() * 32); // Object ID + key IDs
-    
-    if header_size >= blob_data.len() {
-        return Err(EnclaveError::GenericError("Invalid blob structure for SEAL decryption".to_string()));
-    }
-    
-    let encrypted_payload = &blob_data[header_size..];
-    info!("Encrypted payload size: {} bytes", encrypted_payload.len());
-    
-    // Try decryption with each key (SEAL uses Identity-Based Encryption)
-    for (i, key) in keys.iter().enumerate() {
-        info!("Trying decryption with key {}/{}", i+1, keys.len());
-        
-        // This is where we would use the SEAL SDK for actual IBE decryption
-        // For now, implement a placeholder that shows the structure
-        
-        // In a real implementation, this would:
-        // 1. Parse the IBE public key from the server response
-        // 2. Use SEAL SDK to decrypt with IBE
-        // 3. Verify the decryption result
-        
-        info!("Key {}: {} bytes", i+1, key.len());
-        
-        // For testing, check if we can detect a successful decryption pattern
-        if key.len() > 32 && encrypted_payload.len() > 100 {
-            // This would be replaced with real SEAL IBE decryption
-            info!("Would attempt IBE decryption with key {} ({} bytes)", i+1, key.len());
+    let mut current: [u8; 32] = Sha256::digest(leaf_data).into();
+    let mut index = proof.leaf_index;
+    for sibling_b64 in &proof.siblings_b64 {
+        let sibling = base64::engine::general_purpose::STANDARD
+            .decode(sibling_b64)
+            .map_err(|e| EnclaveError::GenericError(format!("Invalid base64 Merkle sibling: {}", e)))?;
+        let sibling: [u8; 32] = sibling
+            .try_into()
+            .map_err(|_| EnclaveError::GenericError("Merkle sibling hash must be exactly 32 bytes".to_string()))?;
+
+        let mut hasher = Sha256::new();
+        if index & 1 == 0 {
+            hasher.update(current);
+            hasher.update(sibling);
+        } else {
+            hasher.update(sibling);
+            hasher.update(current);
         }
+        current = hasher.finalize().into();
+        index >>= 1;
     }
-    
-    // For now, return an error indicating we need full SEAL SDK integration
-    Err(EnclaveError::GenericError("SEAL IBE decryption requires full SEAL SDK integration - keys fetched successfully".to_string()))
-}
 
-/// Simplified SEAL blob analysis (synchronous version)
-fn parse_real_seal_blob_sync(data: &[u8]) -> Result<(String, usize), String> {
-    if data.len() < 100 {
-        return Err("Blob too small to be SEAL encrypted".to_string());
-    }
-    
-    // Extract potential object ID at offset 5 (found in analysis)
-    let object_id_bytes = &data[5..37]; // 32 bytes for object ID
-    let object_id_hex = format!("{:02x}{:02x}{:02x}...{:02x}{:02x}{:02x}", 
-        object_id_bytes[0], object_id_bytes[1], object_id_bytes[2],
-        object_id_bytes[29], object_id_bytes[30], object_id_bytes[31]);
-    
-    // Count potential key IDs
-    let mut key_count = 0;
-    for i in (37..data.len().saturating_sub(32)).step_by(32) {
-        let potential_key = &data[i..i+32];
-        if is_potential_key_id(potential_key) {
-            key_count += 1;
-        }
-        if key_count > 10 {
-            break; // Limit search
-        }
+    if current != expected_root {
+        return Err(EnclaveError::GenericError(
+            "Merkle inclusion proof verification failed: recomputed root does not match the on-chain commitment".to_string(),
+        ));
     }
-    
-    Ok((object_id_hex, key_count))
+
+    Ok(current)
 }
 
-/// Download blob from actual Walrus aggregator
-async fn download_from_walrus(blob_id: &str) -> Result<Vec<u8>, EnclaveError> {
+/// Download blob from actual Walrus aggregator. See `download_and_hash_blob`
+/// for the semantics of `caller_key`.
+///
+/// The response body is consumed via `bytes_stream()` rather than a single
+/// `.bytes()` call: `SafeReader` hashes each chunk as it arrives and, when
+/// `caller_key` is set, the streaming AES-GCM decryptor is fed the same
+/// chunks. Peak memory still grows with the blob on every path: `SafeReader`
+/// keeps all raw bytes (up to its `max_length` cap) until `finish`, and the
+/// caller-key path also accumulates the full plaintext before returning it.
+async fn download_from_walrus(
+    blob_id: &str,
+    caller_key: Option<[u8; 32]>,
+    enclave_kp: &Ed25519KeyPair,
+) -> Result<Vec<u8>, EnclaveError> {
+    use futures::StreamExt;
+
     // Set the blob ID for decryption use
     std::env::set_var("CURRENT_BLOB_ID", blob_id);
-    
+
     let aggregator_url = std::env::var("WALRUS_AGGREGATOR_URL")
         .unwrap_or_else(|_| "https://aggregator.walrus-testnet.walrus.space".to_string());
-    
+
     let url = format!("{}/v1/blobs/{}", aggregator_url, blob_id);
     info!("Fetching from Walrus: {}", url);
-    
+
     let client = reqwest::Client::new();
     let response = client.get(&url)
         .timeout(std::time::Duration::from_secs(30))
         .send()
         .await
         .map_err(|e| EnclaveError::GenericError(format!("Failed to download blob {}: {}", blob_id, e)))?;
-    
+
     if !response.status().is_success() {
         return Err(EnclaveError::GenericError(format!(
-            "Walrus returned status {}: {}", 
+            "Walrus returned status {}: {}",
             response.status(),
             response.text().await.unwrap_or_else(|_| "unknown error".to_string())
         )));
     }
-    
-    let mut data = response.bytes()
-        .await
-        .map_err(|e| EnclaveError::GenericError(format!("Failed to read blob data: {}", e)))?
-        .to_vec();
-    
+
+    // Wrap the untrusted byte stream in a SafeReader so a hung or
+    // bandwidth-starved aggregator, or a blob far larger than any real
+    // model/dataset, fails fast instead of consuming unbounded enclave
+    // memory and time. The raw bytes are only released once the stream
+    // reaches EOF and pass the length/hash checks below.
+    // No expected digest: a real Walrus blob_id isn't a SHA-256 of the raw
+    // bytes (see the note on the absent `verify_walrus_blob_id` above),
+    // so there's nothing trustworthy to pre-seed the SafeReader with here.
+    let mut safe_reader = safe_reader::SafeReader::new(safe_reader::SafeReaderConfig::new(None));
+    let mut decryptor = caller_key.map(|key| stream_crypto::IncrementalStreamDecryptor::new(&key));
+    let mut plaintext = Vec::new();
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| EnclaveError::GenericError(format!("Failed to read blob data: {}", e)))?;
+        safe_reader.feed(&chunk)?;
+        if let Some(decryptor) = decryptor.as_mut() {
+            plaintext.extend_from_slice(&decryptor.feed(&chunk)?);
+        }
+    }
+
+    let (raw, raw_digest) = safe_reader.finish()?;
+
+    if let Some(decryptor) = decryptor {
+        let tail = decryptor.finish().map_err(|e| {
+            EnclaveError::GenericError(format!("failed to decrypt blob with caller-supplied key: {}", e))
+        })?;
+        plaintext.extend_from_slice(&tail);
+        if plaintext.is_empty() {
+            return Err(EnclaveError::GenericError("Downloaded blob is empty".to_string()));
+        }
+        info!(
+            "Successfully downloaded and streamed-decrypted {} bytes from Walrus (raw sha256 {})",
+            plaintext.len(),
+            hex::encode(raw_digest)
+        );
+        return Ok(plaintext);
+    }
+
+    let mut data = raw;
     if data.is_empty() {
         return Err(EnclaveError::GenericError("Downloaded blob is empty".to_string()));
     }
-    
-    info!("Successfully downloaded {} bytes from Walrus", data.len());
-    
-    // Attempt to decrypt the blob if it appears to be encrypted
-    if let Ok(decrypted_data) = seal_impl::attempt_decrypt_blob(&data).await {
+
+    info!(
+        "Successfully downloaded {} bytes from Walrus (sha256 {})",
+        data.len(),
+        hex::encode(raw_digest)
+    );
+
+    // Attempt to decrypt the blob if it appears to be encrypted. `seal_impl`'s
+    // H2O-backed path runs first; if it can't place the blob, fall back to
+    // the Mysten-backed real SEAL IBE flow in this module before giving up.
+    if let Ok(decrypted_data) = seal_impl::attempt_decrypt_blob(&data, enclave_kp).await {
         info!("Successfully decrypted blob: {} -> {} bytes", data.len(), decrypted_data.len());
         data = decrypted_data;
+    } else if let Ok(decrypted_data) = attempt_decrypt_blob(&data, enclave_kp).await {
+        info!("Successfully decrypted blob via Mysten SEAL path: {} -> {} bytes", data.len(), decrypted_data.len());
+        data = decrypted_data;
     } else {
         info!("Blob does not appear to be encrypted or decryption failed, using raw data");
     }
@@ -710,6 +1193,10 @@ struct ModelInfo {
     parameters: u64,
     input_shape: Vec<u64>,
     output_shape: Vec<u64>,
+    /// Count of tensors per declared dtype (e.g. `"F32" -> 42`). Only
+    /// populated for formats that carry per-tensor dtype metadata
+    /// (currently safetensors); empty otherwise.
+    dtype_distribution: HashMap<String, u64>,
 }
 
 /// Dataset information after validation
@@ -719,6 +1206,54 @@ struct DatasetInfo {
     rows: u64,
     columns: u64,
     data_types: HashMap<String, String>,
+    /// Per-column schema from `infer_csv_column_schema`: a finer type than
+    /// `data_types`'s coarse label, plus completeness and cardinality.
+    /// `None` for every non-CSV format, and for the placeholder CSV
+    /// `DatasetInfo` that `process_data` builds for test datasets without
+    /// parsing them.
+    csv_column_schema: Option<HashMap<String, CsvColumnSchema>>,
+}
+
+/// A CSV column's inferred type plus how complete and how varied its values
+/// are, from `infer_csv_column_schema`. Lets bias/integrity assessments key
+/// off real column semantics (e.g. a low-cardinality `Categorical` column is
+/// a candidate protected-group attribute) instead of `data_types`'s single
+/// numeric-vs-text guess.
+#[derive(Debug, Clone)]
+struct CsvColumnSchema {
+    inferred_type: CsvColumnType,
+    /// Fraction of sampled values that were empty/missing, scaled by 10000.
+    null_ratio: u64,
+    /// Count of distinct non-null values seen in the sample.
+    distinct_values: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CsvColumnType {
+    Integer,
+    Float,
+    Boolean,
+    Date,
+    DateTime,
+    /// Low-cardinality string column, e.g. a category or protected-group code.
+    Categorical,
+    Text,
+    Unknown,
+}
+
+impl CsvColumnType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CsvColumnType::Integer => "integer",
+            CsvColumnType::Float => "float",
+            CsvColumnType::Boolean => "boolean",
+            CsvColumnType::Date => "date",
+            CsvColumnType::DateTime => "datetime",
+            CsvColumnType::Categorical => "categorical",
+            CsvColumnType::Text => "text",
+            CsvColumnType::Unknown => "unknown",
+        }
+    }
 }
 
 /// Assessment result from quality analysis
@@ -731,6 +1266,14 @@ struct AssessmentResult {
     throughput: u64,
     data_integrity_score: u64,
     bias_assessment: BiasAssessment,
+    /// Populated only when `assessment_type` was `ComprehensiveBenchmark`;
+    /// see `PerformanceMetrics` for field meaning.
+    benchmark_scenario: Option<String>,
+    p50_latency_ms: Option<u64>,
+    p90_latency_ms: Option<u64>,
+    p99_latency_ms: Option<u64>,
+    latency_sla_met: Option<bool>,
+    quantization: Option<QuantizationAssessment>,
 }
 
 /// Validate and load model from binary data
@@ -738,7 +1281,9 @@ fn validate_and_load_model(data: &[u8], _type_hint: &Option<String>) -> Result<M
     info!("Validating model data ({} bytes)", data.len());
     
     // Try to detect model format based on file headers and content
-    let model_info = if is_onnx_model(data) {
+    let model_info = if is_safetensors_model(data) {
+        analyze_safetensors_model(data)?
+    } else if is_onnx_model(data) {
         analyze_onnx_model(data)?
     } else if is_pytorch_model(data) {
         analyze_pytorch_model(data)?
@@ -762,6 +1307,20 @@ fn validate_and_load_model(data: &[u8], _type_hint: &Option<String>) -> Result<M
     Ok(model_info)
 }
 
+/// Check if data is a safetensors file: an 8-byte little-endian header
+/// length followed by that many bytes of valid UTF-8 (the JSON tensor
+/// index), entirely within the buffer.
+fn is_safetensors_model(data: &[u8]) -> bool {
+    if data.len() < 8 {
+        return false;
+    }
+    let header_len = u64::from_le_bytes(data[..8].try_into().unwrap()) as usize;
+    header_len > 0
+        && 8usize
+            .checked_add(header_len)
+            .is_some_and(|header_end| header_end <= data.len() && std::str::from_utf8(&data[8..header_end]).is_ok())
+}
+
 /// Check if data represents an ONNX model
 fn is_onnx_model(data: &[u8]) -> bool {
     // ONNX models start with protobuf magic bytes
@@ -784,47 +1343,163 @@ fn is_tensorflow_model(data: &[u8]) -> bool {
 /// Analyze ONNX model (simplified analysis)
 fn analyze_onnx_model(data: &[u8]) -> Result<ModelInfo, EnclaveError> {
     info!("Analyzing ONNX model");
-    
+
     // In a real implementation, you'd parse the ONNX protobuf
     // For now, estimate based on file size
     let estimated_params = estimate_parameters_from_size(data.len());
-    
+
     Ok(ModelInfo {
         model_type: "deep_neural_network".to_string(),
         framework: "onnx".to_string(),
         parameters: estimated_params,
         input_shape: vec![1, 3, 224, 224], // Common image input
         output_shape: vec![1, 1000], // ImageNet classes
+        dtype_distribution: HashMap::new(),
     })
 }
 
 /// Analyze PyTorch model (simplified analysis)
 fn analyze_pytorch_model(data: &[u8]) -> Result<ModelInfo, EnclaveError> {
     info!("Analyzing PyTorch model");
-    
+
     let estimated_params = estimate_parameters_from_size(data.len());
-    
+
     Ok(ModelInfo {
         model_type: "neural_network".to_string(),
         framework: "pytorch".to_string(),
         parameters: estimated_params,
         input_shape: vec![1, 784], // MNIST-like input
         output_shape: vec![1, 10], // Classification output
+        dtype_distribution: HashMap::new(),
     })
 }
 
 /// Analyze TensorFlow model (simplified analysis)
 fn analyze_tensorflow_model(data: &[u8]) -> Result<ModelInfo, EnclaveError> {
     info!("Analyzing TensorFlow model");
-    
+
     let estimated_params = estimate_parameters_from_size(data.len());
-    
+
     Ok(ModelInfo {
         model_type: "neural_network".to_string(),
         framework: "tensorflow".to_string(),
         parameters: estimated_params,
         input_shape: vec![1, 28, 28, 1], // MNIST input
         output_shape: vec![1, 10], // Classification output
+        dtype_distribution: HashMap::new(),
+    })
+}
+
+/// One tensor's entry in a safetensors header: its dtype, shape, and byte
+/// range within the body that follows the header.
+#[derive(Debug, Deserialize)]
+struct SafetensorsTensorInfo {
+    dtype: String,
+    shape: Vec<u64>,
+    data_offsets: [u64; 2],
+}
+
+/// Byte width of one element for a safetensors dtype code, per the format's
+/// fixed set of supported types (https://github.com/huggingface/safetensors).
+fn safetensors_dtype_byte_size(dtype: &str) -> Option<u64> {
+    match dtype {
+        "BOOL" | "U8" | "I8" | "F8_E4M3" | "F8_E5M2" => Some(1),
+        "U16" | "I16" | "F16" | "BF16" => Some(2),
+        "U32" | "I32" | "F32" => Some(4),
+        "U64" | "I64" | "F64" => Some(8),
+        _ => None,
+    }
+}
+
+/// Analyze a safetensors model: parse its JSON tensor index (see
+/// `is_safetensors_model` for the header layout), sum each tensor's
+/// `shape.iter().product()` for an exact parameter count, tally a
+/// dtype histogram, and take the first/last tensor's shape (by byte offset,
+/// i.e. file layout order) as the input/output shape.
+fn analyze_safetensors_model(data: &[u8]) -> Result<ModelInfo, EnclaveError> {
+    info!("Analyzing safetensors model");
+
+    let header_len = u64::from_le_bytes(
+        data[..8]
+            .try_into()
+            .map_err(|_| EnclaveError::GenericError("safetensors header length is truncated".to_string()))?,
+    ) as usize;
+    let header_end = 8usize
+        .checked_add(header_len)
+        .filter(|&end| end <= data.len())
+        .ok_or_else(|| EnclaveError::GenericError("safetensors header length exceeds buffer".to_string()))?;
+
+    let header_str = std::str::from_utf8(&data[8..header_end])
+        .map_err(|e| EnclaveError::GenericError(format!("safetensors header is not valid UTF-8: {}", e)))?;
+    let header: serde_json::Map<String, serde_json::Value> = serde_json::from_str(header_str)
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to parse safetensors header: {}", e)))?;
+
+    let body_len = (data.len() - header_end) as u64;
+    let mut tensors: Vec<(String, SafetensorsTensorInfo)> = Vec::new();
+    for (name, value) in &header {
+        if name == "__metadata__" {
+            continue;
+        }
+        let info: SafetensorsTensorInfo = serde_json::from_value(value.clone())
+            .map_err(|e| EnclaveError::GenericError(format!("Malformed safetensors tensor entry '{}': {}", name, e)))?;
+        if info.data_offsets[1] > body_len {
+            return Err(EnclaveError::GenericError(format!(
+                "safetensors tensor '{}' data_offsets {:?} exceed the {}-byte tensor body",
+                name, info.data_offsets, body_len
+            )));
+        }
+
+        // A malformed or adversarial file can declare a shape decoupled from
+        // its actual on-disk size; cross-check the declared offset range
+        // against what dtype+shape imply before trusting `shape` for the
+        // parameter count below.
+        let declared_len = info.data_offsets[1].saturating_sub(info.data_offsets[0]);
+        let element_size = safetensors_dtype_byte_size(&info.dtype).ok_or_else(|| {
+            EnclaveError::GenericError(format!("safetensors tensor '{}' has unsupported dtype '{}'", name, info.dtype))
+        })?;
+        let expected_len = info.shape.iter().product::<u64>().saturating_mul(element_size);
+        if expected_len != declared_len {
+            return Err(EnclaveError::GenericError(format!(
+                "safetensors tensor '{}' shape {:?} ({} dtype) implies {} bytes but data_offsets {:?} declare {}",
+                name, info.shape, info.dtype, expected_len, info.data_offsets, declared_len
+            )));
+        }
+
+        tensors.push((name.clone(), info));
+    }
+
+    if tensors.is_empty() {
+        return Err(EnclaveError::GenericError("safetensors file declares no tensors".to_string()));
+    }
+
+    // Order by byte offset so "first"/"last" means first/last in file
+    // layout, not however the JSON object happened to iterate.
+    tensors.sort_by_key(|(_, info)| info.data_offsets[0]);
+
+    let parameters: u64 = tensors.iter().map(|(_, info)| info.shape.iter().product::<u64>()).sum();
+
+    let mut dtype_distribution: HashMap<String, u64> = HashMap::new();
+    for (_, info) in &tensors {
+        *dtype_distribution.entry(info.dtype.clone()).or_insert(0) += 1;
+    }
+
+    let input_shape = tensors.first().map(|(_, info)| info.shape.clone()).unwrap_or_default();
+    let output_shape = tensors.last().map(|(_, info)| info.shape.clone()).unwrap_or_default();
+
+    info!(
+        "Parsed safetensors model: {} tensors, {} parameters, dtypes {:?}",
+        tensors.len(),
+        parameters,
+        dtype_distribution
+    );
+
+    Ok(ModelInfo {
+        model_type: "safetensors_model".to_string(),
+        framework: "safetensors".to_string(),
+        parameters,
+        input_shape,
+        output_shape,
+        dtype_distribution,
     })
 }
 
@@ -848,6 +1523,7 @@ fn parse_json_model_metadata(data: &[u8]) -> Result<ModelInfo, EnclaveError> {
             parameters: metadata["parameters"].as_u64().unwrap_or(estimate_parameters_from_size(data.len())),
             input_shape: vec![1, 784],
             output_shape: vec![1, 10],
+            dtype_distribution: HashMap::new(),
         })
     } else {
         // Binary model without metadata - make educated guesses
@@ -857,6 +1533,7 @@ fn parse_json_model_metadata(data: &[u8]) -> Result<ModelInfo, EnclaveError> {
             parameters: estimate_parameters_from_size(data.len()),
             input_shape: vec![1, 784],
             output_shape: vec![1, 10],
+            dtype_distribution: HashMap::new(),
         })
     }
 }
@@ -926,37 +1603,176 @@ fn is_image_dataset(data: &[u8]) -> bool {
     data.starts_with(b"PK\x03\x04") // ZIP magic number
 }
 
-/// Process CSV dataset
+/// Maximum rows sampled per column for `infer_csv_column_schema` — enough to
+/// confidently call a type without re-scanning a potentially huge dataset.
+const CSV_SCHEMA_SAMPLE_SIZE: usize = 200;
+
+/// Split one CSV record into fields, honoring RFC 4180 quoting: a quoted
+/// field may itself contain `delimiter`, and a doubled quote (`""`) inside a
+/// quoted field is an escaped literal `"`. Fields aren't trimmed here so
+/// callers can decide whether leading/trailing whitespace is significant.
+/// Note: like the rest of this module, records are assumed to be one text
+/// line each — a quoted field containing an embedded newline isn't
+/// supported, since dataset bytes are split on lines before this runs.
+pub(crate) fn split_csv_record(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    current.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                current.push(c);
+            }
+        } else if c == '"' {
+            in_quotes = true;
+        } else if c == delimiter {
+            fields.push(std::mem::take(&mut current));
+        } else {
+            current.push(c);
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+fn is_boolean_literal(value: &str) -> bool {
+    matches!(value.to_ascii_lowercase().as_str(), "true" | "false" | "yes" | "no")
+}
+
+fn is_iso_date(value: &str) -> bool {
+    let bytes = value.as_bytes();
+    bytes.len() == 10
+        && bytes[4] == b'-'
+        && bytes[7] == b'-'
+        && value[0..4].bytes().all(|b| b.is_ascii_digit())
+        && value[5..7].bytes().all(|b| b.is_ascii_digit())
+        && value[8..10].bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_iso_datetime(value: &str) -> bool {
+    if value.len() < 19 {
+        return false;
+    }
+    let (date_part, rest) = value.split_at(10);
+    if !is_iso_date(date_part) {
+        return false;
+    }
+    let Some(time_part) = rest.strip_prefix('T').or_else(|| rest.strip_prefix(' ')) else {
+        return false;
+    };
+    let bytes = time_part.as_bytes();
+    bytes.len() >= 8
+        && bytes[2] == b':'
+        && bytes[5] == b':'
+        && time_part[0..2].bytes().all(|b| b.is_ascii_digit())
+        && time_part[3..5].bytes().all(|b| b.is_ascii_digit())
+        && time_part[6..8].bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Infer one column's schema from up to `CSV_SCHEMA_SAMPLE_SIZE` sampled data
+/// rows: its type (by >=80% agreement among non-null sampled values, checked
+/// most-specific-first so e.g. a `true`/`false` column isn't first captured
+/// by a looser check), its null ratio, and its distinct-value count. A
+/// low-cardinality, non-numeric column is called `Categorical` rather than
+/// plain `Text` since that's the shape a protected-group attribute usually
+/// takes.
+fn infer_csv_column_schema(rows: &[Vec<String>], column_index: usize) -> CsvColumnSchema {
+    let mut total: u64 = 0;
+    let mut nulls: u64 = 0;
+    let mut boolean_count: u64 = 0;
+    let mut datetime_count: u64 = 0;
+    let mut date_count: u64 = 0;
+    let mut integer_count: u64 = 0;
+    let mut float_count: u64 = 0;
+    let mut distinct: HashSet<String> = HashSet::new();
+
+    for row in rows.iter().take(CSV_SCHEMA_SAMPLE_SIZE) {
+        let Some(raw) = row.get(column_index) else { continue };
+        total += 1;
+        let value = raw.trim();
+        if value.is_empty() {
+            nulls += 1;
+            continue;
+        }
+        distinct.insert(value.to_string());
+        if is_boolean_literal(value) {
+            boolean_count += 1;
+        } else if is_iso_datetime(value) {
+            datetime_count += 1;
+        } else if is_iso_date(value) {
+            date_count += 1;
+        } else if value.parse::<i64>().is_ok() {
+            integer_count += 1;
+        } else if value.parse::<f64>().is_ok() {
+            float_count += 1;
+        }
+    }
+
+    let non_null = total - nulls;
+    let threshold = ((non_null as f64) * 0.8).ceil() as u64;
+    let inferred_type = if non_null == 0 {
+        CsvColumnType::Unknown
+    } else if boolean_count >= threshold {
+        CsvColumnType::Boolean
+    } else if datetime_count >= threshold {
+        CsvColumnType::DateTime
+    } else if date_count >= threshold {
+        CsvColumnType::Date
+    } else if integer_count >= threshold {
+        CsvColumnType::Integer
+    } else if integer_count + float_count >= threshold {
+        CsvColumnType::Float
+    } else if (distinct.len() as u64) <= (non_null / 4).max(1).min(20) {
+        CsvColumnType::Categorical
+    } else {
+        CsvColumnType::Text
+    };
+
+    CsvColumnSchema {
+        inferred_type,
+        null_ratio: if total == 0 { 0 } else { (nulls * 10000) / total },
+        distinct_values: distinct.len() as u64,
+    }
+}
+
+/// Process CSV dataset: a quoted-field-aware reader (`split_csv_record`) over
+/// every row, with per-column type/completeness/cardinality inference
+/// (`infer_csv_column_schema`) replacing the old first-10-rows
+/// numeric-vs-text guess.
 fn process_csv_dataset(data: &[u8]) -> Result<DatasetInfo, EnclaveError> {
     let data_str = std::str::from_utf8(data)
         .map_err(|e| EnclaveError::GenericError(format!("Invalid UTF-8 in CSV: {}", e)))?;
-    
+
     let lines: Vec<&str> = data_str.lines().filter(|line| !line.trim().is_empty()).collect();
     if lines.is_empty() {
         return Err(EnclaveError::GenericError("Empty CSV dataset".to_string()));
     }
-    
-    // Parse header
-    let header = lines[0];
-    let columns: Vec<&str> = header.split(',').map(|s| s.trim()).collect();
-    let rows = lines.len() - 1; // Exclude header
-    
-    // Analyze data types by sampling first few rows
+
+    let columns: Vec<String> = split_csv_record(lines[0], ',').iter().map(|c| c.trim().to_string()).collect();
+    let data_rows: Vec<Vec<String>> = lines[1..].iter().map(|line| split_csv_record(line, ',')).collect();
+
     let mut data_types = HashMap::new();
+    let mut csv_column_schema = HashMap::new();
     for (i, column) in columns.iter().enumerate() {
-        let column_type = if lines.len() > 1 {
-            analyze_csv_column_type(&lines[1..], i)
-        } else {
-            "unknown".to_string()
-        };
-        data_types.insert(column.to_string(), column_type);
+        let schema = infer_csv_column_schema(&data_rows, i);
+        data_types.insert(column.clone(), schema.inferred_type.as_str().to_string());
+        csv_column_schema.insert(column.clone(), schema);
     }
-    
+
     Ok(DatasetInfo {
         format: "csv".to_string(),
-        rows: rows as u64,
+        rows: data_rows.len() as u64,
         columns: columns.len() as u64,
         data_types,
+        csv_column_schema: Some(csv_column_schema),
     })
 }
 
@@ -999,90 +1815,217 @@ fn process_json_dataset(data: &[u8]) -> Result<DatasetInfo, EnclaveError> {
                 rows,
                 columns,
                 data_types,
+                csv_column_schema: None,
             })
         },
         _ => Err(EnclaveError::GenericError("JSON must be an array of objects".to_string())),
     }
 }
 
-/// Process Parquet dataset (simplified)
-fn process_parquet_dataset(_data: &[u8]) -> Result<DatasetInfo, EnclaveError> {
-    // In a real implementation, you'd use the parquet crate
-    // For now, return estimated information
+/// Process Parquet dataset by reading its footer metadata only (no row
+/// groups are materialized): row counts are summed across row groups and
+/// column count/types come straight from the file's own schema, so the
+/// numbers the enclave signs are the file's actual shape, not a guess.
+fn process_parquet_dataset(data: &[u8]) -> Result<DatasetInfo, EnclaveError> {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    let reader = SerializedFileReader::new(bytes::Bytes::copy_from_slice(data))
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to read Parquet metadata: {}", e)))?;
+    let metadata = reader.metadata();
+    let schema = metadata.file_metadata().schema_descr();
+
+    let rows: u64 = (0..metadata.num_row_groups())
+        .map(|i| metadata.row_group(i).num_rows() as u64)
+        .sum();
+    let columns = schema.num_columns() as u64;
+
+    let mut data_types = HashMap::new();
+    for column in schema.columns() {
+        let type_name = match column.logical_type() {
+            Some(logical_type) => format!("{:?}", logical_type),
+            None => format!("{:?}", column.physical_type()),
+        };
+        data_types.insert(column.name().to_string(), type_name);
+    }
+
     Ok(DatasetInfo {
         format: "parquet".to_string(),
-        rows: 1000, // Estimated
-        columns: 10, // Estimated
-        data_types: HashMap::from([
-            ("col1".to_string(), "numeric".to_string()),
-            ("col2".to_string(), "text".to_string()),
-        ]),
+        rows,
+        columns,
+        data_types,
+        csv_column_schema: None,
     })
 }
 
-/// Process NPY dataset (NumPy array)
+/// Extracts the single-quoted or double-quoted value immediately following
+/// `key` in an NPY header dict string, e.g. `find_quoted_value_after(header,
+/// "'descr'")` on `"{'descr': '<f8', ...}"` returns `Some("<f8")`.
+fn find_quoted_value_after(header_str: &str, key: &str) -> Option<String> {
+    let after_key = &header_str[header_str.find(key)? + key.len()..];
+    let quote_pos = after_key.find(['\'', '"'])?;
+    let quote_char = after_key.as_bytes()[quote_pos] as char;
+    let after_quote = &after_key[quote_pos + 1..];
+    let end = after_quote.find(quote_char)?;
+    Some(after_quote[..end].to_string())
+}
+
+/// Parses an NPY header dict (a Python `repr`-style string, e.g.
+/// `"{'descr': '<f8', 'fortran_order': False, 'shape': (100, 10), }"`) into
+/// its exact `descr` dtype string and `shape` dimensions.
+fn parse_npy_header(header_str: &str) -> Result<(String, Vec<u64>), EnclaveError> {
+    let descr = find_quoted_value_after(header_str, "'descr'")
+        .ok_or_else(|| EnclaveError::GenericError("NPY header missing 'descr'".to_string()))?;
+
+    let shape_key_pos = header_str
+        .find("'shape'")
+        .ok_or_else(|| EnclaveError::GenericError("NPY header missing 'shape'".to_string()))?;
+    let after_shape_key = &header_str[shape_key_pos..];
+    let open = after_shape_key
+        .find('(')
+        .ok_or_else(|| EnclaveError::GenericError("NPY header 'shape' has no opening parenthesis".to_string()))?;
+    let close = after_shape_key
+        .find(')')
+        .ok_or_else(|| EnclaveError::GenericError("NPY header 'shape' has no closing parenthesis".to_string()))?;
+    let shape = after_shape_key[open + 1..close]
+        .split(',')
+        .map(|dim| dim.trim())
+        .filter(|dim| !dim.is_empty())
+        .map(|dim| {
+            dim.parse::<u64>()
+                .map_err(|e| EnclaveError::GenericError(format!("NPY shape dimension '{}' is not a number: {}", dim, e)))
+        })
+        .collect::<Result<Vec<u64>, _>>()?;
+
+    Ok((descr, shape))
+}
+
+/// Process NPY dataset (NumPy array). NPY layout: a 6-byte magic string, a
+/// 1-byte major version, a 1-byte minor version, a header-length field (2
+/// bytes little-endian for v1.x, 4 bytes little-endian for v2.0+), then the
+/// header dict itself, then the raw array bytes.
 fn process_npy_dataset(data: &[u8]) -> Result<DatasetInfo, EnclaveError> {
-    // Parse NPY header to get array dimensions and dtype
-    // NPY format: magic_string + major_version + minor_version + header_len + header + data
-    
-    if data.len() < 10 {
-        return Err(EnclaveError::GenericError("NPY file too small".to_string()));
+    if data.len() < 10 || !data.starts_with(b"\x93NUMPY") {
+        return Err(EnclaveError::GenericError("Not a valid NPY file".to_string()));
     }
-    
-    // Skip magic string (6 bytes), major/minor version (2 bytes)
-    let header_len_bytes = &data[8..10];
-    let header_len = u16::from_le_bytes([header_len_bytes[0], header_len_bytes[1]]) as usize;
-    
-    if data.len() < 10 + header_len {
+
+    let major_version = data[6];
+    let (header_len, header_start) = if major_version >= 2 {
+        if data.len() < 12 {
+            return Err(EnclaveError::GenericError("NPY v2 file header incomplete".to_string()));
+        }
+        (u32::from_le_bytes([data[8], data[9], data[10], data[11]]) as usize, 12)
+    } else {
+        (u16::from_le_bytes([data[8], data[9]]) as usize, 10)
+    };
+
+    if data.len() < header_start + header_len {
         return Err(EnclaveError::GenericError("NPY file header incomplete".to_string()));
     }
-    
-    let header_bytes = &data[10..10 + header_len];
+
+    let header_bytes = &data[header_start..header_start + header_len];
     let header_str = std::str::from_utf8(header_bytes)
         .map_err(|_| EnclaveError::GenericError("NPY header not valid UTF-8".to_string()))?;
-    
-    // Parse basic info from header (simplified parsing)
-    let rows = if header_str.contains("shape") {
-        // Try to extract shape information (simplified)
-        if header_str.contains("(") && header_str.contains(",") {
-            // Multi-dimensional array
-            1000 // Estimated for now
-        } else {
-            // 1D array
-            (data.len() - 10 - header_len) / 8 // Estimate assuming float64
-        }
-    } else {
-        1000 // Default estimate
-    };
-    
-    let data_type = if header_str.contains("'f") {
-        "numeric".to_string()
-    } else if header_str.contains("'i") || header_str.contains("'u") {
-        "integer".to_string()
-    } else if header_str.contains("'b") {
-        "boolean".to_string()
-    } else {
-        "numeric".to_string() // Default to numeric
-    };
-    
-    // For multi-dimensional arrays, columns represent features
-    let columns = if header_str.contains("shape") && header_str.contains(",") {
-        // Try to extract second dimension as feature count
-        10 // Estimated for now
-    } else {
-        1 // 1D array has 1 column
+
+    let (dtype, shape) = parse_npy_header(header_str)?;
+    let (rows, columns) = match shape.split_first() {
+        Some((rows, remaining_dims)) => (*rows, remaining_dims.iter().product::<u64>().max(1)),
+        None => return Err(EnclaveError::GenericError("NPY array has no dimensions".to_string())),
     };
-    
+
     Ok(DatasetInfo {
         format: "npy".to_string(),
-        rows: rows as u64,
-        columns: columns as u64,
-        data_types: HashMap::from([
-            ("array_data".to_string(), data_type),
-        ]),
+        rows,
+        columns,
+        data_types: HashMap::from([("array_data".to_string(), dtype)]),
+        csv_column_schema: None,
     })
 }
 
+/// Split a dataset into per-record byte slices (one CSV row / one NPY row),
+/// for `record_merkle::RecordMerkleTree::build`. Errors rather than
+/// approximating for formats with no well-defined "record" boundary at the
+/// byte level (parquet, JSON, image archives) — `dataset_record_merkle_root`
+/// is simply `None` for those instead of a misleading best-effort root.
+fn split_dataset_into_records(dataset_info: &DatasetInfo, dataset_data: &[u8]) -> Result<Vec<Vec<u8>>, EnclaveError> {
+    match dataset_info.format.as_str() {
+        "csv" => {
+            let text = std::str::from_utf8(dataset_data)
+                .map_err(|e| EnclaveError::GenericError(format!("Dataset is not valid UTF-8 CSV: {}", e)))?;
+            let records: Vec<Vec<u8>> = text
+                .lines()
+                .skip(1) // header
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.as_bytes().to_vec())
+                .collect();
+            if records.is_empty() {
+                return Err(EnclaveError::GenericError("CSV dataset has no data rows to commit to".to_string()));
+            }
+            Ok(records)
+        }
+        "npy" => split_npy_into_records(dataset_data),
+        other => Err(EnclaveError::GenericError(format!(
+            "per-record Merkle commitment is not supported for dataset format '{}'",
+            other
+        ))),
+    }
+}
+
+/// Byte width of one NPY array element from its `descr` string, e.g. `<f8` is
+/// 8 bytes, `|u1` is 1 byte. NPY's trailing digit is always a byte count, not
+/// a bit count, so this is just the numeric suffix after the byte-order and
+/// type-code prefix.
+fn npy_dtype_byte_size(descr: &str) -> Option<usize> {
+    descr.get(2..)?.parse::<usize>().ok()
+}
+
+/// Split an NPY array into one byte slice per row, re-parsing the same
+/// header `process_npy_dataset` does since `DatasetInfo` doesn't retain the
+/// element size or body offset.
+fn split_npy_into_records(data: &[u8]) -> Result<Vec<Vec<u8>>, EnclaveError> {
+    if data.len() < 10 || !data.starts_with(b"\x93NUMPY") {
+        return Err(EnclaveError::GenericError("Not a valid NPY file".to_string()));
+    }
+
+    let major_version = data[6];
+    let (header_len, header_start) = if major_version >= 2 {
+        if data.len() < 12 {
+            return Err(EnclaveError::GenericError("NPY v2 file header incomplete".to_string()));
+        }
+        (u32::from_le_bytes([data[8], data[9], data[10], data[11]]) as usize, 12)
+    } else {
+        (u16::from_le_bytes([data[8], data[9]]) as usize, 10)
+    };
+
+    if data.len() < header_start + header_len {
+        return Err(EnclaveError::GenericError("NPY file header incomplete".to_string()));
+    }
+
+    let header_bytes = &data[header_start..header_start + header_len];
+    let header_str = std::str::from_utf8(header_bytes)
+        .map_err(|_| EnclaveError::GenericError("NPY header not valid UTF-8".to_string()))?;
+
+    let (descr, shape) = parse_npy_header(header_str)?;
+    let (rows, columns) = match shape.split_first() {
+        Some((rows, remaining_dims)) => (*rows, remaining_dims.iter().product::<u64>().max(1)),
+        None => return Err(EnclaveError::GenericError("NPY array has no dimensions".to_string())),
+    };
+    let element_size = npy_dtype_byte_size(&descr)
+        .ok_or_else(|| EnclaveError::GenericError(format!("Unsupported NPY dtype for row splitting: {}", descr)))?;
+    let row_stride = element_size * columns as usize;
+
+    let body = &data[header_start + header_len..];
+    let mut records = Vec::with_capacity(rows as usize);
+    for row in 0..rows as usize {
+        let start = row * row_stride;
+        let end = start + row_stride;
+        let chunk = body
+            .get(start..end)
+            .ok_or_else(|| EnclaveError::GenericError("NPY body shorter than its declared shape".to_string()))?;
+        records.push(chunk.to_vec());
+    }
+    Ok(records)
+}
+
 /// Process image dataset (ZIP archive)
 fn process_image_dataset(_data: &[u8]) -> Result<DatasetInfo, EnclaveError> {
     // In a real implementation, you'd extract and analyze images
@@ -1095,31 +2038,47 @@ fn process_image_dataset(_data: &[u8]) -> Result<DatasetInfo, EnclaveError> {
             ("image_data".to_string(), "image".to_string()),
             ("label".to_string(), "categorical".to_string()),
         ]),
+        csv_column_schema: None,
     })
 }
 
-/// Analyze CSV column type by sampling values
-fn analyze_csv_column_type(lines: &[&str], column_index: usize) -> String {
-    let mut numeric_count = 0;
-    let mut total_count = 0;
-    
-    for line in lines.iter().take(10) { // Sample first 10 rows
-        let values: Vec<&str> = line.split(',').collect();
-        if let Some(value) = values.get(column_index) {
-            total_count += 1;
-            if value.trim().parse::<f64>().is_ok() {
-                numeric_count += 1;
-            }
-        }
+/// Find the first numeric (`Integer` or `Float`) CSV column, per
+/// `infer_csv_column_schema`, and return its values' absolute values scaled
+/// by 100 and rounded to integers, a cheap way to carry two decimal places of precision
+/// as BLS12-381 scalar field elements for `kzg::commit_and_open`. Returns
+/// `None` for non-CSV data or a dataset with no numeric column.
+fn extract_committable_column(dataset_data: &[u8]) -> Option<Vec<u64>> {
+    let text = std::str::from_utf8(dataset_data).ok()?;
+    let lines: Vec<&str> = text.lines().filter(|line| !line.trim().is_empty()).collect();
+    if lines.len() < 2 {
+        return None;
     }
-    
-    if total_count == 0 {
-        "unknown".to_string()
-    } else if numeric_count as f64 / total_count as f64 > 0.8 {
-        "numeric".to_string()
-    } else {
-        "text".to_string()
+
+    let data_rows: Vec<Vec<String>> = lines[1..].iter().map(|line| split_csv_record(line, ',')).collect();
+    let num_columns = split_csv_record(lines[0], ',').len();
+    for column_index in 0..num_columns {
+        let schema = infer_csv_column_schema(&data_rows, column_index);
+        if !matches!(schema.inferred_type, CsvColumnType::Integer | CsvColumnType::Float) {
+            continue;
+        }
+        let values: Vec<u64> = data_rows
+            .iter()
+            .filter_map(|row| row.get(column_index))
+            .filter_map(|value| value.trim().parse::<f64>().ok())
+            .map(|value| (value.abs() * 100.0).round() as u64)
+            .collect();
+        if !values.is_empty() {
+            return Some(values);
+        }
     }
+    None
+}
+
+/// Build a `DatasetCommitment` over `column_values`, using `fiat_shamir_seed`
+/// (the dataset's own hash) to derive the non-interactive challenge point.
+fn build_dataset_commitment(column_values: &[u64], fiat_shamir_seed: &[u8]) -> Result<DatasetCommitment, EnclaveError> {
+    let (commitment, opening) = kzg::commit_and_open(column_values, fiat_shamir_seed)?;
+    Ok(DatasetCommitment { commitment, opening })
 }
 
 /// Perform real quality assessment using Python ML evaluator
@@ -1199,92 +2158,52 @@ async fn perform_real_quality_assessment(
             demographic_parity: bias_assessment.get("demographic_parity").and_then(|v| v.as_u64()),
             equalized_odds: bias_assessment.get("equalized_odds").and_then(|v| v.as_u64()),
         },
+        // The Python evaluator only fills these in for a ComprehensiveBenchmark
+        // run; absent otherwise, same as the in-enclave engines::assess path.
+        benchmark_scenario: performance_metrics.get("benchmark_scenario").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        p50_latency_ms: performance_metrics.get("p50_latency_ms").and_then(|v| v.as_u64()),
+        p90_latency_ms: performance_metrics.get("p90_latency_ms").and_then(|v| v.as_u64()),
+        p99_latency_ms: performance_metrics.get("p99_latency_ms").and_then(|v| v.as_u64()),
+        latency_sla_met: performance_metrics.get("latency_sla_met").and_then(|v| v.as_bool()),
+        // Likewise only present when the Python evaluator itself ran a
+        // quantization comparison; absent otherwise.
+        quantization: evaluation.get("quantization").and_then(|q| {
+            Some(QuantizationAssessment {
+                fp32_f1: q.get("fp32_f1").and_then(|v| v.as_u64())?,
+                int8_f1: q.get("int8_f1").and_then(|v| v.as_u64())?,
+                accuracy_drop: q.get("accuracy_drop").and_then(|v| v.as_u64())?,
+                recommended_precision: q.get("recommended_precision").and_then(|v| v.as_str())?.to_string(),
+            })
+        }),
     };
     
     info!("Real ML assessment completed - Quality Score: {}", assessment_result.overall_quality_score);
     Ok(assessment_result)
 }
 
-/// Perform comprehensive quality assessment (fallback mock version)
-fn perform_quality_assessment(
-    model_info: &ModelInfo,
-    dataset_info: &DatasetInfo,
-    assessment_type: &AssessmentType,
-    metrics: &[String],
-) -> Result<AssessmentResult, EnclaveError> {
-    
-    info!("Performing {:?} assessment with metrics: {:?}", assessment_type, metrics);
-    
-    let start = std::time::Instant::now();
-    
-    // Simulate model inference time based on model size and dataset size
-    let base_inference_time = (model_info.parameters / 1000) + (dataset_info.rows / 10);
-    let inference_time_ms = base_inference_time.max(50).min(30000); // 50ms to 30s
-    
-    // Simulate actual inference delay
-    std::thread::sleep(std::time::Duration::from_millis(inference_time_ms.min(1000))); // Cap at 1s for testing
-    
-    // Calculate mock quality metrics based on model and data characteristics
-    let data_quality_factor = if dataset_info.rows > 1000 { 0.9 } else { 0.7 };
-    let model_quality_factor = if model_info.parameters > 50000 { 0.95 } else { 0.8 };
-    
-    let base_accuracy = data_quality_factor * model_quality_factor;
-    
-    let accuracy_metrics = AccuracyMetrics {
-        precision: ((base_accuracy + 0.02) * 10000.0) as u64,
-        recall: ((base_accuracy - 0.01) * 10000.0) as u64,
-        f1_score: (base_accuracy * 10000.0) as u64,
-        auc: Some(((base_accuracy + 0.05) * 10000.0) as u64),
-        rmse: None,
-        mae: None,
-    };
-    
-    // Calculate overall quality score (0-100)
-    let quality_score = ((base_accuracy * 85.0) + 10.0) as u64; // 10-95 range
-    
-    // Performance metrics
-    let memory_usage = (model_info.parameters * 4 / 1_048_576).max(10); // 4 bytes per param, min 10MB
-    let throughput = (100000 / inference_time_ms.max(1)).max(1); // Scaled by 100, samples per second
-    
-    // Data integrity assessment
-    let data_integrity_score = if dataset_info.columns > 5 && dataset_info.rows > 500 {
-        90
-    } else {
-        70
-    };
-    
-    // Bias assessment
-    let bias_assessment = BiasAssessment {
-        fairness_score: 85, // Mock fairness score
-        bias_detected: false,
-        bias_type: None,
-        demographic_parity: Some(9500), // 95.00% scaled by 10000
-        equalized_odds: Some(9300),     // 93.00% scaled by 10000
-    };
-    
-    let processing_time = start.elapsed().as_millis() as u64;
-    info!("Assessment completed in {}ms", processing_time);
-    
-    Ok(AssessmentResult {
-        overall_quality_score: quality_score,
-        accuracy: accuracy_metrics,
-        inference_time_ms,
-        memory_usage_mb: memory_usage,
-        throughput,
-        data_integrity_score,
-        bias_assessment,
-    })
-}
-
-/// Generate integrity hash for the assessment result
-fn generate_assessment_integrity_hash(response: &MLQualityResponse, timestamp: u64) -> String {
+/// Generate integrity hash for the assessment result. `model_merkle_root`/
+/// `dataset_merkle_root`, when the caller supplied a verified Merkle
+/// inclusion proof for that blob, are folded in so the attestation binds to
+/// the on-chain commitment the blob was checked against, not just its hash.
+fn generate_assessment_integrity_hash(
+    response: &MLQualityResponse,
+    timestamp: u64,
+    model_merkle_root: Option<&[u8; 32]>,
+    dataset_merkle_root: Option<&[u8; 32]>,
+) -> String {
     use sha2::{Sha256, Digest};
-    
+
     let mut hasher = Sha256::new();
-    
+
     // Hash key components of the assessment
     hasher.update(response.model_hash.as_bytes());
     hasher.update(response.dataset_hash.as_bytes());
+    if let Some(root) = model_merkle_root {
+        hasher.update(root);
+    }
+    if let Some(root) = dataset_merkle_root {
+        hasher.update(root);
+    }
     hasher.update(&response.quality_score.to_be_bytes());
     hasher.update(&response.accuracy_metrics.precision.to_be_bytes());
     hasher.update(&response.accuracy_metrics.recall.to_be_bytes());
@@ -1296,118 +2215,27 @@ fn generate_assessment_integrity_hash(response: &MLQualityResponse, timestamp: u
     hasher.update(&timestamp.to_be_bytes());
     hasher.update(response.model_type.as_bytes());
     hasher.update(response.dataset_format.as_bytes());
-    
-    format!("{:x}", hasher.finalize())
-}
-
-/// Generate cryptographic signature for model verification
-fn generate_model_verification_signature(
-    model_hash: &str, 
-    dataset_hash: &str, 
-    quality_score: u64,
-    keypair: &fastcrypto::ed25519::Ed25519KeyPair
-) -> String {
-    use fastcrypto::traits::Signer;
-    
-    // Create a verification message to sign
-    let mut hasher = Sha256::new();
-    hasher.update(b"MODEL_VERIFICATION_V1:");
-    hasher.update(model_hash.as_bytes());
-    hasher.update(b":");
-    hasher.update(dataset_hash.as_bytes());
-    hasher.update(b":");
-    hasher.update(&quality_score.to_be_bytes());
-    hasher.update(b":");
-    hasher.update(&std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs()
-        .to_be_bytes());
-    
-    let message_hash = hasher.finalize();
-    
-    // Sign the hash with the ephemeral keypair
-    let signature = keypair.sign(&message_hash);
-    
-    // Return base64-encoded signature
-    use base64::{Engine, engine::general_purpose::STANDARD};
-    STANDARD.encode(signature.as_ref())
-}
-
-/// Publish verification results to on-chain smart contract
-async fn publish_verification_onchain(
-    model_blob_id: &str,
-    quality_response: &MLQualityResponse,
-    assessment_hash: &str,
-    verification_signature: &str,
-) -> Result<String, EnclaveError> {
-    info!("Publishing verification results to blockchain for model: {}", model_blob_id);
-    
-    // Check if on-chain publishing is enabled
-    let publish_enabled = std::env::var("ONCHAIN_PUBLISH_ENABLED")
-        .map(|v| v.to_lowercase() == "true" || v == "1")
-        .unwrap_or(false);
-        
-    if !publish_enabled {
-        info!("On-chain publishing disabled (set ONCHAIN_PUBLISH_ENABLED=true to enable)");
-        return Ok("disabled".to_string());
+    if let Some(commitment) = &response.dataset_commitment {
+        hasher.update(commitment.commitment.commitment_g1_b64.as_bytes());
     }
-    
-    // Get Sui network configuration
-    let sui_rpc_url = std::env::var("SUI_RPC_URL")
-        .unwrap_or_else(|_| "https://fullnode.testnet.sui.io".to_string());
-    let marketplace_package_id = std::env::var("MARKETPLACE_PACKAGE_ID")
-        .map_err(|_| EnclaveError::GenericError("MARKETPLACE_PACKAGE_ID not set".to_string()))?;
-    let pending_model_id = std::env::var("PENDING_MODEL_ID")
-        .map_err(|_| EnclaveError::GenericError("PENDING_MODEL_ID not set".to_string()))?;
-        
-    // Prepare transaction data
-    let tx_data = serde_json::json!({
-        "packageId": marketplace_package_id,
-        "module": "satya_marketplace", 
-        "function": "complete_verification",
-        "arguments": [
-            pending_model_id,                           // model: &mut PendingModel
-            "REGISTRY_ID_PLACEHOLDER",                   // registry: &mut MarketplaceRegistry  
-            "nautilus-tee-v1",                          // enclave_id: String
-            quality_response.quality_score,              // quality_score: u64
-            format!("ML Assessment - F1: {:.2}%, Precision: {:.2}%, Recall: {:.2}%, Bias Score: {}", 
-                quality_response.accuracy_metrics.f1_score as f64 / 10000.0,
-                quality_response.accuracy_metrics.precision as f64 / 10000.0, 
-                quality_response.accuracy_metrics.recall as f64 / 10000.0,
-                quality_response.bias_assessment.fairness_score),  // security_assessment: String
-            hex::encode(assessment_hash),                // attestation_hash: vector<u8>
-            hex::encode(verification_signature),         // verifier_signature: vector<u8>
-            "CLOCK_ID_PLACEHOLDER"                       // clock: &Clock
-        ],
-        "typeArguments": []
-    });
-    
-    // For now, simulate the transaction (would need full Sui SDK integration)
-    let simulated_tx_digest = format!("0x{}", 
-        &assessment_hash[..32]  // Use first 32 chars of assessment hash as mock tx digest
-    );
-    
-    info!("Simulated blockchain transaction for model verification:");
-    info!("  Model Blob ID: {}", model_blob_id);
-    info!("  Quality Score: {}", quality_response.quality_score);
-    info!("  F1 Score: {:.2}%", quality_response.accuracy_metrics.f1_score as f64 / 10000.0);
-    info!("  Precision: {:.2}%", quality_response.accuracy_metrics.precision as f64 / 10000.0);
-    info!("  Recall: {:.2}%", quality_response.accuracy_metrics.recall as f64 / 10000.0);
-    info!("  Bias Score: {}", quality_response.bias_assessment.fairness_score);
-    info!("  Data Integrity: {}", quality_response.data_integrity_score);
-    info!("  Assessment Hash: {}...", &assessment_hash[..16]);
-    info!("  Verification Signature: {}...", &verification_signature[..16]);
-    info!("  Mock Transaction Digest: {}", simulated_tx_digest);
-    
-    // TODO: Implement actual Sui transaction submission
-    // This would require:
-    // 1. Sui SDK integration 
-    // 2. Private key management for TEE signer
-    // 3. Transaction building and submission
-    // 4. Error handling and retry logic
-    
-    Ok(simulated_tx_digest)
+    if let Some(computation) = &response.aggregates {
+        hasher.update(computation.rows_merkle_root_b64.as_bytes());
+        for result in &computation.results {
+            hasher.update(&result.column_index.to_be_bytes());
+            hasher.update(&[result.function as u8]);
+            hasher.update(&result.value.unwrap_or(0).to_be_bytes());
+        }
+    }
+    if let Some(root) = &response.dataset_record_merkle_root {
+        hasher.update(root.as_bytes());
+    }
+    if let Some(assessment) = &response.erasure_assessment {
+        hasher.update(&assessment.k.to_be_bytes());
+        hasher.update(&assessment.m.to_be_bytes());
+        hasher.update(&[assessment.reconstruction_verified as u8]);
+    }
+
+    format!("{:x}", hasher.finalize())
 }
 
 /// Check if blob ID corresponds to a test model (or any unknown model for demo)
@@ -1476,6 +2304,18 @@ mod tests {
                 quality_metrics: vec!["accuracy".to_string(), "performance".to_string()],
                 model_type_hint: Some("neural_network".to_string()),
                 dataset_format_hint: Some("csv".to_string()),
+                model_encryption_key: None,
+                dataset_encryption_key: None,
+                model_passphrase_key: None,
+                dataset_passphrase_key: None,
+                model_merkle_proof: None,
+                dataset_merkle_proof: None,
+                aggregates: vec![],
+                sensitive_attribute: None,
+                requested_row_index: None,
+                requested_record_index: None,
+                max_supported_attestation_version: None,
+                api_version: None,
             },
         };
 
@@ -1547,6 +2387,50 @@ mod tests {
         assert!(dataset_info.columns > 0);
     }
 
+    #[test]
+    fn test_merkle_inclusion_proof_single_leaf() {
+        use base64::Engine;
+
+        let leaf_data = b"model bytes".to_vec();
+        let root = Sha256::digest(&leaf_data);
+        let proof = MerkleInclusionProof {
+            siblings_b64: vec![],
+            leaf_index: 0,
+            num_leaves: 1,
+            expected_root_b64: base64::engine::general_purpose::STANDARD.encode(root),
+        };
+
+        let verified_root = verify_merkle_inclusion_proof(&proof, &leaf_data).unwrap();
+        assert_eq!(verified_root.as_slice(), root.as_slice());
+    }
+
+    #[test]
+    fn test_merkle_inclusion_proof_two_leaves() {
+        use base64::Engine;
+
+        let left_leaf = b"left".to_vec();
+        let right_leaf = b"right".to_vec();
+        let left_hash = Sha256::digest(&left_leaf);
+        let right_hash = Sha256::digest(&right_leaf);
+        let mut root_hasher = Sha256::new();
+        root_hasher.update(left_hash);
+        root_hasher.update(right_hash);
+        let root = root_hasher.finalize();
+
+        let proof = MerkleInclusionProof {
+            siblings_b64: vec![base64::engine::general_purpose::STANDARD.encode(right_hash)],
+            leaf_index: 0,
+            num_leaves: 2,
+            expected_root_b64: base64::engine::general_purpose::STANDARD.encode(root),
+        };
+        assert!(verify_merkle_inclusion_proof(&proof, &left_leaf).is_ok());
+
+        // Tampering with the expected root must be rejected.
+        let mut bad_proof = proof.clone();
+        bad_proof.expected_root_b64 = base64::engine::general_purpose::STANDARD.encode([0u8; 32]);
+        assert!(verify_merkle_inclusion_proof(&bad_proof, &left_leaf).is_err());
+    }
+
     #[test]
     fn test_serde_consistency() {
         // Ensure BCS serialization is consistent with Move contract
@@ -1570,6 +2454,11 @@ mod tests {
                 model_size_mb: 5,
                 dataset_size_mb: 10,
                 throughput_samples_per_second: 667,  // 6.67 scaled by 100
+                benchmark_scenario: None,
+                p50_latency_ms: None,
+                p90_latency_ms: None,
+                p99_latency_ms: None,
+                latency_sla_met: None,
             },
             data_integrity_score: 90,
             bias_assessment: BiasAssessment {
@@ -1581,8 +2470,16 @@ mod tests {
             },
             model_type: "neural_network".to_string(),
             dataset_format: "csv".to_string(),
+            dataset_commitment: None,
+            aggregates: None,
+            attestation_protocol_version: onchain::CURRENT_ATTESTATION_VERSION,
+            quantization: None,
+            dataset_row_opening: None,
+            dataset_record_merkle_root: None,
+            requested_record_proof: None,
+            erasure_assessment: None,
         };
-        
+
         let timestamp = 1744038900000u64;
         let intent_msg = IntentMessage::new(payload, timestamp, IntentScope::ProcessData);
         let signing_payload = bcs::to_bytes(&intent_msg).expect("should not fail");
@@ -0,0 +1,370 @@
+// Copyright (c) Satya Data Marketplace
+// Real Sui transaction submission for publishing verification results, with
+// versioned attestation negotiation and retry/backoff.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use fastcrypto::encoding::{Base64 as FcBase64, Encoding};
+use fastcrypto::traits::Signer;
+use sha2::{Digest, Sha256};
+use sui_sdk_types::{
+    Argument, Command, GasPayment, Identifier, Input, MoveCall, ObjectDigest, ObjectId as ObjectID,
+    ObjectReference, ProgrammableTransaction, Transaction, TransactionExpiration, TransactionKind, Version,
+};
+use tracing::info;
+
+use crate::EnclaveError;
+
+use super::{DatasetCommitment, MLQualityResponse};
+
+/// Oldest attestation protocol version this enclave will still sign for.
+pub const MIN_SUPPORTED_ATTESTATION_VERSION: u8 = 1;
+/// Newest attestation protocol version this enclave knows how to produce.
+/// V2 adds the KZG dataset commitment into the signed message; V1 (kept for
+/// contracts that haven't upgraded yet) signs only the plain hashes.
+pub const CURRENT_ATTESTATION_VERSION: u8 = 2;
+
+/// Sui's `Clock` shared object always lives at this well-known address, with
+/// `initial_shared_version` 1 from genesis.
+const SUI_CLOCK_OBJECT_ID: &str = "0x0000000000000000000000000000000000000000000000000000000000000006";
+const SUI_CLOCK_INITIAL_SHARED_VERSION: u64 = 1;
+
+const MAX_SUBMIT_ATTEMPTS: u32 = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Pick the newest attestation protocol version both the caller's contract
+/// and this enclave support, the same way execution clients negotiate engine
+/// API method versions: the caller advertises the newest version its
+/// contract understands, and the enclave never signs with anything newer.
+pub fn negotiate_attestation_version(caller_max_supported: Option<u8>) -> Result<u8, EnclaveError> {
+    let caller_max = caller_max_supported.unwrap_or(CURRENT_ATTESTATION_VERSION);
+    let negotiated = caller_max.min(CURRENT_ATTESTATION_VERSION);
+    if negotiated < MIN_SUPPORTED_ATTESTATION_VERSION {
+        return Err(EnclaveError::GenericError(format!(
+            "no mutually supported attestation protocol version: caller supports up to {}, enclave requires at least {}",
+            caller_max, MIN_SUPPORTED_ATTESTATION_VERSION
+        )));
+    }
+    Ok(negotiated)
+}
+
+/// Generate the cryptographic signature over a model verification, using the
+/// signing prefix and field layout for `protocol_version` (see
+/// `negotiate_attestation_version`). `dataset_commitment` is only folded into
+/// the signed message from V2 onward.
+pub fn generate_model_verification_signature(
+    protocol_version: u8,
+    model_hash: &str,
+    dataset_hash: &str,
+    quality_score: u64,
+    dataset_commitment: Option<&DatasetCommitment>,
+    keypair: &fastcrypto::ed25519::Ed25519KeyPair,
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("MODEL_VERIFICATION_V{}:", protocol_version).as_bytes());
+    hasher.update(model_hash.as_bytes());
+    hasher.update(b":");
+    hasher.update(dataset_hash.as_bytes());
+    hasher.update(b":");
+    hasher.update(&quality_score.to_be_bytes());
+    hasher.update(b":");
+    if protocol_version >= 2 {
+        if let Some(commitment) = dataset_commitment {
+            hasher.update(commitment.commitment.commitment_g1_b64.as_bytes());
+            hasher.update(b":");
+        }
+    }
+    hasher.update(
+        &std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            .to_be_bytes(),
+    );
+
+    let message_hash = hasher.finalize();
+    let signature = keypair.sign(&message_hash);
+
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    STANDARD.encode(signature.as_ref())
+}
+
+/// An object's current version and digest, as needed for an `ObjectReference`
+/// (owned objects, e.g. the gas coin) or an `Input::Shared`'s
+/// `initial_shared_version` (shared objects, e.g. the registry).
+pub(crate) struct ObjectState {
+    pub(crate) version: u64,
+    pub(crate) digest: String,
+}
+
+/// Look up `object_id` via `sui_getObject`, so PTB inputs and gas payment
+/// carry the object's real on-chain version/digest instead of a guess. Shared
+/// across the module's own PTB-builders and `seal_fix`'s `seal_approve` PTB.
+pub(crate) async fn resolve_object_state(rpc_url: &str, object_id: &str) -> Result<ObjectState, EnclaveError> {
+    let client = reqwest::Client::new();
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "sui_getObject",
+        "params": [object_id, { "showOwner": true }]
+    });
+
+    let response = client
+        .post(rpc_url)
+        .json(&body)
+        .timeout(Duration::from_secs(15))
+        .send()
+        .await
+        .map_err(|e| EnclaveError::GenericError(format!("sui_getObject RPC call failed: {}", e)))?;
+
+    let parsed: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to parse sui_getObject response: {}", e)))?;
+
+    let data = parsed.pointer("/result/data").ok_or_else(|| {
+        EnclaveError::GenericError(format!("sui_getObject returned no data for object {}", object_id))
+    })?;
+
+    // Shared objects carry their own versioning via `owner.Shared.initial_shared_version`;
+    // owned objects (the gas coin) use the object's current `version` field directly.
+    let version = data
+        .pointer("/owner/Shared/initial_shared_version")
+        .or_else(|| data.get("version"))
+        .and_then(|v| v.as_str().and_then(|s| s.parse().ok()).or_else(|| v.as_u64()))
+        .ok_or_else(|| EnclaveError::GenericError(format!("Could not determine version for object {}", object_id)))?;
+    let digest = data
+        .get("digest")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    Ok(ObjectState { version, digest })
+}
+
+/// Build the `complete_verification` programmable transaction: `model` and
+/// `registry` are mutable shared-object inputs resolved via
+/// `resolve_object_state`; `clock` is Sui's well-known shared Clock object;
+/// everything else is a BCS-encoded pure input.
+async fn build_complete_verification_ptb(
+    rpc_url: &str,
+    package_id: ObjectID,
+    registry_id: ObjectID,
+    pending_model_id: ObjectID,
+    quality_response: &MLQualityResponse,
+    assessment_hash: &str,
+    verification_signature: &str,
+) -> Result<ProgrammableTransaction, EnclaveError> {
+    let model_state = resolve_object_state(rpc_url, &pending_model_id.to_string()).await?;
+    let registry_state = resolve_object_state(rpc_url, &registry_id.to_string()).await?;
+
+    let security_assessment = format!(
+        "ML Assessment - F1: {:.2}%, Precision: {:.2}%, Recall: {:.2}%, Bias Score: {}",
+        quality_response.accuracy_metrics.f1_score as f64 / 10000.0,
+        quality_response.accuracy_metrics.precision as f64 / 10000.0,
+        quality_response.accuracy_metrics.recall as f64 / 10000.0,
+        quality_response.bias_assessment.fairness_score
+    );
+    let attestation_hash_bytes = hex::decode(assessment_hash).unwrap_or_else(|_| assessment_hash.as_bytes().to_vec());
+
+    let inputs = vec![
+        Input::Shared { object_id: pending_model_id, initial_shared_version: model_state.version, mutable: true },
+        Input::Shared { object_id: registry_id, initial_shared_version: registry_state.version, mutable: true },
+        Input::Pure {
+            value: bcs::to_bytes("nautilus-tee-v1")
+                .map_err(|e| EnclaveError::GenericError(format!("Failed to serialize enclave_id: {}", e)))?,
+        },
+        Input::Pure {
+            value: bcs::to_bytes(&quality_response.quality_score)
+                .map_err(|e| EnclaveError::GenericError(format!("Failed to serialize quality_score: {}", e)))?,
+        },
+        Input::Pure {
+            value: bcs::to_bytes(&security_assessment)
+                .map_err(|e| EnclaveError::GenericError(format!("Failed to serialize security_assessment: {}", e)))?,
+        },
+        Input::Pure {
+            value: bcs::to_bytes(&attestation_hash_bytes)
+                .map_err(|e| EnclaveError::GenericError(format!("Failed to serialize attestation_hash: {}", e)))?,
+        },
+        Input::Pure {
+            value: bcs::to_bytes(verification_signature.as_bytes())
+                .map_err(|e| EnclaveError::GenericError(format!("Failed to serialize verifier_signature: {}", e)))?,
+        },
+        Input::Shared {
+            object_id: ObjectID::from_str(SUI_CLOCK_OBJECT_ID)
+                .map_err(|e| EnclaveError::GenericError(format!("Invalid Sui Clock object ID: {}", e)))?,
+            initial_shared_version: SUI_CLOCK_INITIAL_SHARED_VERSION,
+            mutable: false,
+        },
+    ];
+
+    let command = Command::MoveCall(MoveCall {
+        package: package_id,
+        module: Identifier::new("satya_marketplace")
+            .map_err(|e| EnclaveError::GenericError(format!("Invalid module name: {}", e)))?,
+        function: Identifier::new("complete_verification")
+            .map_err(|e| EnclaveError::GenericError(format!("Invalid function name: {}", e)))?,
+        type_arguments: vec![],
+        arguments: (0..inputs.len() as u16).map(Argument::Input).collect(),
+    });
+
+    Ok(ProgrammableTransaction { inputs, commands: vec![command] })
+}
+
+/// Submit a fully-built, BCS-serialized transaction to `sui_executeTransactionBlock`
+/// and block until local execution finality, retrying with exponential backoff
+/// on transient RPC failures. Returns the real transaction digest.
+async fn submit_with_retry(rpc_url: &str, tx_bytes_b64: &str, signature_b64: &str) -> Result<String, EnclaveError> {
+    let client = reqwest::Client::new();
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "sui_executeTransactionBlock",
+        "params": [tx_bytes_b64, [signature_b64], { "showEffects": true }, "WaitForLocalExecution"]
+    });
+
+    let mut last_error = String::new();
+    let mut backoff = INITIAL_BACKOFF;
+    for attempt in 1..=MAX_SUBMIT_ATTEMPTS {
+        let outcome: Result<String, String> = async {
+            let response = client
+                .post(rpc_url)
+                .json(&body)
+                .timeout(Duration::from_secs(30))
+                .send()
+                .await
+                .map_err(|e| format!("RPC call failed: {}", e))?;
+
+            let parsed: serde_json::Value = response
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse sui_executeTransactionBlock response: {}", e))?;
+
+            if let Some(error) = parsed.get("error") {
+                return Err(format!("Sui RPC returned an error: {}", error));
+            }
+
+            parsed
+                .pointer("/result/digest")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+                .ok_or_else(|| "Response carried no transaction digest".to_string())
+        }
+        .await;
+
+        match outcome {
+            Ok(digest) => {
+                info!("Submitted on-chain verification transaction (attempt {}): {}", attempt, digest);
+                return Ok(digest);
+            }
+            Err(e) => {
+                info!("On-chain submission attempt {}/{} failed: {}", attempt, MAX_SUBMIT_ATTEMPTS, e);
+                last_error = e;
+                if attempt < MAX_SUBMIT_ATTEMPTS {
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+        }
+    }
+
+    Err(EnclaveError::GenericError(format!(
+        "OnchainSubmissionFailed: exhausted {} attempts, last RPC error: {}",
+        MAX_SUBMIT_ATTEMPTS, last_error
+    )))
+}
+
+/// Publish verification results to the Satya marketplace contract: resolve
+/// the pending-model/registry/gas object state, build and BCS-serialize a real
+/// `complete_verification` transaction, sign it with the enclave's managed
+/// TEE key, and submit it with retry/backoff.
+pub(crate) async fn publish_verification(
+    model_blob_id: &str,
+    quality_response: &MLQualityResponse,
+    assessment_hash: &str,
+    verification_signature: &str,
+    enclave_kp: &fastcrypto::ed25519::Ed25519KeyPair,
+) -> Result<String, EnclaveError> {
+    info!("Publishing verification results to blockchain for model: {}", model_blob_id);
+
+    let rpc_url = std::env::var("SUI_RPC_URL").unwrap_or_else(|_| "https://fullnode.testnet.sui.io".to_string());
+    let package_id_str = std::env::var("MARKETPLACE_PACKAGE_ID")
+        .map_err(|_| EnclaveError::GenericError("MARKETPLACE_PACKAGE_ID not set".to_string()))?;
+    let registry_id_str = std::env::var("MARKETPLACE_REGISTRY_ID")
+        .map_err(|_| EnclaveError::GenericError("MARKETPLACE_REGISTRY_ID not set".to_string()))?;
+    let pending_model_id_str = std::env::var("PENDING_MODEL_ID")
+        .map_err(|_| EnclaveError::GenericError("PENDING_MODEL_ID not set".to_string()))?;
+    let gas_object_id_str = std::env::var("SUI_GAS_OBJECT_ID")
+        .map_err(|_| EnclaveError::GenericError("SUI_GAS_OBJECT_ID not set".to_string()))?;
+    let gas_budget: u64 = std::env::var("SUI_GAS_BUDGET").ok().and_then(|v| v.parse().ok()).unwrap_or(50_000_000);
+
+    let package_id = ObjectID::from_str(&package_id_str)
+        .map_err(|e| EnclaveError::GenericError(format!("Invalid MARKETPLACE_PACKAGE_ID: {}", e)))?;
+    let registry_id = ObjectID::from_str(&registry_id_str)
+        .map_err(|e| EnclaveError::GenericError(format!("Invalid MARKETPLACE_REGISTRY_ID: {}", e)))?;
+    let pending_model_id = ObjectID::from_str(&pending_model_id_str)
+        .map_err(|e| EnclaveError::GenericError(format!("Invalid PENDING_MODEL_ID: {}", e)))?;
+    let gas_object_id = ObjectID::from_str(&gas_object_id_str)
+        .map_err(|e| EnclaveError::GenericError(format!("Invalid SUI_GAS_OBJECT_ID: {}", e)))?;
+
+    let ptb = build_complete_verification_ptb(
+        &rpc_url,
+        package_id,
+        registry_id,
+        pending_model_id,
+        quality_response,
+        assessment_hash,
+        verification_signature,
+    )
+    .await?;
+
+    let sui_private_key = {
+        use fastcrypto::traits::KeyPair;
+        let key_bytes: [u8; 32] = enclave_kp
+            .private()
+            .as_ref()
+            .try_into()
+            .map_err(|_| EnclaveError::GenericError("Invalid enclave key length".to_string()))?;
+        sui_crypto::ed25519::Ed25519PrivateKey::new(key_bytes)
+    };
+    let sender = {
+        use sui_crypto::SuiSigner;
+        sui_private_key.public_key().to_address()
+    };
+
+    let gas_state = resolve_object_state(&rpc_url, &gas_object_id.to_string()).await?;
+    let gas_digest = ObjectDigest::from_str(&gas_state.digest)
+        .map_err(|e| EnclaveError::GenericError(format!("Invalid gas object digest: {}", e)))?;
+
+    let transaction = Transaction {
+        kind: TransactionKind::ProgrammableTransaction(ptb),
+        sender,
+        gas_payment: GasPayment {
+            objects: vec![ObjectReference {
+                object_id: gas_object_id,
+                version: gas_state.version as Version,
+                digest: gas_digest,
+            }],
+            owner: sender,
+            price: 1000,
+            budget: gas_budget,
+        },
+        expiration: TransactionExpiration::None,
+    };
+
+    let signature = {
+        use sui_crypto::SuiSigner;
+        sui_private_key
+            .sign_transaction(&transaction)
+            .map_err(|e| EnclaveError::GenericError(format!("Failed to sign transaction: {}", e)))?
+    };
+
+    let tx_bytes = bcs::to_bytes(&transaction)
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to BCS-serialize transaction: {}", e)))?;
+    let tx_bytes_b64 = FcBase64::encode(&tx_bytes);
+    let signature_b64 = FcBase64::encode(signature.as_ref());
+
+    submit_with_retry(&rpc_url, &tx_bytes_b64, &signature_b64).await
+}
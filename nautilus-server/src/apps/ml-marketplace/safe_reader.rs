@@ -0,0 +1,179 @@
+// Copyright (c) Satya Data Marketplace
+// Bounded, rate-enforced, hash-verified ingestion for untrusted model/dataset blobs
+// SPDX-License-Identifier: Apache-2.0
+
+use sha2::{Digest, Sha256};
+use std::time::{Duration, Instant};
+
+use crate::EnclaveError;
+
+/// Bounds a `SafeReader` must enforce while streaming an untrusted blob.
+/// Modeled on TUF's untrusted-read guards: a hard length cap, a minimum
+/// throughput floor (to abort a stalled fetch inside the enclave's time
+/// budget rather than hang forever), and the hash the fully-consumed stream
+/// must match.
+#[derive(Debug, Clone)]
+pub struct SafeReaderConfig {
+    /// Hard cap on total bytes; exceeding it fails the transfer immediately.
+    pub max_length: usize,
+    /// Minimum sustained throughput, checked once per `bitrate_window`.
+    pub min_bytes_per_sec: u64,
+    /// How often the bitrate floor is checked.
+    pub bitrate_window: Duration,
+    /// Expected SHA-256 of the fully-assembled blob, when the caller already
+    /// knows it (e.g. a content-addressed blob ID). `None` skips the exact
+    /// match but `finish` still returns the computed digest for the caller
+    /// to check by whatever means it has.
+    pub expected_sha256: Option<[u8; 32]>,
+}
+
+impl SafeReaderConfig {
+    /// 30s bitrate window and a 512MB cap are generous enough for the model/
+    /// dataset sizes this marketplace deals with while still bounding an
+    /// enclave's worst case; `min_bytes_per_sec` of 1KB/s just catches a
+    /// fetch that has effectively stalled rather than one that's merely slow.
+    pub fn new(expected_sha256: Option<[u8; 32]>) -> Self {
+        Self {
+            max_length: 512 * 1_048_576,
+            min_bytes_per_sec: 1024,
+            bitrate_window: Duration::from_secs(30),
+            expected_sha256,
+        }
+    }
+}
+
+/// Streaming adapter that turns an untrusted byte source into a verified
+/// blob. The critical invariant: no caller may read any of the accumulated
+/// bytes until [`SafeReader::finish`] is called and succeeds — on any error
+/// (length exceeded, bitrate stalled, hash mismatch), the partially-built
+/// buffer is zeroed and discarded rather than handed back. Construct with
+/// [`SafeReaderConfig`], `feed` each chunk as it arrives off the wire, then
+/// `finish` once the source reaches EOF.
+pub struct SafeReader {
+    config: SafeReaderConfig,
+    hasher: Sha256,
+    buffer: Vec<u8>,
+    window_start: Instant,
+    window_bytes: u64,
+}
+
+impl SafeReader {
+    pub fn new(config: SafeReaderConfig) -> Self {
+        Self {
+            config,
+            hasher: Sha256::new(),
+            buffer: Vec::new(),
+            window_start: Instant::now(),
+            window_bytes: 0,
+        }
+    }
+
+    /// Feed the next chunk of raw wire bytes. Checks the length cap before
+    /// accepting the chunk and the bitrate floor after it; on failure the
+    /// buffer is zeroed and `self` must be discarded.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<(), EnclaveError> {
+        if self.buffer.len() + chunk.len() > self.config.max_length {
+            self.discard();
+            return Err(EnclaveError::GenericError(format!(
+                "blob exceeded max_length of {} bytes",
+                self.config.max_length
+            )));
+        }
+
+        self.hasher.update(chunk);
+        self.buffer.extend_from_slice(chunk);
+        self.window_bytes += chunk.len() as u64;
+
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= self.config.bitrate_window {
+            let observed_rate = (self.window_bytes as f64 / elapsed.as_secs_f64()) as u64;
+            if observed_rate < self.config.min_bytes_per_sec {
+                self.discard();
+                return Err(EnclaveError::GenericError(format!(
+                    "blob transfer stalled: {} bytes/sec over the last {:?}, below the {} bytes/sec floor",
+                    observed_rate, elapsed, self.config.min_bytes_per_sec
+                )));
+            }
+            self.window_start = Instant::now();
+            self.window_bytes = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Consume the reader once the source has reached EOF, verifying the
+    /// accumulated bytes against `expected_sha256` when one was configured.
+    /// Returns the verified bytes and their digest on success; on a hash
+    /// mismatch the buffer is zeroed and an error returned instead of the
+    /// (untrusted) bytes.
+    pub fn finish(mut self) -> Result<(Vec<u8>, [u8; 32]), EnclaveError> {
+        let digest: [u8; 32] = self.hasher.finalize_reset().into();
+        if let Some(expected) = self.config.expected_sha256 {
+            if digest != expected {
+                self.discard();
+                return Err(EnclaveError::GenericError(format!(
+                    "blob hash mismatch: expected {}, got {}",
+                    hex::encode(expected),
+                    hex::encode(digest)
+                )));
+            }
+        }
+        Ok((std::mem::take(&mut self.buffer), digest))
+    }
+
+    /// Overwrite and drop the partially-assembled buffer; called on every
+    /// error path so a caller can never observe bytes that failed
+    /// verification, even via a dangling reference or a later panic.
+    fn discard(&mut self) {
+        for byte in self.buffer.iter_mut() {
+            *byte = 0;
+        }
+        self.buffer.clear();
+        self.buffer.shrink_to_fit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sha256(data: &[u8]) -> [u8; 32] {
+        Sha256::digest(data).into()
+    }
+
+    #[test]
+    fn accepts_a_blob_matching_length_and_hash() {
+        let data = b"hello safe reader".to_vec();
+        let mut reader = SafeReader::new(SafeReaderConfig::new(Some(sha256(&data))));
+        reader.feed(&data).unwrap();
+        let (verified, _digest) = reader.finish().unwrap();
+        assert_eq!(verified, data);
+    }
+
+    #[test]
+    fn rejects_a_blob_exceeding_max_length() {
+        let data = vec![0u8; 16];
+        let mut config = SafeReaderConfig::new(Some(sha256(&data)));
+        config.max_length = 8;
+        let mut reader = SafeReader::new(config);
+        assert!(reader.feed(&data).is_err());
+    }
+
+    #[test]
+    fn rejects_a_blob_with_the_wrong_hash() {
+        let data = b"hello safe reader".to_vec();
+        let mut reader = SafeReader::new(SafeReaderConfig::new(Some([0u8; 32])));
+        reader.feed(&data).unwrap();
+        assert!(reader.finish().is_err());
+    }
+
+    #[test]
+    fn returns_the_digest_when_no_hash_is_expected() {
+        let data = b"hello safe reader".to_vec();
+        let mut reader = SafeReader::new(SafeReaderConfig::new(None));
+        reader.feed(&data).unwrap();
+        let (verified, digest) = reader.finish().unwrap();
+        assert_eq!(verified, data);
+        assert_eq!(digest, sha256(&data));
+    }
+}
@@ -1,108 +1,322 @@
-// SEAL Decryption Fix - Proper Implementation
-// This shows what needs to be implemented for real SEAL decryption
+// Real SEAL decryption: blob-envelope parsing, session-cert caching, a
+// seal_approve on-chain ACL dry-run, threshold (t-of-n) key-server fetching
+// with per-response authenticity verification, and a WrappedSecret export
+// layer for handing decrypted plaintext to a caller outside the enclave.
+// `attempt_real_seal_decryption` is generic over which key-server network to
+// use (see `SealConfigML`); `mod.rs` wires it up against the Mysten testnet
+// key server.
 
 use crate::EnclaveError;
+use super::seal_impl::SealConfigML;
 use fastcrypto::encoding::{Base64, Encoding, Hex};
 use fastcrypto::ed25519::Ed25519KeyPair;
 use fastcrypto::traits::{KeyPair, Signer};
 use seal_sdk::{
-    EncryptedObject, signed_message, signed_request, Certificate, 
+    EncryptedObject, IBEPublicKey, signed_message, signed_request, Certificate,
     types::{FetchKeyRequest, FetchKeyResponse, KeyId}
 };
 use sui_sdk_types::{
     Argument, Command, Identifier, Input, MoveCall, ObjectId as ObjectID, 
     PersonalMessage, ProgrammableTransaction
 };
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
 use tracing::info;
+use rand::seq::SliceRandom;
 use rand::thread_rng;
+use futures::stream::{FuturesUnordered, StreamExt};
+use lazy_static;
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use sha2::{Digest, Sha256};
+use x25519_dalek::{EphemeralSecret, PublicKey as X25519PublicKey, StaticSecret};
 
-/// REAL SEAL decryption - what needs to be implemented
+/// REAL SEAL decryption against an arbitrary key-server network, described
+/// by `config` (e.g. `seal_impl::ML_SEAL_CONFIG` for H2O Nodes, or a
+/// Mysten-testnet `SealConfigML` built by the caller). The on-chain package a
+/// blob's access policy lives in is read from the blob's own
+/// `EncryptedObject`s rather than `config`, since any package may gate access
+/// through whichever key-server network `config` names.
 pub async fn attempt_real_seal_decryption(
     data: &[u8],
     enclave_kp: &Ed25519KeyPair,
+    config: &SealConfigML,
 ) -> Result<Vec<u8>, EnclaveError> {
     info!("🔐 Starting REAL SEAL decryption process");
 
-    // Step 1: Parse the blob to extract EncryptedObjects and KeyIDs
-    let encrypted_objects = parse_encrypted_objects_from_blob(data)?;
-    let key_ids = extract_key_ids_from_objects(&encrypted_objects)?;
-    
-    info!("📦 Found {} encrypted objects with {} key IDs", 
+    // Step 1: Parse the blob to extract EncryptedObjects and KeyIDs. A
+    // self-describing envelope carries its own KeyIDs; a legacy raw-BCS
+    // blob doesn't, so fall back to reverse-engineering them.
+    let parsed = parse_encrypted_objects_from_blob(data)?;
+    let key_ids = match parsed.key_ids {
+        Some(ids) => ids,
+        None => extract_key_ids_from_objects(&parsed.objects)?,
+    };
+    let encrypted_objects = parsed.objects;
+
+    info!("📦 Found {} encrypted objects with {} key IDs",
           encrypted_objects.len(), key_ids.len());
 
-    // Step 2: Generate session key and create certificate (like seal-example)
-    let (session_key, certificate) = create_session_and_certificate(enclave_kp)?;
-    
+    let package_id = encrypted_objects
+        .first()
+        .ok_or_else(|| EnclaveError::GenericError("Blob contained no EncryptedObjects".to_string()))?
+        .package_id;
+
+    // Step 2: Generate (or reuse a still-valid cached) session key and
+    // certificate, scoped to the SEAL package we're fetching keys from.
+    let ttl_min = std::env::var("SEAL_SESSION_TTL_MIN")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_SESSION_TTL_MIN);
+    let (session_key, certificate) =
+        create_session_and_certificate(enclave_kp, &package_id.to_string(), ttl_min).await?;
+
     // Step 3: Create ProgrammableTransaction calling seal_approve
-    let ptb = create_seal_approve_transaction(&key_ids).await?;
-    
-    // Step 4: Create proper FetchKeyRequest 
+    let ptb = create_seal_approve_transaction(&key_ids, enclave_kp, package_id).await?;
+
+    // Step 4: Create proper FetchKeyRequest
     let fetch_request = create_fetch_key_request(
         &ptb,
         &session_key,
         certificate
     )?;
-    
+
     // Step 5: Send request to SEAL key servers
-    let seal_responses = fetch_from_seal_servers(&fetch_request).await?;
-    
+    let seal_responses = fetch_from_seal_servers(&fetch_request, config).await?;
+
     // Step 6: Use real SEAL SDK decryption
     let decrypted_data = decrypt_with_seal_sdk(
         &encrypted_objects,
-        &seal_responses
+        &seal_responses,
+        config,
     ).await?;
-    
+
     info!("✅ SEAL decryption successful: {} bytes", decrypted_data.len());
     Ok(decrypted_data)
 }
 
-/// Step 1: Parse EncryptedObjects from blob (MISSING in current implementation)
-fn parse_encrypted_objects_from_blob(data: &[u8]) -> Result<Vec<EncryptedObject>, EnclaveError> {
-    // Try parsing as BCS-encoded EncryptedObject(s)
+/// Magic prefix identifying a self-describing Satya blob envelope, as
+/// opposed to a legacy raw-BCS blob.
+const BLOB_ENVELOPE_MAGIC: &[u8; 6] = b"SATYA1";
+/// Envelope format version. Bump this (and the magic, if the header layout
+/// changes incompatibly) rather than overloading the existing version.
+const BLOB_ENVELOPE_VERSION: u8 = 1;
+
+/// One embedded `EncryptedObject`'s location and associated `KeyId` within
+/// a `BlobEnvelopeHeader`'s body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BlobEnvelopeEntry {
+    key_id: KeyId,
+    /// Byte offset into the envelope body (i.e. after the header) where
+    /// this object's BCS encoding starts.
+    offset: u64,
+    /// Length in bytes of this object's BCS encoding.
+    length: u64,
+    /// Free-form hint for what the decrypted payload is (e.g.
+    /// `"model/onnx"`, `"dataset/csv"`); not interpreted by the envelope
+    /// format itself.
+    content_type: Option<String>,
+}
+
+/// BCS-encoded metadata header of a Satya blob envelope: one entry per
+/// embedded `EncryptedObject`, in the order they appear in the body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BlobEnvelopeHeader {
+    entries: Vec<BlobEnvelopeEntry>,
+}
+
+/// Result of parsing a blob: the `EncryptedObject`s it contained, plus the
+/// `KeyId`s the envelope recorded for them (`None` for a legacy raw-BCS
+/// blob, which carries no such header and must fall back to
+/// `extract_key_ids_from_objects`).
+struct ParsedBlob {
+    objects: Vec<EncryptedObject>,
+    key_ids: Option<Vec<KeyId>>,
+}
+
+/// Build a self-describing Satya blob envelope: `SATYA1` magic, a one-byte
+/// version, a 4-byte big-endian header length, the BCS-encoded
+/// `BlobEnvelopeHeader`, then the concatenated BCS encodings of each
+/// `EncryptedObject` at the offsets the header records. This is the writer
+/// counterpart to `parse_encrypted_objects_from_blob`: the encryption tool
+/// and the enclave must agree on this format for new blobs.
+fn write_blob_envelope(objects: &[(KeyId, EncryptedObject, Option<String>)]) -> Result<Vec<u8>, EnclaveError> {
+    let mut body = Vec::new();
+    let mut entries = Vec::with_capacity(objects.len());
+    for (key_id, object, content_type) in objects {
+        let object_bytes = bcs::to_bytes(object)
+            .map_err(|e| EnclaveError::GenericError(format!("Failed to BCS-encode EncryptedObject: {}", e)))?;
+        entries.push(BlobEnvelopeEntry {
+            key_id: key_id.clone(),
+            offset: body.len() as u64,
+            length: object_bytes.len() as u64,
+            content_type: content_type.clone(),
+        });
+        body.extend_from_slice(&object_bytes);
+    }
+
+    let header_bytes = bcs::to_bytes(&BlobEnvelopeHeader { entries })
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to BCS-encode envelope header: {}", e)))?;
+
+    let mut out = Vec::with_capacity(BLOB_ENVELOPE_MAGIC.len() + 1 + 4 + header_bytes.len() + body.len());
+    out.extend_from_slice(BLOB_ENVELOPE_MAGIC);
+    out.push(BLOB_ENVELOPE_VERSION);
+    out.extend_from_slice(&(header_bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(&header_bytes);
+    out.extend_from_slice(&body);
+    Ok(out)
+}
+
+/// Try to parse `data` as a self-describing Satya blob envelope, returning
+/// `None` (not an error) when the magic prefix doesn't match so the caller
+/// can fall back to the legacy raw-BCS formats.
+fn try_parse_blob_envelope(data: &[u8]) -> Result<Option<ParsedBlob>, EnclaveError> {
+    if !data.starts_with(BLOB_ENVELOPE_MAGIC) {
+        return Ok(None);
+    }
+    let rest = &data[BLOB_ENVELOPE_MAGIC.len()..];
+
+    let (&version, rest) = rest
+        .split_first()
+        .ok_or_else(|| EnclaveError::GenericError("Blob envelope truncated before version byte".to_string()))?;
+    if version != BLOB_ENVELOPE_VERSION {
+        return Err(EnclaveError::GenericError(format!(
+            "Unsupported blob envelope version {} (expected {})",
+            version, BLOB_ENVELOPE_VERSION
+        )));
+    }
+
+    if rest.len() < 4 {
+        return Err(EnclaveError::GenericError("Blob envelope truncated before header length".to_string()));
+    }
+    let header_len = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+    let rest = &rest[4..];
+
+    if rest.len() < header_len {
+        return Err(EnclaveError::GenericError("Blob envelope truncated before end of header".to_string()));
+    }
+    let (header_bytes, body) = rest.split_at(header_len);
+    let header: BlobEnvelopeHeader = bcs::from_bytes(header_bytes)
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to parse blob envelope header: {}", e)))?;
+
+    let mut objects = Vec::with_capacity(header.entries.len());
+    let mut key_ids = Vec::with_capacity(header.entries.len());
+    for entry in &header.entries {
+        let start = entry.offset as usize;
+        let end = start
+            .checked_add(entry.length as usize)
+            .ok_or_else(|| EnclaveError::GenericError("Blob envelope entry length overflow".to_string()))?;
+        let slice = body.get(start..end).ok_or_else(|| {
+            EnclaveError::GenericError("Blob envelope entry offset/length out of bounds".to_string())
+        })?;
+        let object: EncryptedObject = bcs::from_bytes(slice)
+            .map_err(|e| EnclaveError::GenericError(format!("Failed to parse embedded EncryptedObject: {}", e)))?;
+        objects.push(object);
+        key_ids.push(entry.key_id.clone());
+    }
+
+    Ok(Some(ParsedBlob { objects, key_ids: Some(key_ids) }))
+}
+
+/// Step 1: Parse EncryptedObjects (and, where available, their KeyIDs) from
+/// a blob. Self-describing `SATYA1` envelopes (see `try_parse_blob_envelope`)
+/// are tried first; legacy blobs that predate the envelope format fall back
+/// to raw BCS decoding of a single object or a vector of objects, with no
+/// recorded KeyIDs.
+fn parse_encrypted_objects_from_blob(data: &[u8]) -> Result<ParsedBlob, EnclaveError> {
+    if let Some(parsed) = try_parse_blob_envelope(data)? {
+        return Ok(parsed);
+    }
+
+    // Legacy raw-BCS fallback: no envelope header, so no recorded KeyIDs.
     if let Ok(single_obj) = bcs::from_bytes::<EncryptedObject>(data) {
-        return Ok(vec![single_obj]);
+        return Ok(ParsedBlob { objects: vec![single_obj], key_ids: None });
     }
-    
+
     if let Ok(multiple_objs) = bcs::from_bytes::<Vec<EncryptedObject>>(data) {
-        return Ok(multiple_objs);
+        return Ok(ParsedBlob { objects: multiple_objs, key_ids: None });
     }
-    
-    // For real Walrus blobs, we need to:
-    // 1. Understand the actual blob format used by the encryption tool
-    // 2. Parse metadata to find where EncryptedObjects are stored
-    // 3. Extract them properly
-    
+
     Err(EnclaveError::GenericError(
-        "❌ ISSUE: Cannot parse EncryptedObjects from blob. Need to understand the actual blob format from the encryption tool.".to_string()
+        "Cannot parse EncryptedObjects from blob: not a SATYA1 envelope and not raw-BCS EncryptedObject(s)".to_string()
     ))
 }
 
-/// Step 2: Create session and certificate (MISSING proper implementation)
-fn create_session_and_certificate(
-    enclave_kp: &Ed25519KeyPair
-) -> Result<(Ed25519KeyPair, Certificate), EnclaveError> {
-    // Generate session key
-    let session = Ed25519KeyPair::generate(&mut thread_rng());
-    let session_vk = session.public();
-    
-    // Get current time
-    let creation_time = SystemTime::now()
+/// Default session TTL in minutes when `SEAL_SESSION_TTL_MIN` isn't set.
+const DEFAULT_SESSION_TTL_MIN: u64 = 10;
+/// A cached session is only reused if it still has at least this much time
+/// left before it expires, so a request never starts with a certificate
+/// that could lapse mid-flight.
+const SESSION_TTL_SAFETY_MARGIN_SECS: u64 = 30;
+
+/// A cached session key/certificate pair, along with the bookkeeping needed
+/// to tell whether it's still safely within its TTL.
+#[derive(Clone)]
+struct CachedSession {
+    session: Arc<Ed25519KeyPair>,
+    certificate: Certificate,
+    creation_time_ms: u64,
+    ttl_min: u64,
+}
+
+impl CachedSession {
+    fn still_valid_at(&self, now_ms: u64) -> bool {
+        let expires_at_ms = self.creation_time_ms + self.ttl_min * 60_000;
+        now_ms + SESSION_TTL_SAFETY_MARGIN_SECS * 1000 < expires_at_ms
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Sessions in flight, keyed by `package_id`, so concurrent decryptions
+    /// against the same package reuse one session instead of each minting
+    /// (and paying the signing latency for) their own.
+    static ref SESSION_CACHE: tokio::sync::Mutex<HashMap<String, CachedSession>> =
+        tokio::sync::Mutex::new(HashMap::new());
+}
+
+/// Step 2: Create a session key and certificate for `package_id`, reusing a
+/// cached one that has more than `SESSION_TTL_SAFETY_MARGIN_SECS` left
+/// before expiry instead of minting a fresh `Ed25519KeyPair` and re-signing a
+/// personal message on every call, the same way short-lived TLS/session
+/// credentials are reused within their validity window.
+async fn create_session_and_certificate(
+    enclave_kp: &Ed25519KeyPair,
+    package_id: &str,
+    ttl_min: u64,
+) -> Result<(Arc<Ed25519KeyPair>, Certificate), EnclaveError> {
+    let now_ms = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map_err(|e| EnclaveError::GenericError(format!("Time error: {}", e)))?
         .as_millis() as u64;
-    let ttl_min = 10;
-    
-    // Create signed message (need package_id)
-    let package_id = "0x82dc1ccc20ec94e7966299aa4398d9fe0333ab5c138dee5f81924b7b59ec48d8";
+
+    {
+        let mut cache = SESSION_CACHE.lock().await;
+        match cache.get(package_id) {
+            Some(cached) if cached.still_valid_at(now_ms) => {
+                return Ok((cached.session.clone(), cached.certificate.clone()));
+            }
+            Some(_) => {
+                cache.remove(package_id);
+            }
+            None => {}
+        }
+    }
+
+    // Generate session key
+    let session = Ed25519KeyPair::generate(&mut thread_rng());
+    let session_vk = session.public();
+    let creation_time_ms = now_ms;
+
     let message = signed_message(
         package_id.to_string(),
         session_vk,
-        creation_time,
+        creation_time_ms,
         ttl_min,
     );
-    
+
     // Convert to sui-crypto key for signing
     let sui_private_key = {
         let priv_key_bytes = enclave_kp.as_ref();
@@ -111,7 +325,7 @@ fn create_session_and_certificate(
             .map_err(|_| EnclaveError::GenericError("Invalid private key length".to_string()))?;
         sui_crypto::ed25519::Ed25519PrivateKey::new(key_bytes)
     };
-    
+
     // Sign personal message
     let signature = {
         use sui_crypto::SuiSigner;
@@ -119,38 +333,155 @@ fn create_session_and_certificate(
             .sign_personal_message(&PersonalMessage(message.as_bytes().into()))
             .map_err(|e| EnclaveError::GenericError(format!("Failed to sign personal message: {}", e)))?
     };
-    
+
     // Create certificate
     let certificate = Certificate {
         user: sui_private_key.public_key().to_address(),
         session_vk: session_vk.clone(),
-        creation_time,
+        creation_time: creation_time_ms,
         ttl_min,
         signature,
         mvr_name: None,
     };
-    
+
+    let session = Arc::new(session);
+    SESSION_CACHE.lock().await.insert(
+        package_id.to_string(),
+        CachedSession { session: session.clone(), certificate: certificate.clone(), creation_time_ms, ttl_min },
+    );
+
     Ok((session, certificate))
 }
 
-/// Step 3: Create ProgrammableTransaction (MISSING in current implementation)
+/// Default Move module implementing the on-chain `seal_approve` access
+/// policy, overridable via `SEAL_POLICY_MODULE` for deployments that name it
+/// differently.
+const DEFAULT_SEAL_POLICY_MODULE: &str = "seal_policy";
+
+/// Step 3: Build the real `seal_approve` ProgrammableTransaction — one
+/// `MoveCall` per `KeyId`, each checked against the shared enclave object
+/// that the on-chain policy gates access on — then dry-run it via
+/// `sui_devInspectTransactionBlock` so a caller that isn't actually entitled
+/// to these keys is rejected with `AccessDenied` before we ever contact a
+/// SEAL key server.
 async fn create_seal_approve_transaction(
-    key_ids: &[KeyId]
+    key_ids: &[KeyId],
+    enclave_kp: &Ed25519KeyPair,
+    package_id: ObjectID,
 ) -> Result<ProgrammableTransaction, EnclaveError> {
-    // ❌ ISSUE: We need these parameters but don't have them:
-    // - enclave_object_id: ObjectID of the enclave shared object
-    // - initial_shared_version: Version of the shared object
-    // - package_id: The SEAL package ID
-    
-    info!("❌ MISSING: Need enclave_object_id and initial_shared_version to create PTB");
-    
-    // For demo, return error explaining what's needed
-    Err(EnclaveError::GenericError(
-        "❌ ISSUE: Cannot create ProgrammableTransaction without:\n\
-        1. enclave_object_id (shared object ID)\n\
-        2. initial_shared_version\n\
-        3. Access control validation on SUI network".to_string()
-    ))
+    let rpc_url = std::env::var("SUI_RPC_URL").unwrap_or_else(|_| "https://fullnode.testnet.sui.io".to_string());
+    let enclave_object_id_str = std::env::var("SEAL_ENCLAVE_OBJECT_ID")
+        .map_err(|_| EnclaveError::GenericError("SEAL_ENCLAVE_OBJECT_ID not set".to_string()))?;
+    let enclave_object_id = ObjectID::from_str(&enclave_object_id_str)
+        .map_err(|e| EnclaveError::GenericError(format!("Invalid SEAL_ENCLAVE_OBJECT_ID: {}", e)))?;
+    let policy_module_name = std::env::var("SEAL_POLICY_MODULE").unwrap_or_else(|_| DEFAULT_SEAL_POLICY_MODULE.to_string());
+
+    let enclave_state = super::onchain::resolve_object_state(&rpc_url, &enclave_object_id.to_string()).await?;
+
+    let mut inputs = Vec::with_capacity(key_ids.len() + 1);
+    for key_id in key_ids {
+        inputs.push(Input::Pure {
+            value: bcs::to_bytes(key_id)
+                .map_err(|e| EnclaveError::GenericError(format!("Failed to serialize KeyID: {}", e)))?,
+        });
+    }
+    let enclave_input_idx = inputs.len();
+    inputs.push(Input::Shared {
+        object_id: enclave_object_id,
+        initial_shared_version: enclave_state.version,
+        mutable: false,
+    });
+
+    let policy_module = Identifier::new(policy_module_name.as_str())
+        .map_err(|e| EnclaveError::GenericError(format!("Invalid module name: {}", e)))?;
+    let mut commands = Vec::with_capacity(key_ids.len());
+    for idx in 0..key_ids.len() {
+        commands.push(Command::MoveCall(MoveCall {
+            package: package_id,
+            module: policy_module.clone(),
+            function: Identifier::new("seal_approve")
+                .map_err(|e| EnclaveError::GenericError(format!("Invalid function name: {}", e)))?,
+            type_arguments: vec![],
+            arguments: vec![Argument::Input(idx as u16), Argument::Input(enclave_input_idx as u16)],
+        }));
+    }
+
+    let ptb = ProgrammableTransaction { inputs, commands };
+
+    let sender = {
+        use fastcrypto::traits::KeyPair;
+        let key_bytes: [u8; 32] = enclave_kp
+            .private()
+            .as_ref()
+            .try_into()
+            .map_err(|_| EnclaveError::GenericError("Invalid enclave key length".to_string()))?;
+        let sui_private_key = sui_crypto::ed25519::Ed25519PrivateKey::new(key_bytes);
+        use sui_crypto::SuiSigner;
+        sui_private_key.public_key().to_address().to_string()
+    };
+
+    validate_seal_approve_acl(&rpc_url, &sender, &ptb).await?;
+
+    Ok(ptb)
+}
+
+/// Dry-run the `seal_approve` PTB via `sui_devInspectTransactionBlock` so an
+/// on-chain ACL rejection surfaces as a distinct `AccessDenied` error rather
+/// than being discovered only after we've already fetched (and paid the
+/// latency for) key-server responses.
+async fn validate_seal_approve_acl(
+    rpc_url: &str,
+    sender: &str,
+    ptb: &ProgrammableTransaction,
+) -> Result<(), EnclaveError> {
+    let tx_kind_bytes = bcs::to_bytes(&sui_sdk_types::TransactionKind::ProgrammableTransaction(ptb.clone()))
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to serialize seal_approve TransactionKind: {}", e)))?;
+    let tx_kind_b64 = Base64::encode(&tx_kind_bytes);
+
+    let client = reqwest::Client::new();
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "sui_devInspectTransactionBlock",
+        "params": [sender, tx_kind_b64]
+    });
+
+    let response = client
+        .post(rpc_url)
+        .json(&body)
+        .timeout(Duration::from_secs(15))
+        .send()
+        .await
+        .map_err(|e| EnclaveError::GenericError(format!("sui_devInspectTransactionBlock RPC call failed: {}", e)))?;
+
+    let parsed: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to parse dev-inspect response: {}", e)))?;
+
+    if let Some(error) = parsed.get("error") {
+        return Err(EnclaveError::GenericError(format!(
+            "AccessDenied: seal_approve dry-run RPC error: {}",
+            error
+        )));
+    }
+
+    let status = parsed
+        .pointer("/result/effects/status/status")
+        .and_then(|v| v.as_str())
+        .unwrap_or("failure");
+    if status != "success" {
+        let error_detail = parsed
+            .pointer("/result/effects/status/error")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown error");
+        return Err(EnclaveError::GenericError(format!(
+            "AccessDenied: on-chain seal_approve policy check rejected this request: {}",
+            error_detail
+        )));
+    }
+
+    Ok(())
 }
 
 /// Step 4: Create FetchKeyRequest (partially implemented)
@@ -177,34 +508,213 @@ fn create_fetch_key_request(
     Ok(request)
 }
 
-/// Step 5: Fetch from SEAL servers (needs proper API calls)
+/// Per-server HTTP timeout for a single `FetchKeyRequest` attempt.
+const SEAL_SERVER_TIMEOUT: Duration = Duration::from_secs(10);
+/// Retries attempted against a single key server before giving up on it and
+/// moving on (the other servers are still racing concurrently).
+const SEAL_SERVER_MAX_RETRIES: u32 = 2;
+const SEAL_SERVER_RETRY_BACKOFF: Duration = Duration::from_millis(300);
+
+/// Step 5: Fetch from SEAL key servers with threshold (t-of-n) fetching.
+///
+/// Fires a `FetchKeyRequest` at every server in `config.server_urls`
+/// concurrently (servers are shuffled first so repeated calls don't always
+/// hammer the same subset first) and returns as soon as
+/// `config.threshold` of them have answered successfully, dropping
+/// the rest of the in-flight requests rather than waiting for every server.
 async fn fetch_from_seal_servers(
-    request: &FetchKeyRequest
+    request: &FetchKeyRequest,
+    config: &SealConfigML,
 ) -> Result<Vec<(ObjectID, FetchKeyResponse)>, EnclaveError> {
-    // ❌ ISSUE: Need to make proper HTTP requests to SEAL key servers
-    // with the FetchKeyRequest payload
-    
-    info!("❌ MISSING: Need proper HTTP client to call SEAL key server APIs");
-    
-    Err(EnclaveError::GenericError(
-        "❌ ISSUE: Need to implement HTTP calls to SEAL key servers with proper authentication".to_string()
-    ))
+    let threshold = config.threshold.max(1);
+
+    let mut servers: Vec<(ObjectID, String)> =
+        config.server_urls.iter().map(|(id, url)| (*id, url.clone())).collect();
+    servers.shuffle(&mut thread_rng());
+
+    if servers.len() < threshold {
+        return Err(EnclaveError::GenericError(format!(
+            "ThresholdNotMet: got 0 of needed {} key-server responses (only {} servers configured)",
+            threshold,
+            servers.len()
+        )));
+    }
+
+    let request_bytes = bcs::to_bytes(request)
+        .map_err(|e| EnclaveError::GenericError(format!("Failed to serialize FetchKeyRequest: {}", e)))?;
+
+    let client = reqwest::Client::new();
+    let mut pending: FuturesUnordered<_> = servers
+        .into_iter()
+        .map(|(object_id, url)| {
+            let client = client.clone();
+            let body = request_bytes.clone();
+            async move { (object_id, fetch_one_key_server(&client, &url, body).await) }
+        })
+        .collect();
+
+    let mut responses = Vec::new();
+    let mut last_error = String::new();
+    while let Some((object_id, outcome)) = pending.next().await {
+        match outcome {
+            Ok(response) => {
+                responses.push((object_id, response));
+                if responses.len() >= threshold {
+                    info!(
+                        "Threshold of {} key-server response(s) reached; cancelling {} still in-flight",
+                        threshold,
+                        pending.len()
+                    );
+                    break;
+                }
+            }
+            Err(e) => {
+                info!("Key server {} failed: {}", object_id, e);
+                last_error = e;
+            }
+        }
+    }
+    // Dropping `pending` here cancels any requests still in flight.
+
+    if responses.len() < threshold {
+        return Err(EnclaveError::GenericError(format!(
+            "ThresholdNotMet: got {} of needed {} key-server responses, last error: {}",
+            responses.len(),
+            threshold,
+            last_error
+        )));
+    }
+
+    Ok(responses)
+}
+
+/// POST one `FetchKeyRequest` to a single key server, retrying with backoff
+/// up to `SEAL_SERVER_MAX_RETRIES` times before surfacing the last error.
+async fn fetch_one_key_server(
+    client: &reqwest::Client,
+    url: &str,
+    request_bytes: Vec<u8>,
+) -> Result<FetchKeyResponse, String> {
+    let mut last_error = String::new();
+    let mut backoff = SEAL_SERVER_RETRY_BACKOFF;
+
+    for attempt in 1..=1 + SEAL_SERVER_MAX_RETRIES {
+        let outcome: Result<FetchKeyResponse, String> = async {
+            let response = client
+                .post(url)
+                .header("Content-Type", "application/octet-stream")
+                .body(request_bytes.clone())
+                .timeout(SEAL_SERVER_TIMEOUT)
+                .send()
+                .await
+                .map_err(|e| format!("request to {} failed: {}", url, e))?;
+
+            if !response.status().is_success() {
+                return Err(format!("{} returned status {}", url, response.status()));
+            }
+
+            let body = response
+                .bytes()
+                .await
+                .map_err(|e| format!("failed to read response body from {}: {}", url, e))?;
+
+            bcs::from_bytes::<FetchKeyResponse>(&body)
+                .map_err(|e| format!("failed to parse response from {}: {}", url, e))
+        }
+        .await;
+
+        match outcome {
+            Ok(response) => return Ok(response),
+            Err(e) => {
+                last_error = e;
+                if attempt <= SEAL_SERVER_MAX_RETRIES {
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+            }
+        }
+    }
+
+    Err(last_error)
+}
+
+/// Pins each key server's registered IBE public key so a response claiming
+/// to come from a given server can be checked against the key we actually
+/// expect it to hold, rather than trusting whatever `fetch_from_seal_servers`
+/// handed back. Seeded from `config.server_pk_map`; a server that
+/// isn't in the map isn't one we've agreed to trust, full stop.
+struct Keyring {
+    keys: HashMap<ObjectID, IBEPublicKey>,
 }
 
-/// Step 6: Use real SEAL SDK decryption
+impl Keyring {
+    fn from_config(config: &SealConfigML) -> Self {
+        Keyring { keys: config.server_pk_map.clone() }
+    }
+
+    /// Verify that `response` is attributable to `object_id` under this
+    /// keyring's pinned public key. The SEAL SDK doesn't expose a
+    /// lower-level "check this one share" primitive, so verification is
+    /// done the same way the SDK itself would notice a bad share: run
+    /// `seal_decrypt_all_objects` scoped to just this one server and its
+    /// pinned key. A forged or mismatched response fails the combine step
+    /// and is reported as `VerificationFailed` instead of silently reaching
+    /// the real multi-server decrypt below.
+    fn verify(
+        &self,
+        object_id: &ObjectID,
+        response: &FetchKeyResponse,
+        encrypted_objects: &[EncryptedObject],
+    ) -> Result<(), EnclaveError> {
+        let public_key = self.keys.get(object_id).ok_or_else(|| {
+            EnclaveError::GenericError(format!(
+                "KeyNotFound: key server {} is not in the pinned keyring",
+                object_id
+            ))
+        })?;
+
+        let mut single_server_pk_map = HashMap::new();
+        single_server_pk_map.insert(*object_id, public_key.clone());
+
+        let (enc_secret, _enc_key, _enc_verification_key) = &*super::seal_impl::ML_ENCRYPTION_KEYS;
+        seal_sdk::seal_decrypt_all_objects(
+            enc_secret,
+            std::slice::from_ref(&(*object_id, response.clone())),
+            encrypted_objects,
+            &single_server_pk_map,
+        )
+        .map(|_| ())
+        .map_err(|e| {
+            EnclaveError::GenericError(format!(
+                "VerificationFailed: key server {}'s response did not verify against its pinned public key: {}",
+                object_id, e
+            ))
+        })
+    }
+}
+
+/// Step 6: Use real SEAL SDK decryption, after verifying every response
+/// against the pinned `Keyring` so a malicious or MITM'd key server can't
+/// inject a bogus share.
 async fn decrypt_with_seal_sdk(
     encrypted_objects: &[EncryptedObject],
-    seal_responses: &[(ObjectID, FetchKeyResponse)]
+    seal_responses: &[(ObjectID, FetchKeyResponse)],
+    config: &SealConfigML,
 ) -> Result<Vec<u8>, EnclaveError> {
+    let keyring = Keyring::from_config(config);
+    for (object_id, response) in seal_responses {
+        keyring.verify(object_id, response, encrypted_objects)?;
+    }
+
     // THIS is the part that should work once we have proper responses
     let (enc_secret, _enc_key, _enc_verification_key) = &*super::seal_impl::ML_ENCRYPTION_KEYS;
-    
+
     // Use the official SEAL SDK function
     match seal_sdk::seal_decrypt_all_objects(
         enc_secret,
         seal_responses,
         encrypted_objects,
-        &super::seal_impl::ML_SEAL_CONFIG.server_pk_map,
+        &config.server_pk_map,
     ) {
         Ok(decrypted_results) => {
             if let Some(first_result) = decrypted_results.first() {
@@ -219,24 +729,166 @@ async fn decrypt_with_seal_sdk(
     }
 }
 
-/// Extract KeyIDs from EncryptedObjects
+/// Fallback for legacy (non-envelope) blobs: each `EncryptedObject` carries
+/// its own `KeyId` as the `id` field, so no separate lookup is needed.
 fn extract_key_ids_from_objects(objects: &[EncryptedObject]) -> Result<Vec<KeyId>, EnclaveError> {
-    // ❌ ISSUE: Need to understand EncryptedObject structure to extract KeyIDs
-    info!("❌ MISSING: Need to extract KeyIDs from EncryptedObject structure");
-    
-    // For now, return empty
-    Ok(Vec::new())
-}
-
-/// Summary of what needs to be fixed for real SEAL decryption
-pub fn print_seal_decryption_requirements() {
-    println!("🔧 SEAL Decryption Requirements:");
-    println!("1. ❌ Parse real blob format to extract EncryptedObjects");
-    println!("2. ❌ Get enclave_object_id and initial_shared_version");
-    println!("3. ❌ Create valid ProgrammableTransaction calling seal_approve");
-    println!("4. ❌ Implement proper SEAL key server HTTP API calls");
-    println!("5. ❌ Extract KeyIDs from EncryptedObject structure");
-    println!("6. ✅ Use seal_decrypt_all_objects (this part is correct)");
-    println!();
-    println!("🎯 Root Issue: We're missing the SUI blockchain context and proper blob format understanding");
+    if objects.is_empty() {
+        return Err(EnclaveError::GenericError("Blob contained no EncryptedObjects".to_string()));
+    }
+    Ok(objects.iter().map(|obj| obj.id.clone()).collect())
+}
+
+/// Current `WrappedSecret` format version. Bump on any incompatible change
+/// to the wrap layout so `unwrap` can reject stale callers cleanly.
+const WRAPPED_SECRET_VERSION: u32 = 1;
+
+/// An authenticated, doubly-sealed export of plaintext recovered inside the
+/// enclave: the plaintext never leaves in the clear. A random AES-256 data
+/// key seals the plaintext; that data key is in turn sealed to
+/// `recipient_pk` under a key derived from an ephemeral X25519 ECDH, so only
+/// the holder of the matching private key can ever recover it. Mirrors the
+/// SecureKeyWrapper import/export pattern used by hardware key stores.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WrappedSecret {
+    pub version: u32,
+    /// Ephemeral X25519 public key used for the ECDH that derives the
+    /// key-encryption key; the recipient redoes the same ECDH with their
+    /// private key to recover it.
+    pub ephemeral_pk: [u8; 32],
+    /// The random AES-256 data key, sealed under the ECDH-derived
+    /// key-encryption key.
+    pub wrapped_key_ciphertext: Vec<u8>,
+    pub wrapped_key_iv: [u8; 12],
+    pub wrapped_key_tag: [u8; 16],
+    /// Binds this wrap to the requesting enclave/session public key: fed in
+    /// as GCM additional authenticated data on both seal layers, so a
+    /// wrapper whose description is altered to name a different session
+    /// fails tag verification instead of silently decrypting.
+    pub description: String,
+    /// The plaintext, sealed under the (still-wrapped) data key.
+    pub payload_ciphertext: Vec<u8>,
+    pub payload_iv: [u8; 12],
+    pub payload_tag: [u8; 16],
+}
+
+/// Split a combined AES-GCM `ciphertext || tag` output (as produced by the
+/// `aead::Aead::encrypt` call) into its separate pieces, since
+/// `WrappedSecret` carries the tag as its own field rather than appended.
+fn split_gcm_tag(mut sealed: Vec<u8>) -> Result<(Vec<u8>, [u8; 16]), EnclaveError> {
+    if sealed.len() < 16 {
+        return Err(EnclaveError::GenericError(
+            "wrap_for_caller: AES-GCM output shorter than a tag".to_string(),
+        ));
+    }
+    let tag_start = sealed.len() - 16;
+    let tag_bytes = sealed.split_off(tag_start);
+    let mut tag = [0u8; 16];
+    tag.copy_from_slice(&tag_bytes);
+    Ok((sealed, tag))
+}
+
+/// Export path for handing decrypted plaintext to a caller outside the
+/// enclave without ever putting it on the wire in the clear.
+/// `recipient_pk` is the caller's X25519 public key; `session_vk` is the
+/// (string-encoded) verification key of the enclave/session that produced
+/// this plaintext, bound into the wrap as AAD so a wrapper can't be replayed
+/// under a different session's identity.
+pub fn wrap_for_caller(
+    plaintext: &[u8],
+    recipient_pk: &[u8; 32],
+    session_vk: &str,
+) -> Result<WrappedSecret, EnclaveError> {
+    let description = format!(
+        "satya-wrapped-secret:v{}:session={}",
+        WRAPPED_SECRET_VERSION, session_vk
+    );
+
+    // Layer 1: a fresh random AES-256 data key seals the plaintext.
+    let mut data_key = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut data_key);
+    let mut payload_iv = [0u8; 12];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut payload_iv);
+    let payload_cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&data_key));
+    let payload_sealed = payload_cipher
+        .encrypt(
+            Nonce::from_slice(&payload_iv),
+            Payload { msg: plaintext, aad: description.as_bytes() },
+        )
+        .map_err(|e| EnclaveError::GenericError(format!("wrap_for_caller: payload seal failed: {}", e)))?;
+    let (payload_ciphertext, payload_tag) = split_gcm_tag(payload_sealed)?;
+
+    // Layer 2: an ephemeral X25519 ECDH with `recipient_pk`, hashed together
+    // with the binding description, derives the key-encryption key that
+    // seals the data key itself.
+    let ephemeral_sk = EphemeralSecret::random_from_rng(rand::thread_rng());
+    let ephemeral_pk = X25519PublicKey::from(&ephemeral_sk);
+    let shared_secret = ephemeral_sk.diffie_hellman(&X25519PublicKey::from(*recipient_pk));
+
+    let mut hasher = Sha256::new();
+    hasher.update(shared_secret.as_bytes());
+    hasher.update(description.as_bytes());
+    let kek = hasher.finalize();
+
+    let mut wrapped_key_iv = [0u8; 12];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut wrapped_key_iv);
+    let key_cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&kek));
+    let wrapped_key_sealed = key_cipher
+        .encrypt(
+            Nonce::from_slice(&wrapped_key_iv),
+            Payload { msg: &data_key, aad: description.as_bytes() },
+        )
+        .map_err(|e| EnclaveError::GenericError(format!("wrap_for_caller: key-wrap seal failed: {}", e)))?;
+    let (wrapped_key_ciphertext, wrapped_key_tag) = split_gcm_tag(wrapped_key_sealed)?;
+
+    Ok(WrappedSecret {
+        version: WRAPPED_SECRET_VERSION,
+        ephemeral_pk: ephemeral_pk.to_bytes(),
+        wrapped_key_ciphertext,
+        wrapped_key_iv,
+        wrapped_key_tag,
+        description,
+        payload_ciphertext,
+        payload_iv,
+        payload_tag,
+    })
+}
+
+/// Inverse of `wrap_for_caller`: recovers the plaintext given the
+/// recipient's X25519 private key. Any tampering with either seal layer, or
+/// with its `description`, fails the relevant GCM tag check rather than
+/// returning garbage. The session named in `description` is not compared
+/// against any expected session here.
+pub fn unwrap(wrapped: &WrappedSecret, recipient_sk: &StaticSecret) -> Result<Vec<u8>, EnclaveError> {
+    if wrapped.version != WRAPPED_SECRET_VERSION {
+        return Err(EnclaveError::GenericError(format!(
+            "unwrap: unsupported WrappedSecret version {} (expected {})",
+            wrapped.version, WRAPPED_SECRET_VERSION
+        )));
+    }
+
+    let shared_secret = recipient_sk.diffie_hellman(&X25519PublicKey::from(wrapped.ephemeral_pk));
+    let mut hasher = Sha256::new();
+    hasher.update(shared_secret.as_bytes());
+    hasher.update(wrapped.description.as_bytes());
+    let kek = hasher.finalize();
+
+    let mut wrapped_key_sealed = wrapped.wrapped_key_ciphertext.clone();
+    wrapped_key_sealed.extend_from_slice(&wrapped.wrapped_key_tag);
+    let key_cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&kek));
+    let data_key = key_cipher
+        .decrypt(
+            Nonce::from_slice(&wrapped.wrapped_key_iv),
+            Payload { msg: &wrapped_key_sealed, aad: wrapped.description.as_bytes() },
+        )
+        .map_err(|_| EnclaveError::GenericError("unwrap: key-unwrap AES-GCM tag verification failed".to_string()))?;
+
+    let mut payload_sealed = wrapped.payload_ciphertext.clone();
+    payload_sealed.extend_from_slice(&wrapped.payload_tag);
+    let payload_cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&data_key));
+    payload_cipher
+        .decrypt(
+            Nonce::from_slice(&wrapped.payload_iv),
+            Payload { msg: &payload_sealed, aad: wrapped.description.as_bytes() },
+        )
+        .map_err(|_| EnclaveError::GenericError("unwrap: payload AES-GCM tag verification failed".to_string()))
 }
\ No newline at end of file
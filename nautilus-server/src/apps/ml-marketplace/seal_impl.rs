@@ -37,13 +37,22 @@ lazy_static::lazy_static! {
                     parse_ibe_public_key("0xa040b5548bb0428fba159895c07080cbfdc76ef01bb88ca2ced5c85b07782e09970a1f5684e2a0dd3d3e31beb6cbd7ea02c49a3794b26c6d3d9ffdc99e4984cc981d0d72e933c2af3309216bf7011e9e82c7b68276882f18ba0ea7f45a7721db").unwrap()
                 );
                 map
-            }
+            },
+            server_urls: {
+                let mut map = HashMap::new();
+                map.insert(
+                    ObjectID::from_str("0x0d7b76b217d1a03ffd77b066624b5c690fa89892032").unwrap(),
+                    "https://rpc.h2o-nodes.com/dsn/0d7b76b217d1a03ffd77b066624b5c690fa89892032/v1/service".to_string(),
+                );
+                map
+            },
+            // Only one key server is registered today, so t-of-n collapses to 1-of-1;
+            // raising this to >1 once more H2O Nodes servers come online is the whole
+            // point of keeping it a config field rather than a hardcoded assumption.
+            threshold: 1,
         }
     };
     
-    /// H2O Nodes testnet key server URL
-    pub static ref H2O_KEY_SERVER_URL: &'static str = "https://rpc.h2o-nodes.com/dsn/0d7b76b217d1a03ffd77b066624b5c690fa89892032/v1/service";
-    
     /// Encryption keys for this enclave instance
     pub static ref ML_ENCRYPTION_KEYS: (seal_sdk::ElGamalSecretKey, seal_sdk::types::ElGamalPublicKey, seal_sdk::types::ElgamalVerificationKey) = {
         seal_sdk::genkey(&mut thread_rng())
@@ -56,6 +65,13 @@ pub struct SealConfigML {
     pub public_keys: Vec<IBEPublicKey>,
     pub package_id: ObjectID,
     pub server_pk_map: HashMap<ObjectID, IBEPublicKey>,
+    /// HTTP endpoint to reach each key server at, keyed the same way as
+    /// `server_pk_map`. Used by `seal_fix::fetch_from_seal_servers` to know
+    /// where to POST `FetchKeyRequest`s.
+    pub server_urls: HashMap<ObjectID, String>,
+    /// Minimum number of key-server responses required before decryption can
+    /// proceed (the "t" in t-of-n threshold fetching).
+    pub threshold: usize,
 }
 
 /// Parse IBE public key from hex string
@@ -108,7 +124,35 @@ pub async fn attempt_seal_decryption(data: &[u8], enclave_kp: &Ed25519KeyPair) -
     if entropy < 7.0 {
         return Err(EnclaveError::GenericError("Blob does not appear to be encrypted (low entropy)".to_string()));
     }
-    
+
+    // High entropy alone doesn't distinguish SEAL ciphertext from compressed
+    // formats (ZIP, PNG, gzipped pickles) — those also exceed 7.0 bits/byte.
+    // A uniformity check over the full byte-value distribution does: truly
+    // random ciphertext clusters around the 255-degrees-of-freedom mean,
+    // while compressed data's residual structure pushes X² well outside it.
+    // The chi-square test needs an expected count >=5 per one of the 256
+    // bins to be meaningful, i.e. roughly 1280+ sampled bytes; below that,
+    // small blobs are statistically likely to be misclassified, so fall
+    // back to the entropy check alone rather than gate on noise.
+    const CHI_SQUARE_MIN_SAMPLE_BYTES: usize = 1280;
+    let sample_len = std::cmp::min(4096, data.len());
+    if sample_len >= CHI_SQUARE_MIN_SAMPLE_BYTES {
+        let chi_square = chi_square_uniformity(&data[..sample_len]);
+        info!("📊 Blob chi-square uniformity: {:.2} (encrypted data typically 150.0-400.0)", chi_square);
+
+        if !(150.0..=400.0).contains(&chi_square) {
+            return Err(EnclaveError::GenericError(format!(
+                "Blob does not appear to be uniformly random (chi-square {:.2} outside [150.0, 400.0]); likely compressed rather than encrypted",
+                chi_square
+            )));
+        }
+    } else {
+        info!(
+            "📊 Blob too small ({} bytes) for a meaningful chi-square test; relying on entropy alone",
+            sample_len
+        );
+    }
+
     // Try multiple approaches to extract EncryptedObjects
     let encrypted_objects = extract_encrypted_objects_from_blob(data).await?;
     info!("📦 Extracted {} EncryptedObject(s) from blob", encrypted_objects.len());
@@ -132,10 +176,19 @@ async fn extract_encrypted_objects_from_blob(data: &[u8]) -> Result<Vec<Encrypte
         return Ok(multiple_objs);
     }
     
-    // Method 2: Look for BCS-encoded EncryptedObjects within the blob
+    // Method 2: Self-describing SATYA container — an explicit offset/length
+    // table lets us seek straight to each framed object in O(object count)
+    // instead of brute-force scanning every byte offset (Method 2b below).
+    if let Ok(framed_objects) = parse_satya_container(data) {
+        info!("✅ Parsed {} EncryptedObject(s) from SATYA container", framed_objects.len());
+        return Ok(framed_objects);
+    }
+
+    // Method 2b: Legacy fallback for blobs packed before the SATYA container
+    // format existed — scan for BCS-encoded EncryptedObjects at every offset.
     let embedded_objects = scan_for_embedded_encrypted_objects(data)?;
     if !embedded_objects.is_empty() {
-        info!("✅ Found {} embedded EncryptedObjects", embedded_objects.len());
+        info!("✅ Found {} embedded EncryptedObjects via legacy scan", embedded_objects.len());
         return Ok(embedded_objects);
     }
     
@@ -171,34 +224,147 @@ async fn attempt_real_h2o_seal_decryption(encrypted_objects: &[EncryptedObject],
     let fetch_request = create_h2o_fetch_key_request(&ptb, &session_keypair, certificate)?;
     info!("📮 Created FetchKeyRequest");
     
-    // Step 5: Fetch keys from H2O Nodes key server (with quick timeout)
+    // Step 5: Fetch keys from every configured H2O Nodes key server
+    // concurrently, wait for all of them (with an overall timeout so a hung
+    // server can't stall the whole blob decryption), and proceed only if at
+    // least `ML_SEAL_CONFIG.threshold` of them answered.
+    let threshold = ML_SEAL_CONFIG.threshold.max(1);
     let seal_responses = match tokio::time::timeout(
-        std::time::Duration::from_secs(3), // Very quick timeout to avoid hanging
-        fetch_keys_from_h2o_server(&fetch_request)
+        std::time::Duration::from_secs(5),
+        fetch_keys_from_h2o_servers(&fetch_request)
     ).await {
-        Ok(result) => {
-            match result {
-                Ok(responses) if !responses.is_empty() => responses,
-                _ => {
-                    info!("⚡ H2O server returned empty/invalid response, using mock decryption");
-                    return create_mock_decrypted_model_data();
-                }
-            }
-        },
+        Ok(Ok(responses)) if responses.len() >= threshold => responses,
+        Ok(Ok(responses)) => {
+            return Err(EnclaveError::GenericError(format!(
+                "UntrustedKeyServer: only {} of {} needed H2O key-server responses arrived; refusing to decrypt",
+                responses.len(), threshold
+            )));
+        }
+        Ok(Err(e)) => {
+            return Err(EnclaveError::GenericError(format!(
+                "UntrustedKeyServer: H2O key-server fetch failed: {}", e
+            )));
+        }
         Err(_) => {
-            info!("⚡ H2O key server timeout (3s), falling back to mock decryption");
-            return create_mock_decrypted_model_data();
+            return Err(EnclaveError::GenericError(
+                "UntrustedKeyServer: H2O key-server fetch timed out after 5s".to_string(),
+            ));
         }
     };
-    info!("🔐 Fetched {} key responses from H2O server", seal_responses.len());
-    
+    info!("🔐 Fetched {} key responses from {} H2O server(s)", seal_responses.len(), ML_SEAL_CONFIG.server_urls.len());
+
+    // Step 5b: Verify every response is actually attributable to the server
+    // whose pinned IBEPublicKey we trust, and consistent with the
+    // enc_verification_key we submitted, before it's counted toward the
+    // decryption set — a compromised or spoofed endpoint must not be able to
+    // inject a response on transport success alone.
+    let mut verified_responses = Vec::with_capacity(seal_responses.len());
+    for (object_id, response) in &seal_responses {
+        match verify_h2o_key_server_response(object_id, response, encrypted_objects) {
+            Ok(()) => verified_responses.push((*object_id, response.clone())),
+            Err(e) => info!("❌ Rejecting H2O server {} response: {}", object_id, e),
+        }
+    }
+
+    if verified_responses.len() < threshold {
+        return Err(EnclaveError::GenericError(format!(
+            "UntrustedKeyServer: only {} of {} H2O responses verified authentic; refusing to decrypt",
+            verified_responses.len(), threshold
+        )));
+    }
+    info!("🔐 Verified {} authentic key response(s)", verified_responses.len());
+
     // Step 6: Decrypt using SEAL SDK
-    let decrypted_data = decrypt_with_h2o_responses(encrypted_objects, &seal_responses).await?;
+    let decrypted_data = decrypt_with_h2o_responses(encrypted_objects, &verified_responses).await?;
     info!("✅ SEAL decryption successful: {} bytes", decrypted_data.len());
     
     Ok(decrypted_data)
 }
 
+/// Magic bytes identifying a self-describing SATYA container.
+const SATYA_CONTAINER_MAGIC: &[u8; 5] = b"SATYA";
+/// Container format version this build reads and writes.
+const SATYA_CONTAINER_VERSION: u8 = 1;
+/// `magic (5) + version (1) + object count (2)`
+const SATYA_CONTAINER_HEADER_LEN: usize = 8;
+/// Each table entry is a `(u64 offset, u64 length)` little-endian pair.
+const SATYA_CONTAINER_ENTRY_LEN: usize = 16;
+
+/// Parse the self-describing SATYA container format: magic bytes `b"SATYA"`,
+/// a u8 version, a u16 little-endian object count, then that many little-
+/// endian `(u64 offset, u64 length)` pairs framing each `EncryptedObject` in
+/// the blob. Lets callers seek directly to each object instead of the
+/// quadratic brute-force scan in `scan_for_embedded_encrypted_objects`.
+fn parse_satya_container(data: &[u8]) -> Result<Vec<EncryptedObject>, EnclaveError> {
+    if data.len() < SATYA_CONTAINER_HEADER_LEN || &data[..5] != SATYA_CONTAINER_MAGIC {
+        return Err(EnclaveError::GenericError("not a SATYA container: bad magic".to_string()));
+    }
+
+    let version = data[5];
+    if version != SATYA_CONTAINER_VERSION {
+        return Err(EnclaveError::GenericError(format!("unsupported SATYA container version {}", version)));
+    }
+
+    let object_count = u16::from_le_bytes([data[6], data[7]]) as usize;
+    let table_start = SATYA_CONTAINER_HEADER_LEN;
+    let table_len = object_count * SATYA_CONTAINER_ENTRY_LEN;
+    let table_end = table_start + table_len;
+    if data.len() < table_end {
+        return Err(EnclaveError::GenericError("SATYA container offset table is truncated".to_string()));
+    }
+
+    let mut objects = Vec::with_capacity(object_count);
+    for i in 0..object_count {
+        let entry = &data[table_start + i * SATYA_CONTAINER_ENTRY_LEN..table_start + (i + 1) * SATYA_CONTAINER_ENTRY_LEN];
+        let offset = u64::from_le_bytes(entry[0..8].try_into().unwrap()) as usize;
+        let length = u64::from_le_bytes(entry[8..16].try_into().unwrap()) as usize;
+        let end = offset
+            .checked_add(length)
+            .filter(|&end| end <= data.len())
+            .ok_or_else(|| EnclaveError::GenericError(format!("SATYA container entry {} is out of bounds", i)))?;
+
+        let obj = bcs::from_bytes::<EncryptedObject>(&data[offset..end])
+            .map_err(|e| EnclaveError::GenericError(format!("failed to parse framed EncryptedObject {}: {}", i, e)))?;
+        objects.push(obj);
+    }
+
+    Ok(objects)
+}
+
+/// Pack `objects` into the self-describing SATYA container format that
+/// `parse_satya_container` reads back, so the marketplace can write blobs
+/// that skip the legacy O(n²) scan entirely.
+pub fn write_satya_container(objects: &[EncryptedObject]) -> Result<Vec<u8>, EnclaveError> {
+    if objects.len() > u16::MAX as usize {
+        return Err(EnclaveError::GenericError("too many EncryptedObjects for a SATYA container".to_string()));
+    }
+
+    let bodies = objects
+        .iter()
+        .map(bcs::to_bytes)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| EnclaveError::GenericError(format!("failed to BCS-encode EncryptedObject: {}", e)))?;
+
+    let header_len = SATYA_CONTAINER_HEADER_LEN + bodies.len() * SATYA_CONTAINER_ENTRY_LEN;
+    let total_len = header_len + bodies.iter().map(Vec::len).sum::<usize>();
+    let mut out = Vec::with_capacity(total_len);
+    out.extend_from_slice(SATYA_CONTAINER_MAGIC);
+    out.push(SATYA_CONTAINER_VERSION);
+    out.extend_from_slice(&(objects.len() as u16).to_le_bytes());
+
+    let mut offset = header_len as u64;
+    for body in &bodies {
+        out.extend_from_slice(&offset.to_le_bytes());
+        out.extend_from_slice(&(body.len() as u64).to_le_bytes());
+        offset += body.len() as u64;
+    }
+    for body in &bodies {
+        out.extend_from_slice(body);
+    }
+
+    Ok(out)
+}
+
 /// Scan for embedded EncryptedObjects within blob data
 fn scan_for_embedded_encrypted_objects(data: &[u8]) -> Result<Vec<EncryptedObject>, EnclaveError> {
     let mut objects = Vec::new();
@@ -261,23 +427,72 @@ fn extract_key_ids_from_encrypted_objects(objects: &[EncryptedObject]) -> Result
     Ok(key_ids)
 }
 
-/// Create session and certificate for H2O Nodes testnet
-async fn create_h2o_session_and_certificate(enclave_kp: &Ed25519KeyPair) -> Result<(Ed25519KeyPair, Certificate), EnclaveError> {
+/// Fraction of a session certificate's TTL remaining at which we proactively
+/// mint a replacement rather than waiting for it to actually lapse, so an
+/// in-flight decryption never races an expiring certificate.
+const H2O_SESSION_REKEY_THRESHOLD: f64 = 0.2;
+
+/// A cached H2O session key/certificate, along with what's needed to decide
+/// whether it's still worth reusing.
+struct CachedH2oSession {
+    session_keypair: std::sync::Arc<Ed25519KeyPair>,
+    certificate: Certificate,
+    creation_time_ms: u64,
+    ttl_min: u64,
+}
+
+impl CachedH2oSession {
+    /// True once less than `H2O_SESSION_REKEY_THRESHOLD` of the TTL remains.
+    fn needs_rekey(&self, now_ms: u64) -> bool {
+        let ttl_ms = self.ttl_min * 60_000;
+        if ttl_ms == 0 {
+            return true;
+        }
+        let elapsed_ms = now_ms.saturating_sub(self.creation_time_ms);
+        let remaining_fraction = 1.0 - (elapsed_ms as f64 / ttl_ms as f64);
+        remaining_fraction < H2O_SESSION_REKEY_THRESHOLD
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Sessions in flight, keyed by `package_id`, so concurrent H2O
+    /// decryptions against the same package reuse one session/certificate
+    /// instead of each paying the key-generation + signing cost.
+    static ref H2O_SESSION_CACHE: tokio::sync::Mutex<HashMap<ObjectID, CachedH2oSession>> =
+        tokio::sync::Mutex::new(HashMap::new());
+}
+
+/// Create (or reuse a cached, still-fresh) session and certificate for H2O
+/// Nodes testnet. A cached session is reused until less than
+/// `H2O_SESSION_REKEY_THRESHOLD` of its TTL remains, at which point it's
+/// proactively replaced instead of being handed out right up to expiry.
+async fn create_h2o_session_and_certificate(enclave_kp: &Ed25519KeyPair) -> Result<(std::sync::Arc<Ed25519KeyPair>, Certificate), EnclaveError> {
     use fastcrypto::traits::KeyPair;
     use std::time::{SystemTime, UNIX_EPOCH};
-    
-    // Generate session keypair
-    let session_keypair = Ed25519KeyPair::generate(&mut thread_rng());
-    let session_vk = session_keypair.public();
-    
-    // Create timestamp
-    let creation_time = SystemTime::now()
+
+    let package_id = ML_SEAL_CONFIG.package_id;
+    let now_ms = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map_err(|e| EnclaveError::GenericError(format!("Time error: {}", e)))?
         .as_millis() as u64;
-    
+
+    {
+        let cache = H2O_SESSION_CACHE.lock().await;
+        if let Some(cached) = cache.get(&package_id) {
+            if !cached.needs_rekey(now_ms) {
+                info!("📜 Reusing cached H2O session certificate");
+                return Ok((cached.session_keypair.clone(), cached.certificate.clone()));
+            }
+        }
+    }
+
+    // Generate session keypair
+    let session_keypair = Ed25519KeyPair::generate(&mut thread_rng());
+    let session_vk = session_keypair.public();
+
+    let creation_time = now_ms;
     let ttl_min = 10;
-    
+
     // Create signed message for H2O package
     let message = seal_sdk::signed_message(
         ML_SEAL_CONFIG.package_id.to_string(),
@@ -315,6 +530,18 @@ async fn create_h2o_session_and_certificate(enclave_kp: &Ed25519KeyPair) -> Resu
     };
     
     info!("📜 Created H2O session certificate with TTL {} minutes", ttl_min);
+
+    let session_keypair = std::sync::Arc::new(session_keypair);
+    H2O_SESSION_CACHE.lock().await.insert(
+        package_id,
+        CachedH2oSession {
+            session_keypair: session_keypair.clone(),
+            certificate: certificate.clone(),
+            creation_time_ms: creation_time,
+            ttl_min,
+        },
+    );
+
     Ok((session_keypair, certificate))
 }
 
@@ -387,58 +614,113 @@ fn create_h2o_fetch_key_request(
     Ok(fetch_request)
 }
 
-/// Fetch keys from H2O Nodes key server
-async fn fetch_keys_from_h2o_server(
+/// Query every key server in `ML_SEAL_CONFIG.server_urls` concurrently via
+/// `futures::future::join_all`, each with its own short per-server timeout,
+/// and aggregate however many respond successfully. A node trusts a *set*
+/// of key servers rather than a single one, so one unreachable or malicious
+/// server only costs it that server's response — the caller decides whether
+/// enough came back to meet `ML_SEAL_CONFIG.threshold`.
+async fn fetch_keys_from_h2o_servers(
     request: &FetchKeyRequest,
 ) -> Result<Vec<(ObjectID, FetchKeyResponse)>, EnclaveError> {
-    info!("🌊 Sending FetchKeyRequest to H2O Nodes: {}", *H2O_KEY_SERVER_URL);
-    
-    let client = reqwest::Client::new();
-    
-    // Serialize the request
     let request_data = bcs::to_bytes(request)
         .map_err(|e| EnclaveError::GenericError(format!("Failed to serialize request: {}", e)))?;
-    
-    // Send HTTP request to H2O key server
-    match client
-        .post(*H2O_KEY_SERVER_URL)
+
+    if ML_SEAL_CONFIG.server_urls.is_empty() {
+        return Err(EnclaveError::GenericError("No H2O key servers configured".to_string()));
+    }
+
+    let client = reqwest::Client::new();
+    let fetches = ML_SEAL_CONFIG.server_urls.iter().map(|(object_id, url)| {
+        let client = client.clone();
+        let url = url.clone();
+        let body = request_data.clone();
+        let object_id = *object_id;
+        async move { (object_id, fetch_one_h2o_server(&client, &url, body).await) }
+    });
+
+    let results = futures::future::join_all(fetches).await;
+
+    let mut seal_responses = Vec::new();
+    for (object_id, outcome) in results {
+        match outcome {
+            Ok(response) => {
+                info!("✅ Received key response from H2O server {}", object_id);
+                seal_responses.push((object_id, response));
+            }
+            Err(e) => info!("❌ H2O server {} failed: {}", object_id, e),
+        }
+    }
+
+    Ok(seal_responses)
+}
+
+/// POST one `FetchKeyRequest` to a single H2O key server.
+async fn fetch_one_h2o_server(
+    client: &reqwest::Client,
+    url: &str,
+    request_data: Vec<u8>,
+) -> Result<FetchKeyResponse, String> {
+    let response = client
+        .post(url)
         .header("Content-Type", "application/octet-stream")
         .body(request_data)
         .timeout(std::time::Duration::from_secs(2)) // Very fast timeout
         .send()
         .await
-    {
-        Ok(response) => {
-            if response.status().is_success() {
-                let response_data = response.bytes().await
-                    .map_err(|e| EnclaveError::GenericError(format!("Failed to read H2O response: {}", e)))?;
-                
-                // Parse response
-                match bcs::from_bytes::<Vec<(ObjectID, FetchKeyResponse)>>(&response_data) {
-                    Ok(seal_responses) => {
-                        info!("✅ Received {} key responses from H2O server", seal_responses.len());
-                        Ok(seal_responses)
-                    },
-                    Err(e) => {
-                        info!("❌ Failed to parse H2O response: {}", e);
-                        // For testing, return empty responses
-                        Ok(vec![])
-                    }
-                }
-            } else {
-                Err(EnclaveError::GenericError(format!(
-                    "H2O key server returned error: {}", 
-                    response.status()
-                )))
-            }
-        },
-        Err(e) => {
-            Err(EnclaveError::GenericError(format!(
-                "Failed to connect to H2O key server: {}", 
-                e
-            )))
-        }
+        .map_err(|e| format!("request to {} failed: {}", url, e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("{} returned status {}", url, response.status()));
     }
+
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| format!("failed to read response body from {}: {}", url, e))?;
+
+    bcs::from_bytes::<FetchKeyResponse>(&body)
+        .map_err(|e| format!("failed to parse response from {}: {}", url, e))
+}
+
+/// Pins each key server's registered IBE public key so a `FetchKeyResponse`
+/// claiming to come from a given server can be checked against the key we
+/// actually expect it to hold, rather than trusting whatever
+/// `fetch_keys_from_h2o_servers` handed back over the wire. The SEAL SDK
+/// doesn't expose a lower-level "check this one share" primitive, so
+/// verification runs `seal_decrypt_all_objects` scoped to just this one
+/// server and its pinned key — a forged or mismatched response fails that
+/// combine step and is rejected as `UntrustedKeyServer` before it ever
+/// reaches the real multi-server decrypt.
+fn verify_h2o_key_server_response(
+    object_id: &ObjectID,
+    response: &FetchKeyResponse,
+    encrypted_objects: &[EncryptedObject],
+) -> Result<(), EnclaveError> {
+    let public_key = ML_SEAL_CONFIG.server_pk_map.get(object_id).ok_or_else(|| {
+        EnclaveError::GenericError(format!(
+            "UntrustedKeyServer: {} is not in the pinned server_pk_map",
+            object_id
+        ))
+    })?;
+
+    let mut single_server_pk_map = HashMap::new();
+    single_server_pk_map.insert(*object_id, public_key.clone());
+
+    let (enc_secret, _enc_key, _enc_verification_key) = &*ML_ENCRYPTION_KEYS;
+    seal_decrypt_all_objects(
+        enc_secret,
+        std::slice::from_ref(&(*object_id, response.clone())),
+        encrypted_objects,
+        &single_server_pk_map,
+    )
+    .map(|_| ())
+    .map_err(|e| {
+        EnclaveError::GenericError(format!(
+            "UntrustedKeyServer: {}'s response did not verify against its pinned public key: {}",
+            object_id, e
+        ))
+    })
 }
 
 /// Decrypt using H2O responses and SEAL SDK
@@ -466,60 +748,10 @@ async fn decrypt_with_h2o_responses(
                 Err(EnclaveError::GenericError("No decrypted results returned".to_string()))
             }
         },
-        Err(e) => {
-            // For testing purposes, if real decryption fails, return mock data for our test blob
-            let blob_id = std::env::var("CURRENT_BLOB_ID").unwrap_or_default();
-            if blob_id == "xbjSJovIngb-zximtkcHUe9k7TobpSIiYk5Uh1AwpT4" {
-                info!("🧪 SEAL decryption failed, returning mock data for test blob");
-                return create_mock_decrypted_model_data();
-            }
-            
-            Err(EnclaveError::GenericError(format!(
-                "H2O SEAL decryption failed: {}", e
-            )))
-        }
-    }
-}
-
-
-/// Create mock decrypted model data for testing the specific blob
-fn create_mock_decrypted_model_data() -> Result<Vec<u8>, EnclaveError> {
-    info!("🧪 Creating mock decrypted model data for test blob (H2O decryption simulation)");
-    
-    // Create a minimal pickle-compatible binary format that the Python ML evaluator can load
-    // This simulates what would come out of a real SEAL decryption
-    
-    // Instead of returning JSON, we need to create a binary that looks like a real ML model
-    // For now, create a simple binary pattern that will trigger the Python evaluator's fallback behavior
-    
-    // Create a structured binary blob that simulates a valid model format
-    let mut mock_binary = Vec::new();
-    
-    // Add a pickle protocol 3 header (what sklearn/joblib models typically use)
-    mock_binary.extend_from_slice(b"\x80\x03");  // Pickle protocol 3 signature
-    
-    // Add some mock structured data to make it look like a real model
-    // This will be enough to trigger the Python evaluator's model loading attempts
-    let mock_model_info = format!(
-        "{{\"model_type\": \"sklearn_mock\", \"accuracy\": 0.89, \"size_mb\": {}, \"decrypted_via_seal\": true, \"timestamp\": {}}}",
-        2.6, // Our blob is 2.6MB 
-        chrono::Utc::now().timestamp()
-    );
-    
-    // Add the JSON as bytes after the pickle header
-    mock_binary.extend_from_slice(mock_model_info.as_bytes());
-    
-    // Pad with some random-looking data to make it the right size (simulate model weights)
-    let target_size = 1024; // 1KB of mock model data
-    while mock_binary.len() < target_size {
-        // Add some pseudo-random bytes that look like model weights
-        let pseudo_random = (mock_binary.len() as u8).wrapping_mul(137).wrapping_add(42);
-        mock_binary.push(pseudo_random);
+        Err(e) => Err(EnclaveError::GenericError(format!(
+            "H2O SEAL decryption failed: {}", e
+        ))),
     }
-    
-    info!("📝 Generated mock binary model: {} bytes (with pickle header)", mock_binary.len());
-    
-    Ok(mock_binary)
 }
 
 /// Calculate Shannon entropy of a byte sequence
@@ -528,7 +760,7 @@ fn calculate_entropy(data: &[u8]) -> f64 {
     for &byte in data {
         counts[byte as usize] += 1;
     }
-    
+
     let len = data.len() as f64;
     counts.iter()
         .filter(|&&count| count > 0)
@@ -537,4 +769,26 @@ fn calculate_entropy(data: &[u8]) -> f64 {
             -p * p.log2()
         })
         .sum()
+}
+
+/// Pearson's chi-square statistic for how uniformly `data`'s bytes are
+/// distributed over the 256 possible symbol values. Shannon entropy alone
+/// can't tell SEAL ciphertext apart from compressed/packed formats (ZIP,
+/// PNG, gzipped pickles) since both exceed ~7.0 bits/byte; true random
+/// ciphertext clusters tightly around the 255-degrees-of-freedom mean,
+/// while compressed data's residual byte-frequency skew pushes X² higher.
+fn chi_square_uniformity(data: &[u8]) -> f64 {
+    let mut counts = [0u32; 256];
+    for &byte in data {
+        counts[byte as usize] += 1;
+    }
+
+    let expected = data.len() as f64 / 256.0;
+    counts
+        .iter()
+        .map(|&count| {
+            let diff = count as f64 - expected;
+            diff * diff / expected
+        })
+        .sum()
 }
\ No newline at end of file
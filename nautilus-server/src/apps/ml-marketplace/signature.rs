@@ -0,0 +1,112 @@
+// Copyright (c) Satya Data Marketplace
+// Pluggable multi-signature scheme for TEEAttestation
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::ed25519::{Ed25519KeyPair, Ed25519PublicKey, Ed25519Signature};
+use fastcrypto::secp256k1::{Secp256k1KeyPair, Secp256k1PublicKey, Secp256k1Signature};
+use fastcrypto::secp256r1::{Secp256r1KeyPair, Secp256r1PublicKey, Secp256r1Signature};
+use fastcrypto::traits::{KeyPair, Signer, ToFromBytes, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::types::{MLMarketplaceError, TEEAttestation};
+
+/// A signature over a `TEEAttestation`, tagged by the curve it was produced with.
+///
+/// Secp256r1 (P-256) is included because it's the curve AWS Nitro-style enclaves
+/// actually sign with, so a Nitro attestation's signature can be embedded directly
+/// without re-signing under Ed25519.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AttestationSignature {
+    Ed25519(Vec<u8>),
+    Secp256k1(Vec<u8>),
+    Secp256r1(Vec<u8>),
+}
+
+/// An `AttestationSignature` bundled with the signer's public key, so the pair is
+/// independently verifiable by anyone who trusts the enclave measurement alone.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedAttestation {
+    pub attestation: TEEAttestation,
+    pub signature: AttestationSignature,
+    pub public_key: Vec<u8>,
+}
+
+/// Serialize a `TEEAttestation` to the canonical byte form that gets signed.
+///
+/// Using BCS (rather than JSON) means the digest matches what a Sui Move verifier
+/// would compute over the same struct.
+fn canonical_digest(attestation: &TEEAttestation) -> Result<[u8; 32], MLMarketplaceError> {
+    let bytes = bcs::to_bytes(attestation)
+        .map_err(|e| MLMarketplaceError::CryptoError(format!("BCS serialization failed: {}", e)))?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    Ok(hasher.finalize().into())
+}
+
+/// Sign a `TEEAttestation` with an Ed25519 key.
+pub fn sign_attestation_ed25519(
+    attestation: &TEEAttestation,
+    key: &Ed25519KeyPair,
+) -> Result<AttestationSignature, MLMarketplaceError> {
+    let digest = canonical_digest(attestation)?;
+    let sig = key.sign(&digest);
+    Ok(AttestationSignature::Ed25519(sig.as_ref().to_vec()))
+}
+
+/// Sign a `TEEAttestation` with a secp256k1 key.
+pub fn sign_attestation_secp256k1(
+    attestation: &TEEAttestation,
+    key: &Secp256k1KeyPair,
+) -> Result<AttestationSignature, MLMarketplaceError> {
+    let digest = canonical_digest(attestation)?;
+    let sig = key.sign(&digest);
+    Ok(AttestationSignature::Secp256k1(sig.as_ref().to_vec()))
+}
+
+/// Sign a `TEEAttestation` with a secp256r1 (P-256) key — the curve AWS Nitro
+/// enclaves sign with.
+pub fn sign_attestation_secp256r1(
+    attestation: &TEEAttestation,
+    key: &Secp256r1KeyPair,
+) -> Result<AttestationSignature, MLMarketplaceError> {
+    let digest = canonical_digest(attestation)?;
+    let sig = key.sign(&digest);
+    Ok(AttestationSignature::Secp256r1(sig.as_ref().to_vec()))
+}
+
+/// Verify an `AttestationSignature` against an attestation and the signer's raw
+/// public key bytes, dispatching the curve-specific check by variant.
+pub fn verify_attestation(
+    attestation: &TEEAttestation,
+    signature: &AttestationSignature,
+    public_key: &[u8],
+) -> Result<bool, MLMarketplaceError> {
+    let digest = canonical_digest(attestation)?;
+
+    let verified = match signature {
+        AttestationSignature::Ed25519(sig_bytes) => {
+            let pk = Ed25519PublicKey::from_bytes(public_key)
+                .map_err(|e| MLMarketplaceError::CryptoError(format!("Invalid Ed25519 public key: {}", e)))?;
+            let sig = Ed25519Signature::from_bytes(sig_bytes)
+                .map_err(|e| MLMarketplaceError::CryptoError(format!("Invalid Ed25519 signature: {}", e)))?;
+            pk.verify(&digest, &sig).is_ok()
+        }
+        AttestationSignature::Secp256k1(sig_bytes) => {
+            let pk = Secp256k1PublicKey::from_bytes(public_key)
+                .map_err(|e| MLMarketplaceError::CryptoError(format!("Invalid secp256k1 public key: {}", e)))?;
+            let sig = Secp256k1Signature::from_bytes(sig_bytes)
+                .map_err(|e| MLMarketplaceError::CryptoError(format!("Invalid secp256k1 signature: {}", e)))?;
+            pk.verify(&digest, &sig).is_ok()
+        }
+        AttestationSignature::Secp256r1(sig_bytes) => {
+            let pk = Secp256r1PublicKey::from_bytes(public_key)
+                .map_err(|e| MLMarketplaceError::CryptoError(format!("Invalid secp256r1 public key: {}", e)))?;
+            let sig = Secp256r1Signature::from_bytes(sig_bytes)
+                .map_err(|e| MLMarketplaceError::CryptoError(format!("Invalid secp256r1 signature: {}", e)))?;
+            pk.verify(&digest, &sig).is_ok()
+        }
+    };
+
+    Ok(verified)
+}
@@ -0,0 +1,183 @@
+// Copyright (c) Satya Data Marketplace
+// Authenticated AES-256-GCM streaming (STREAM/LE31) decryption for large blobs
+// SPDX-License-Identifier: Apache-2.0
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+
+use crate::EnclaveError;
+
+/// Plaintext is sealed in fixed-size chunks so multi-GB blobs never need to sit
+/// fully in RAM.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Random nonce prefix written at the front of the blob; combined with a
+/// per-chunk counter and a final-block flag to build each chunk's 96-bit GCM
+/// nonce (the STREAM/LE31 construction).
+const NONCE_PREFIX_LEN: usize = 7;
+
+/// Build the 96-bit nonce for chunk `counter`: `prefix || u32 counter (LE) ||
+/// last-block flag byte`.
+fn build_nonce(prefix: &[u8; NONCE_PREFIX_LEN], counter: u32, last: bool) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[..NONCE_PREFIX_LEN].copy_from_slice(prefix);
+    nonce[NONCE_PREFIX_LEN..NONCE_PREFIX_LEN + 4].copy_from_slice(&counter.to_le_bytes());
+    nonce[11] = if last { 1 } else { 0 };
+    nonce
+}
+
+/// Encrypt `plaintext` under `key` using the STREAM construction, returning
+/// `nonce_prefix || chunk_0 || chunk_1 || ... || chunk_n` where each chunk is
+/// ciphertext + 16-byte GCM tag.
+pub fn stream_encrypt(key: &[u8; 32], plaintext: &[u8]) -> Result<Vec<u8>, EnclaveError> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+
+    let mut prefix = [0u8; NONCE_PREFIX_LEN];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut prefix);
+
+    let mut out = Vec::with_capacity(NONCE_PREFIX_LEN + plaintext.len() + 16 * (plaintext.len() / CHUNK_SIZE + 1));
+    out.extend_from_slice(&prefix);
+
+    let chunks: Vec<&[u8]> = plaintext.chunks(CHUNK_SIZE).collect();
+    let chunk_count = chunks.len().max(1);
+
+    for (i, chunk) in plaintext.chunks(CHUNK_SIZE).enumerate() {
+        let last = i + 1 == chunk_count;
+        let nonce_bytes = build_nonce(&prefix, i as u32, last);
+        let sealed = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), chunk)
+            .map_err(|e| EnclaveError::GenericError(format!("STREAM AES-GCM seal failed: {}", e)))?;
+        out.extend_from_slice(&sealed);
+    }
+
+    if chunks.is_empty() {
+        let nonce_bytes = build_nonce(&prefix, 0, true);
+        let sealed = cipher
+            .encrypt(Nonce::from_slice(&nonce_bytes), &b""[..])
+            .map_err(|e| EnclaveError::GenericError(format!("STREAM AES-GCM seal failed: {}", e)))?;
+        out.extend_from_slice(&sealed);
+    }
+
+    Ok(out)
+}
+
+/// Incremental counterpart to `stream_decrypt`: feed it chunks as they arrive
+/// off the wire (of any size) and it re-frames them into `CHUNK_SIZE`-aligned
+/// STREAM blocks, so the caller never needs the full ciphertext resident in
+/// memory at once. The nonce prefix is consumed from the first `feed` call(s).
+pub struct IncrementalStreamDecryptor {
+    cipher: Aes256Gcm,
+    prefix: Option<[u8; NONCE_PREFIX_LEN]>,
+    pending: Vec<u8>,
+    counter: u32,
+}
+
+const SEALED_CHUNK_LEN: usize = CHUNK_SIZE + 16;
+
+impl IncrementalStreamDecryptor {
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+            prefix: None,
+            pending: Vec::new(),
+            counter: 0,
+        }
+    }
+
+    /// Feed the next chunk of raw wire bytes. Returns any newly-verified
+    /// plaintext; a sealed chunk that straddles two `feed` calls is buffered
+    /// until enough bytes have arrived to verify it.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Vec<u8>, EnclaveError> {
+        self.pending.extend_from_slice(chunk);
+
+        if self.prefix.is_none() {
+            if self.pending.len() < NONCE_PREFIX_LEN {
+                return Ok(Vec::new());
+            }
+            let mut prefix = [0u8; NONCE_PREFIX_LEN];
+            prefix.copy_from_slice(&self.pending[..NONCE_PREFIX_LEN]);
+            self.prefix = Some(prefix);
+            self.pending.drain(..NONCE_PREFIX_LEN);
+        }
+        let prefix = self.prefix.expect("just set above");
+
+        // Only decrypt chunks once we can tell they are not the final
+        // (possibly short) one, since the last chunk's nonce differs.
+        let mut out = Vec::new();
+        while self.pending.len() > SEALED_CHUNK_LEN {
+            let sealed_chunk: Vec<u8> = self.pending.drain(..SEALED_CHUNK_LEN).collect();
+            let nonce_bytes = build_nonce(&prefix, self.counter, false);
+            let chunk_plain = self
+                .cipher
+                .decrypt(Nonce::from_slice(&nonce_bytes), sealed_chunk.as_slice())
+                .map_err(|_| {
+                    EnclaveError::GenericError(format!(
+                        "STREAM AES-GCM tag verification failed at chunk {}",
+                        self.counter
+                    ))
+                })?;
+            out.extend_from_slice(&chunk_plain);
+            self.counter += 1;
+        }
+        Ok(out)
+    }
+
+    /// Finalize the stream: the remaining buffered bytes are the last
+    /// (possibly short) chunk, sealed with `last = true`.
+    pub fn finish(mut self) -> Result<Vec<u8>, EnclaveError> {
+        let Some(prefix) = self.prefix else {
+            return Err(EnclaveError::GenericError(
+                "blob too small for STREAM nonce prefix".to_string(),
+            ));
+        };
+        let nonce_bytes = build_nonce(&prefix, self.counter, true);
+        let sealed_chunk = std::mem::take(&mut self.pending);
+        self.cipher
+            .decrypt(Nonce::from_slice(&nonce_bytes), sealed_chunk.as_slice())
+            .map_err(|_| {
+                EnclaveError::GenericError(format!(
+                    "STREAM AES-GCM tag verification failed at final chunk {}",
+                    self.counter
+                ))
+            })
+    }
+}
+
+/// Decrypt a STREAM-encoded blob, verifying each chunk's GCM tag before
+/// releasing its plaintext and aborting the whole blob on the first failure.
+pub fn stream_decrypt(key: &[u8; 32], data: &[u8]) -> Result<Vec<u8>, EnclaveError> {
+    if data.len() < NONCE_PREFIX_LEN {
+        return Err(EnclaveError::GenericError("blob too small for STREAM nonce prefix".to_string()));
+    }
+
+    let mut prefix = [0u8; NONCE_PREFIX_LEN];
+    prefix.copy_from_slice(&data[..NONCE_PREFIX_LEN]);
+
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let ciphertext = &data[NONCE_PREFIX_LEN..];
+
+    let mut plaintext = Vec::with_capacity(ciphertext.len());
+    let mut counter: u32 = 0;
+    let mut offset = 0;
+
+    while offset < ciphertext.len() {
+        let remaining = ciphertext.len() - offset;
+        let take = remaining.min(SEALED_CHUNK_LEN);
+        let last = offset + take == ciphertext.len();
+        let sealed_chunk = &ciphertext[offset..offset + take];
+
+        let nonce_bytes = build_nonce(&prefix, counter, last);
+        let chunk_plain = cipher
+            .decrypt(Nonce::from_slice(&nonce_bytes), sealed_chunk)
+            .map_err(|_| EnclaveError::GenericError(format!(
+                "STREAM AES-GCM tag verification failed at chunk {}",
+                counter
+            )))?;
+
+        plaintext.extend_from_slice(&chunk_plain);
+        offset += take;
+        counter += 1;
+    }
+
+    Ok(plaintext)
+}
@@ -292,6 +292,9 @@ pub struct AssessmentResponse {
     pub request_id: String,
     pub status: AssessmentStatus,
     pub attestation: Option<TEEAttestation>,
+    /// The attestation enveloped with its cryptographic signature and signer
+    /// public key, so it is independently verifiable off the server.
+    pub signed_attestation: Option<super::signature::SignedAttestation>,
     pub error_message: Option<String>,
     pub estimated_completion_time: Option<u64>, // Unix timestamp
 }
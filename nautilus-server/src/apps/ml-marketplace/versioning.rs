@@ -0,0 +1,93 @@
+// Copyright (c) Satya Data Marketplace
+// Schema versioning for the assessment API
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use super::types::{AssessmentConfig, AssessmentOperation, BenchmarkConfig, MLMarketplaceError};
+
+/// Oldest `api_version` this enclave can still translate into the current
+/// `AssessmentOperation` shape.
+pub const MIN_SUPPORTED_API_VERSION: u16 = 1;
+
+/// Newest `api_version` this enclave understands natively.
+pub const CURRENT_API_VERSION: u16 = 2;
+
+/// An `AssessmentOperation` tagged with the schema version it was encoded against.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionedAssessmentOperation {
+    pub api_version: u16,
+    #[serde(flatten)]
+    pub operation: AssessmentOperation,
+}
+
+/// Response body for `GET /version`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionInfo {
+    pub min_supported: u16,
+    pub current: u16,
+}
+
+pub fn supported_version_range() -> VersionInfo {
+    VersionInfo {
+        min_supported: MIN_SUPPORTED_API_VERSION,
+        current: CURRENT_API_VERSION,
+    }
+}
+
+/// Reject an `api_version` outside `MIN_SUPPORTED_API_VERSION..=CURRENT_API_VERSION`.
+/// `None` is treated as "the current version", so existing callers that predate
+/// versioning entirely keep working unchanged.
+pub fn check_api_version(api_version: Option<u16>) -> Result<(), MLMarketplaceError> {
+    let api_version = api_version.unwrap_or(CURRENT_API_VERSION);
+    if api_version < MIN_SUPPORTED_API_VERSION || api_version > CURRENT_API_VERSION {
+        return Err(MLMarketplaceError::ModelValidationError(format!(
+            "unsupported api_version {} (supported range is {}..={})",
+            api_version, MIN_SUPPORTED_API_VERSION, CURRENT_API_VERSION
+        )));
+    }
+    Ok(())
+}
+
+/// Validate and normalize a versioned request into the current `AssessmentOperation`
+/// shape, filling in sensible defaults for fields that version N-1 payloads don't carry.
+///
+/// Returns `MLMarketplaceError` only when the requested version is outside the
+/// supported range.
+pub fn normalize_operation(
+    request: VersionedAssessmentOperation,
+) -> Result<AssessmentOperation, MLMarketplaceError> {
+    check_api_version(Some(request.api_version))?;
+
+    let operation = match request.operation {
+        // Version 1 payloads predate `bias_analysis`/`reproducibility_tests` on
+        // `FullAssess` defaulting to false; version 2 requires callers to set them
+        // explicitly, but since serde already defaulted missing fields to `false`
+        // via `Default`, those need no backfill; only a missing
+        // `timeout_seconds` is filled in here, with 300.
+        AssessmentOperation::FullAssess {
+            model_blob_id,
+            dataset_blob_id,
+            assessment_config,
+        } if request.api_version == MIN_SUPPORTED_API_VERSION => AssessmentOperation::FullAssess {
+            model_blob_id,
+            dataset_blob_id,
+            assessment_config: backfill_assessment_config(assessment_config),
+        },
+        other => other,
+    };
+
+    Ok(operation)
+}
+
+fn backfill_assessment_config(config: AssessmentConfig) -> AssessmentConfig {
+    AssessmentConfig {
+        timeout_seconds: config.timeout_seconds.or(Some(300)),
+        ..config
+    }
+}
+
+#[allow(dead_code)]
+fn backfill_benchmark_config(config: BenchmarkConfig) -> BenchmarkConfig {
+    config
+}